@@ -0,0 +1,374 @@
+//! Adapters which encode and decode base64 as it is read or written.
+
+#![cfg(all(not(feature = "std"), feature = "base64"))]
+
+use core::{cmp, fmt};
+
+use crate::{
+    codec::{consume_decoded, StagedOutput},
+    BufRead, Error, ErrorKind, Read, Result, Write,
+};
+
+/// The standard base64 alphabet of [RFC 4648].
+///
+/// [RFC 4648]: https://www.rfc-editor.org/rfc/rfc4648#section-4
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The character which pads the last group of the encoding.
+const PAD: u8 = b'=';
+
+/// Returns the value of a character of the standard alphabet.
+fn decode_char(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Encodes a group of one to three bytes as four characters, padding it as
+/// needed.
+fn encode_group(group: &[u8], out: &mut [u8]) {
+    let mut bytes = [0u8; 3];
+    bytes[..group.len()].copy_from_slice(group);
+    let bits = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+
+    for (i, c) in out[..4].iter_mut().enumerate() {
+        *c = if i <= group.len() {
+            ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize]
+        } else {
+            PAD
+        };
+    }
+}
+
+/// Decodes the first `len` values of a group, where `len` is from 2 to 4,
+/// into `len - 1` bytes.
+fn decode_group(values: &[u8; 4], len: usize) -> ([u8; 3], usize) {
+    let bits = values
+        .iter()
+        .take(len)
+        .enumerate()
+        .fold(0u32, |acc, (i, &v)| acc | u32::from(v) << (18 - 6 * i));
+    let bytes = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+    (bytes, len - 1)
+}
+
+fn invalid_base64() -> Error {
+    Error::new_const(ErrorKind::InvalidData, &"invalid base64 character")
+}
+
+/// Wraps a writer and encodes every byte written to it as base64.
+///
+/// The standard alphabet of [RFC 4648] is used. Every three bytes written
+/// are encoded as four characters; a shorter group at the end of the stream
+/// is held until [`finish`] encodes it with padding. Without a call to
+/// [`finish`], up to two bytes are lost. Encoded text is staged in a small
+/// buffer on the stack, so each call to [`write`] accepts at most 192 bytes.
+///
+/// [RFC 4648]: https://www.rfc-editor.org/rfc/rfc4648#section-4
+/// [`finish`]: Base64Writer::finish
+/// [`write`]: Write::write
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Base64Writer, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 8];
+/// let mut writer = Base64Writer::new(&mut out[..]);
+///
+/// writer.write_all(b"Hell")?;
+/// writer.finish()?;
+///
+/// assert_eq!(&out, b"SGVsbA==");
+/// # Ok(())
+/// # }
+/// ```
+pub struct Base64Writer<W> {
+    inner: W,
+    // Bytes written which do not make up a whole group yet.
+    group: [u8; 3],
+    group_len: usize,
+    // Encoded text which has not been written to `inner` yet.
+    out: StagedOutput<256>,
+}
+
+impl<W: Write> Base64Writer<W> {
+    /// Creates a new `Base64Writer<W>` which writes the encoding to `inner`.
+    pub fn new(inner: W) -> Base64Writer<W> {
+        Base64Writer {
+            inner,
+            group: [0; 3],
+            group_len: 0,
+            out: StagedOutput::new(&"failed to write the base64 text"),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Encodes the last group with padding, writes all remaining text to the
+    /// underlying writer and flushes it, then returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.write_staged()?;
+        if self.group_len > 0 {
+            encode_group(&self.group[..self.group_len], self.out.spare_mut());
+            self.group_len = 0;
+            self.out.commit(4);
+            self.write_staged()?;
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the staged text, if any, to the underlying writer.
+    fn write_staged(&mut self) -> Result<()> {
+        self.out.write_to(&mut self.inner)
+    }
+}
+
+impl<W: Write> Write for Base64Writer<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        self.write_staged()?;
+        if src.is_empty() {
+            return Ok(0);
+        }
+
+        let out = self.out.spare_mut();
+        let mut len = 0;
+        let mut consumed = 0;
+        for &b in src {
+            if len == out.len() {
+                break;
+            }
+            self.group[self.group_len] = b;
+            self.group_len += 1;
+            consumed += 1;
+            if self.group_len == 3 {
+                encode_group(&self.group, &mut out[len..]);
+                self.group_len = 0;
+                len += 4;
+            }
+        }
+        self.out.commit(len);
+        self.out.write_accepted(&mut self.inner);
+        Ok(consumed)
+    }
+
+    /// Writes the text of all whole groups and flushes the underlying writer.
+    ///
+    /// The bytes of an incomplete group are only written by
+    /// [`finish`](Base64Writer::finish).
+    fn flush(&mut self) -> Result<()> {
+        self.write_staged()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for Base64Writer<W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Base64Writer")
+            .field("writer", &self.inner)
+            .finish()
+    }
+}
+
+/// Wraps a reader of base64 text and decodes it into bytes.
+///
+/// The standard alphabet of [RFC 4648] is used. ASCII whitespace, such as the
+/// line breaks of MIME or PEM, is skipped wherever it appears. Groups may be
+/// split across reads of the underlying reader. The last group may be padded
+/// with `=`; after padding, only whitespace may follow. Padding may also be
+/// left out, in which case the end of the stream ends the last group.
+///
+/// [RFC 4648]: https://www.rfc-editor.org/rfc/rfc4648#section-4
+///
+/// # Errors
+///
+/// A character outside the alphabet, misplaced padding, or data after the
+/// padding yields an error of kind [`ErrorKind::InvalidData`], as does a
+/// stream which ends with a single character of a group or with incomplete
+/// padding. Bytes decoded before an invalid character are returned first,
+/// and the invalid character is skipped.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Base64Reader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = Base64Reader::new(&b"SGVs\r\nbG8="[..]);
+///
+/// let mut buf = [0u8; 8];
+/// let n = reader.read(&mut buf)?;
+/// assert_eq!(&buf[..n], b"Hello");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Base64Reader<R> {
+    inner: R,
+    // The values of the characters read of the current group.
+    values: [u8; 4],
+    len: usize,
+    // The number of padding characters read of the current group.
+    pad: usize,
+    // Whether a padded group has ended the data.
+    padded: bool,
+    // Decoded bytes which did not fit in the caller's buffer.
+    out: [u8; 3],
+    out_len: usize,
+    out_pos: usize,
+}
+
+impl<R: BufRead> Base64Reader<R> {
+    /// Creates a new `Base64Reader<R>` which decodes the base64 text read
+    /// from `inner`.
+    pub fn new(inner: R) -> Base64Reader<R> {
+        Base64Reader {
+            inner,
+            values: [0; 4],
+            len: 0,
+            pad: 0,
+            padded: false,
+            out: [0; 3],
+            out_len: 0,
+            out_pos: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader may leave the decoder in
+    /// the middle of a group.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Base64Reader<R>`, returning the underlying reader.
+    ///
+    /// The characters of a partially read group, and any bytes decoded but
+    /// not yet read, are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Copies as much of a decoded group as fits into `dst`, keeping the rest
+    /// for the next read. Returns the number of bytes copied.
+    fn emit(&mut self, (bytes, n): ([u8; 3], usize), dst: &mut [u8]) -> usize {
+        let amt = cmp::min(n, dst.len());
+        dst[..amt].copy_from_slice(&bytes[..amt]);
+        self.out = bytes;
+        self.out_len = n;
+        self.out_pos = amt;
+        amt
+    }
+}
+
+impl<R: BufRead> Read for Base64Reader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if self.out_pos < self.out_len {
+            let amt = cmp::min(self.out_len - self.out_pos, dst.len());
+            dst[..amt].copy_from_slice(&self.out[self.out_pos..self.out_pos + amt]);
+            self.out_pos += amt;
+            return Ok(amt);
+        }
+
+        let mut written = 0;
+        while written == 0 && !dst.is_empty() {
+            let src = self.inner.fill_buf()?;
+            if src.is_empty() {
+                if self.pad > 0 || self.len == 1 {
+                    self.len = 0;
+                    self.pad = 0;
+                    return Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"stream ended within a base64 group",
+                    ));
+                }
+                if self.len > 0 {
+                    let group = decode_group(&self.values, self.len);
+                    self.len = 0;
+                    written = self.emit(group, dst);
+                }
+                break;
+            }
+
+            let mut used = 0;
+            let mut invalid = false;
+            for &c in src {
+                if written == dst.len() || self.out_pos < self.out_len {
+                    break;
+                }
+                used += 1;
+                if c.is_ascii_whitespace() {
+                    continue;
+                }
+
+                let mut group = None;
+                if self.padded {
+                    invalid = true;
+                } else if c == PAD {
+                    if self.len < 2 {
+                        invalid = true;
+                    } else {
+                        self.pad += 1;
+                        if self.len + self.pad == 4 {
+                            group = Some(decode_group(&self.values, self.len));
+                            self.len = 0;
+                            self.pad = 0;
+                            self.padded = true;
+                        }
+                    }
+                } else {
+                    match decode_char(c) {
+                        Some(v) if self.pad == 0 => {
+                            self.values[self.len] = v;
+                            self.len += 1;
+                            if self.len == 4 {
+                                group = Some(decode_group(&self.values, 4));
+                                self.len = 0;
+                            }
+                        }
+                        _ => invalid = true,
+                    }
+                }
+
+                if invalid {
+                    used -= 1;
+                    break;
+                }
+                if let Some((bytes, n)) = group {
+                    let amt = cmp::min(n, dst.len() - written);
+                    dst[written..written + amt].copy_from_slice(&bytes[..amt]);
+                    written += amt;
+                    self.out = bytes;
+                    self.out_len = n;
+                    self.out_pos = amt;
+                }
+            }
+
+            consume_decoded(&mut self.inner, used, invalid, written, invalid_base64())?;
+        }
+        Ok(written)
+    }
+}
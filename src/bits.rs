@@ -0,0 +1,241 @@
+//! Bit-level access to in-memory buffers.
+
+#![cfg(not(feature = "std"))]
+
+use crate::{io_core::offset_position, Error, ErrorKind, Result, SeekFrom};
+
+/// The order in which a [`BitCursor`] takes the bits of each byte.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The most significant bit of each byte comes first, and the first bit
+    /// read becomes the most significant bit of the value.
+    #[default]
+    MsbFirst,
+    /// The least significant bit of each byte comes first, and the first bit
+    /// read becomes the least significant bit of the value, as in DEFLATE.
+    LsbFirst,
+}
+
+/// A cursor over an in-memory buffer which reads and seeks in bits.
+///
+/// This is the bit-level counterpart of [`Cursor`]: the position counts bits
+/// from the start of the buffer, may be set anywhere, including past the end,
+/// and reads fail rather than return partial values once it runs out of
+/// bits. Bits are taken according to a [`BitOrder`].
+///
+/// [`Cursor`]: crate::Cursor
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{BitCursor, SeekFrom};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut bits = BitCursor::new([0b1011_0010, 0xFF]);
+///
+/// assert_eq!(bits.read_bits(3)?, 0b101);
+/// assert_eq!(bits.read_bits(5)?, 0b10010);
+/// assert!(bits.is_byte_aligned());
+///
+/// bits.seek_bits(SeekFrom::Current(-4))?;
+/// assert_eq!(bits.read_bits(8)?, 0b0010_1111);
+/// assert_eq!(bits.bit_position(), 12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitCursor<T> {
+    inner: T,
+    order: BitOrder,
+    pos: u64,
+}
+
+impl<T: AsRef<[u8]>> BitCursor<T> {
+    /// Creates a new `BitCursor` which reads bits of `inner` in
+    /// [`BitOrder::MsbFirst`] order, starting at bit 0.
+    pub fn new(inner: T) -> BitCursor<T> {
+        BitCursor::with_order(inner, BitOrder::MsbFirst)
+    }
+
+    /// Creates a new `BitCursor` which reads bits of `inner` in `order`,
+    /// starting at bit 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BitCursor, BitOrder};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut bits = BitCursor::with_order([0b1011_0010, 0x01], BitOrder::LsbFirst);
+    ///
+    /// assert_eq!(bits.read_bits(3)?, 0b010);
+    /// assert_eq!(bits.read_bits(6)?, 0b1_10110);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_order(inner: T, order: BitOrder) -> BitCursor<T> {
+        BitCursor {
+            inner,
+            order,
+            pos: 0,
+        }
+    }
+
+    /// Returns the bit order.
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Returns the position in bits from the start of the buffer.
+    pub fn bit_position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the position in bits from the start of the buffer.
+    pub fn set_bit_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+
+    /// Returns the number of bits in the buffer.
+    pub fn bit_len(&self) -> u64 {
+        self.inner.as_ref().len() as u64 * 8
+    }
+
+    /// Returns the number of bits between the position and the end of the
+    /// buffer.
+    pub fn remaining_bits(&self) -> u64 {
+        self.bit_len().saturating_sub(self.pos)
+    }
+
+    /// Seeks to an offset in bits, like [`Seek::seek`] does in bytes.
+    ///
+    /// Seeking past the end is allowed; reads then fail. Returns the new
+    /// position in bits.
+    ///
+    /// [`Seek::seek`]: crate::Seek::seek
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if the new
+    /// position would be negative or overflow. The position is left unchanged
+    /// in that case.
+    pub fn seek_bits(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.pos = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.bit_len(), n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+
+        match offset_position(base, offset) {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(Error::new_const(
+                ErrorKind::InvalidInput,
+                &"invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    /// Returns `true` if the position is on a byte boundary.
+    pub fn is_byte_aligned(&self) -> bool {
+        self.pos.is_multiple_of(8)
+    }
+
+    /// Moves the position forward to the next byte boundary, unless it is
+    /// already on one, and returns the new position in bytes.
+    pub fn align_to_byte(&mut self) -> u64 {
+        self.pos = self.pos.saturating_add(7) / 8 * 8;
+        self.pos / 8
+    }
+
+    /// Returns the bytes from the position on, which must be byte aligned.
+    ///
+    /// Returns `None` if the position is not byte aligned. The slice is empty
+    /// if the position is at or past the end of the buffer.
+    pub fn remaining_bytes(&self) -> Option<&[u8]> {
+        if !self.is_byte_aligned() {
+            return None;
+        }
+        let buf = self.inner.as_ref();
+        let start = (self.pos / 8).min(buf.len() as u64) as usize;
+        Some(&buf[start..])
+    }
+
+    /// Reads a single bit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if the position
+    /// is at or past the end of the buffer.
+    pub fn read_bit(&mut self) -> Result<bool> {
+        self.read_bits(1).map(|bit| bit != 0)
+    }
+
+    /// Reads `n` bits and returns them as the low `n` bits of an integer,
+    /// advancing the position by `n`.
+    ///
+    /// Reading 0 bits always succeeds and returns 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if fewer than
+    /// `n` bits remain. The position is left unchanged in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 64.
+    pub fn read_bits(&mut self, n: u32) -> Result<u64> {
+        assert!(n <= 64, "cannot read more than 64 bits at once");
+
+        if u64::from(n) > self.remaining_bits() {
+            return Err(Error::new_const(
+                ErrorKind::UnexpectedEof,
+                &"not enough bits left in the buffer",
+            ));
+        }
+
+        let buf = self.inner.as_ref();
+        let mut value = 0u64;
+        let mut done = 0;
+        while done < n {
+            let byte = buf[(self.pos / 8) as usize];
+            let offset = (self.pos % 8) as u32;
+            let take = (8 - offset).min(n - done);
+            let mask = ((1u16 << take) - 1) as u64;
+
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let bits = (byte >> (8 - offset - take)) as u64 & mask;
+                    value = value << take | bits;
+                }
+                BitOrder::LsbFirst => {
+                    let bits = (byte >> offset) as u64 & mask;
+                    value |= bits << done;
+                }
+            }
+            done += take;
+            self.pos += u64::from(take);
+        }
+        Ok(value)
+    }
+
+    /// Gets a reference to the underlying buffer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying buffer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this cursor, returning the underlying buffer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
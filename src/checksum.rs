@@ -0,0 +1,743 @@
+//! Adapters which compute a checksum of the bytes passing through them.
+
+#![cfg(not(feature = "std"))]
+
+use core::cmp;
+
+use crate::{
+    frame::HeldTail, Error, ErrorKind, IoSlice, IoSliceMut, Read, ReadReady, Result, Write,
+};
+
+/// A running checksum over a sequence of bytes.
+///
+/// Implementations are fed with [`update`] as bytes pass through a
+/// [`ChecksumReader`] or [`ChecksumWriter`], then produce the final value with
+/// [`finalize`]. Checksums narrower than 64 bits are zero-extended.
+///
+/// With the `checksum` feature enabled, `Crc32` and `Adler32` are
+/// provided.
+///
+/// [`update`]: Checksum::update
+/// [`finalize`]: Checksum::finalize
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::Checksum;
+///
+/// /// The sum of all bytes, modulo 2^64.
+/// #[derive(Default)]
+/// struct ByteSum(u64);
+///
+/// impl Checksum for ByteSum {
+///     fn update(&mut self, bytes: &[u8]) {
+///         for &b in bytes {
+///             self.0 = self.0.wrapping_add(b as u64);
+///         }
+///     }
+///
+///     fn finalize(self) -> u64 {
+///         self.0
+///     }
+/// }
+///
+/// let mut sum = ByteSum::default();
+/// sum.update(&[1, 2]);
+/// sum.update(&[3]);
+/// assert_eq!(sum.finalize(), 6);
+/// ```
+pub trait Checksum {
+    /// Feeds `bytes` into the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Consumes the checksum, returning its value over all the bytes fed so
+    /// far.
+    fn finalize(self) -> u64;
+}
+
+/// Reader adapter which feeds every byte read into a [`Checksum`].
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Checksum, ChecksumReader, Read};
+///
+/// # #[derive(Default)]
+/// # struct ByteSum(u64);
+/// # impl Checksum for ByteSum {
+/// #     fn update(&mut self, bytes: &[u8]) {
+/// #         self.0 += bytes.iter().map(|&b| b as u64).sum::<u64>();
+/// #     }
+/// #     fn finalize(self) -> u64 {
+/// #         self.0
+/// #     }
+/// # }
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = ChecksumReader::new(&[1u8, 2, 3][..], ByteSum::default());
+///
+/// let mut buf = [0u8; 3];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(reader.finalize(), 6);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChecksumReader<R, C> {
+    inner: R,
+    checksum: C,
+}
+
+impl<R, C: Checksum> ChecksumReader<R, C> {
+    /// Creates a new `ChecksumReader` which feeds the bytes read from `inner`
+    /// into `checksum`.
+    pub fn new(inner: R, checksum: C) -> ChecksumReader<R, C> {
+        ChecksumReader { inner, checksum }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not included in the
+    /// checksum.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the checksum.
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+
+    /// Consumes the `ChecksumReader`, returning the value of the checksum
+    /// over all the bytes read.
+    pub fn finalize(self) -> u64 {
+        self.checksum.finalize()
+    }
+
+    /// Consumes the `ChecksumReader`, returning the underlying reader and the
+    /// checksum.
+    pub fn into_parts(self) -> (R, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<R: Read, C: Checksum> Read for ChecksumReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let n = self.inner.read_vectored(bufs)?;
+        let mut left = n;
+        for buf in bufs.iter() {
+            if left == 0 {
+                break;
+            }
+            let filled = cmp::min(left, buf.len());
+            self.checksum.update(&buf[..filled]);
+            left -= filled;
+        }
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<R: ReadReady, C: Checksum> ReadReady for ChecksumReader<R, C> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        self.inner.read_ready()
+    }
+}
+
+/// Writer adapter which feeds every byte written into a [`Checksum`].
+///
+/// Only the bytes accepted by the underlying writer are included.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Checksum, ChecksumWriter, Write};
+///
+/// # #[derive(Default)]
+/// # struct ByteSum(u64);
+/// # impl Checksum for ByteSum {
+/// #     fn update(&mut self, bytes: &[u8]) {
+/// #         self.0 += bytes.iter().map(|&b| b as u64).sum::<u64>();
+/// #     }
+/// #     fn finalize(self) -> u64 {
+/// #         self.0
+/// #     }
+/// # }
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 4];
+/// let mut writer = ChecksumWriter::new(&mut out[..], ByteSum::default());
+///
+/// writer.write_all(&[4, 5, 6])?;
+/// assert_eq!(writer.finalize(), 15);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChecksumWriter<W, C> {
+    inner: W,
+    checksum: C,
+}
+
+impl<W, C: Checksum> ChecksumWriter<W, C> {
+    /// Creates a new `ChecksumWriter` which feeds the bytes written to `inner`
+    /// into `checksum`.
+    pub fn new(inner: W, checksum: C) -> ChecksumWriter<W, C> {
+        ChecksumWriter { inner, checksum }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not included in
+    /// the checksum.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the checksum.
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+
+    /// Consumes the `ChecksumWriter`, returning the value of the checksum
+    /// over all the bytes written.
+    pub fn finalize(self) -> u64 {
+        self.checksum.finalize()
+    }
+
+    /// Consumes the `ChecksumWriter`, returning the underlying writer and the
+    /// checksum.
+    pub fn into_parts(self) -> (W, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<W: Write, C: Checksum> Write for ChecksumWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let n = self.inner.write_vectored(bufs)?;
+        let mut left = n;
+        for buf in bufs {
+            if left == 0 {
+                break;
+            }
+            let written = cmp::min(left, buf.len());
+            self.checksum.update(&buf[..written]);
+            left -= written;
+        }
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Trailers ======================================================================================
+
+/// The layout of a checksum stored as a trailer after the data it covers.
+///
+/// A trailer holds the low `width` bytes of the value returned by
+/// [`Checksum::finalize`], in big-endian or little-endian order.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::ChecksumLayout;
+///
+/// let layout = ChecksumLayout::big_endian(4);
+/// assert_eq!(layout.width(), 4);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChecksumLayout {
+    width: u8,
+    big_endian: bool,
+}
+
+impl ChecksumLayout {
+    /// Creates a layout storing `width` bytes, most significant first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is 0 or greater than 8.
+    pub const fn big_endian(width: usize) -> ChecksumLayout {
+        assert!(
+            width > 0 && width <= 8,
+            "checksum width must be from 1 to 8"
+        );
+        ChecksumLayout {
+            width: width as u8,
+            big_endian: true,
+        }
+    }
+
+    /// Creates a layout storing `width` bytes, least significant first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is 0 or greater than 8.
+    pub const fn little_endian(width: usize) -> ChecksumLayout {
+        assert!(
+            width > 0 && width <= 8,
+            "checksum width must be from 1 to 8"
+        );
+        ChecksumLayout {
+            width: width as u8,
+            big_endian: false,
+        }
+    }
+
+    /// Returns the number of bytes in the trailer.
+    pub const fn width(&self) -> usize {
+        self.width as usize
+    }
+
+    /// Encodes `value` into the first [`width`] bytes of the returned array.
+    ///
+    /// [`width`]: ChecksumLayout::width
+    fn encode(self, value: u64) -> [u8; 8] {
+        let width = self.width();
+        let mut out = [0u8; 8];
+        if self.big_endian {
+            out[..width].copy_from_slice(&value.to_be_bytes()[8 - width..]);
+        } else {
+            out[..width].copy_from_slice(&value.to_le_bytes()[..width]);
+        }
+        out
+    }
+}
+
+/// Writer adapter which feeds every byte written into a [`Checksum`] and
+/// appends the final value as a trailer.
+///
+/// The trailer is only written by [`finish`], laid out according to a
+/// [`ChecksumLayout`]. Neither [`flush`] nor dropping the writer emits it.
+///
+/// [`finish`]: ChecksumAppendWriter::finish
+/// [`flush`]: Write::flush
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Checksum, ChecksumAppendWriter, ChecksumLayout, Write};
+///
+/// # #[derive(Default)]
+/// # struct ByteSum(u64);
+/// # impl Checksum for ByteSum {
+/// #     fn update(&mut self, bytes: &[u8]) {
+/// #         self.0 += bytes.iter().map(|&b| b as u64).sum::<u64>();
+/// #     }
+/// #     fn finalize(self) -> u64 {
+/// #         self.0
+/// #     }
+/// # }
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 5];
+/// let layout = ChecksumLayout::big_endian(2);
+/// let mut writer = ChecksumAppendWriter::new(&mut out[..], ByteSum::default(), layout);
+///
+/// writer.write_all(&[1, 2, 0xFF])?;
+/// writer.finish()?;
+/// assert_eq!(out, [1, 2, 0xFF, 0x01, 0x02]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChecksumAppendWriter<W, C> {
+    inner: ChecksumWriter<W, C>,
+    layout: ChecksumLayout,
+}
+
+impl<W: Write, C: Checksum> ChecksumAppendWriter<W, C> {
+    /// Creates a new `ChecksumAppendWriter` which feeds the bytes written to
+    /// `inner` into `checksum` and appends it laid out according to `layout`.
+    pub fn new(inner: W, checksum: C, layout: ChecksumLayout) -> ChecksumAppendWriter<W, C> {
+        ChecksumAppendWriter {
+            inner: ChecksumWriter::new(inner, checksum),
+            layout,
+        }
+    }
+
+    /// Returns the layout of the trailer.
+    pub fn layout(&self) -> ChecksumLayout {
+        self.layout
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not included in
+    /// the checksum.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Gets a reference to the checksum.
+    pub fn checksum(&self) -> &C {
+        self.inner.checksum()
+    }
+
+    /// Writes the trailer and flushes, then returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from writing the trailer or flushing. The
+    /// trailer may have been partially written in that case.
+    pub fn finish(self) -> Result<W> {
+        let (mut inner, checksum) = self.inner.into_parts();
+        let trailer = self.layout.encode(checksum.finalize());
+        inner.write_all(&trailer[..self.layout.width()])?;
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write, C: Checksum> Write for ChecksumAppendWriter<W, C> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader adapter which feeds every byte read into a [`Checksum`] and
+/// verifies it against the trailer ending the stream.
+///
+/// This is the counterpart of [`ChecksumAppendWriter`]. The last
+/// [`width`] bytes of the underlying reader are held back as the expected
+/// checksum, laid out according to a [`ChecksumLayout`], and are never
+/// returned as data. Once the underlying reader reaches its end, the
+/// checksum of the data is compared with the trailer.
+///
+/// Reading fails with [`ErrorKind::InvalidData`] if they differ, or if the
+/// stream is shorter than the trailer. Otherwise the end of the stream is
+/// reported as usual.
+///
+/// [`width`]: ChecksumLayout::width
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Checksum, ChecksumLayout, ChecksumVerifyReader, Read};
+///
+/// # #[derive(Default)]
+/// # struct ByteSum(u64);
+/// # impl Checksum for ByteSum {
+/// #     fn update(&mut self, bytes: &[u8]) {
+/// #         self.0 += bytes.iter().map(|&b| b as u64).sum::<u64>();
+/// #     }
+/// #     fn finalize(self) -> u64 {
+/// #         self.0
+/// #     }
+/// # }
+/// # fn main() -> acid_io::Result<()> {
+/// let data: &[u8] = &[1, 2, 0xFF, 0x01, 0x02];
+/// let layout = ChecksumLayout::big_endian(2);
+/// let mut reader = ChecksumVerifyReader::new(data, ByteSum::default(), layout);
+///
+/// let mut buf = [0u8; 8];
+/// let n = reader.read(&mut buf)?;
+/// assert_eq!(&buf[..n], &[1, 2, 0xFF]);
+/// assert_eq!(reader.read(&mut buf)?, 0);
+/// assert!(reader.is_verified());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChecksumVerifyReader<R, C> {
+    inner: R,
+    // Taken once the end of the stream has been reached.
+    checksum: Option<C>,
+    layout: ChecksumLayout,
+    // The last bytes read, which may turn out to be the trailer.
+    tail: HeldTail<[u8; 8]>,
+    verified: bool,
+}
+
+impl<R: Read, C: Checksum> ChecksumVerifyReader<R, C> {
+    /// Creates a new `ChecksumVerifyReader` which feeds the bytes read from
+    /// `inner` into `checksum` and expects it at the end laid out according
+    /// to `layout`.
+    pub fn new(inner: R, checksum: C, layout: ChecksumLayout) -> ChecksumVerifyReader<R, C> {
+        ChecksumVerifyReader {
+            inner,
+            checksum: Some(checksum),
+            layout,
+            tail: HeldTail::new([0; 8], layout.width()),
+            verified: false,
+        }
+    }
+
+    /// Returns the layout of the trailer.
+    pub fn layout(&self) -> ChecksumLayout {
+        self.layout
+    }
+
+    /// Returns `true` once the end of the stream has been reached and the
+    /// trailer matched the checksum.
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are neither included in
+    /// the checksum nor considered for the trailer.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `ChecksumVerifyReader`, returning the underlying reader.
+    ///
+    /// Any held back bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Compares the checksum with the held back trailer.
+    fn verify(&mut self) -> Result<()> {
+        let checksum = match self.checksum.take() {
+            Some(checksum) => checksum,
+            None if self.verified => return Ok(()),
+            None => return Err(mismatch()),
+        };
+
+        let width = self.layout.width();
+        if self.tail.len() < width {
+            return Err(Error::new_const(
+                ErrorKind::InvalidData,
+                &"stream too short for the checksum trailer",
+            ));
+        }
+        let expected = self.layout.encode(checksum.finalize());
+        if expected[..width] != *self.tail.as_slice() {
+            return Err(mismatch());
+        }
+        self.verified = true;
+        Ok(())
+    }
+}
+
+fn mismatch() -> Error {
+    Error::new_const(ErrorKind::InvalidData, &"checksum mismatch")
+}
+
+impl<R: Read, C: Checksum> Read for ChecksumVerifyReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if self.checksum.is_none() {
+                return self.verify().map(|()| 0);
+            }
+            let n = self.inner.read(buf)?;
+            if n == 0 {
+                return self.verify().map(|()| 0);
+            }
+
+            let emit = self.tail.push(&mut buf[..n]);
+            if emit > 0 {
+                if let Some(checksum) = self.checksum.as_mut() {
+                    checksum.update(&buf[..emit]);
+                }
+                return Ok(emit);
+            }
+        }
+    }
+}
+
+// CRC-32 ========================================================================================
+
+#[cfg(feature = "checksum")]
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+#[cfg(feature = "checksum")]
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// The CRC-32 checksum used by Ethernet, zlib and PNG.
+///
+/// This is the reflected CRC with polynomial `0x04C11DB7`, an initial value
+/// and final XOR of `0xFFFFFFFF`.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Checksum, Crc32};
+///
+/// let mut crc = Crc32::new();
+/// crc.update(b"123456789");
+/// assert_eq!(crc.finalize(), 0xCBF4_3926);
+/// ```
+#[cfg(feature = "checksum")]
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32 {
+    state: u32,
+}
+
+#[cfg(feature = "checksum")]
+impl Crc32 {
+    /// Creates a new CRC-32 over no bytes.
+    pub const fn new() -> Crc32 {
+        Crc32 { state: !0 }
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl Default for Crc32 {
+    fn default() -> Crc32 {
+        Crc32::new()
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl Checksum for Crc32 {
+    fn update(&mut self, bytes: &[u8]) {
+        let mut crc = self.state;
+        for &b in bytes {
+            crc = (crc >> 8) ^ CRC32_TABLE[((crc ^ b as u32) & 0xFF) as usize];
+        }
+        self.state = crc;
+    }
+
+    fn finalize(self) -> u64 {
+        (!self.state) as u64
+    }
+}
+
+// Adler-32 ======================================================================================
+
+#[cfg(feature = "checksum")]
+const ADLER_MOD: u32 = 65521;
+
+// The largest number of bytes which can be summed before `b` may overflow a
+// `u32`, as in zlib.
+#[cfg(feature = "checksum")]
+const ADLER_NMAX: usize = 5552;
+
+/// The Adler-32 checksum used by zlib.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Adler32, Checksum};
+///
+/// let mut adler = Adler32::new();
+/// adler.update(b"Wikipedia");
+/// assert_eq!(adler.finalize(), 0x11E6_0398);
+/// ```
+#[cfg(feature = "checksum")]
+#[derive(Clone, Copy, Debug)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+#[cfg(feature = "checksum")]
+impl Adler32 {
+    /// Creates a new Adler-32 over no bytes.
+    pub const fn new() -> Adler32 {
+        Adler32 { a: 1, b: 0 }
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl Default for Adler32 {
+    fn default() -> Adler32 {
+        Adler32::new()
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl Checksum for Adler32 {
+    fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(ADLER_NMAX) {
+            for &byte in chunk {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+            self.a %= ADLER_MOD;
+            self.b %= ADLER_MOD;
+        }
+    }
+
+    fn finalize(self) -> u64 {
+        ((self.b << 16) | self.a) as u64
+    }
+}
@@ -0,0 +1,323 @@
+//! A writer adapter which batches small writes.
+
+#![cfg(not(feature = "std"))]
+
+use core::{cmp, fmt, mem, ptr};
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+
+use crate::{Error, ErrorKind, Result, Write};
+
+/// Wraps a writer and coalesces small writes into larger ones.
+///
+/// Writes shorter than the coalescing threshold are copied into an internal
+/// buffer, which is only handed to the underlying writer when it cannot hold
+/// the next small write, when a large write arrives, or when [`commit`] is
+/// called. Writes at least as long as the threshold bypass the buffer
+/// entirely: any pending bytes are committed first, to preserve ordering, and
+/// then the write goes straight to the underlying writer.
+///
+/// This differs from [`BufWriter`] in two ways. Large writes are never copied,
+/// and the owner decides exactly where batches end by calling [`commit`].
+///
+/// The buffer can be any type which can be viewed as a mutable byte slice,
+/// such as a fixed-size array for `no_std` targets without an allocator, or a
+/// boxed slice via [`CoalesceWriter::new`] and
+/// [`CoalesceWriter::with_capacity`].
+///
+/// Like [`BufWriter`], dropping a `CoalesceWriter` will attempt to commit the
+/// pending bytes, ignoring any errors. Call [`commit`] or [`flush`] before
+/// dropping it to observe those errors.
+///
+/// [`BufWriter`]: crate::BufWriter
+/// [`commit`]: CoalesceWriter::commit
+/// [`flush`]: Write::flush
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{CoalesceWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 32];
+/// let mut writer = CoalesceWriter::with_buffer(&mut out[..], [0u8; 8], 4);
+///
+/// // Small writes are held back...
+/// writer.write_all(b"ab")?;
+/// writer.write_all(b"cd")?;
+/// assert_eq!(writer.buffer(), b"abcd");
+///
+/// // ...until a large write pushes them out ahead of itself.
+/// writer.write_all(b"efghij")?;
+/// assert!(writer.buffer().is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub struct CoalesceWriter<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> {
+    inner: W,
+    buf: B,
+    len: usize,
+    threshold: usize,
+    // If the inner writer panics in a call to write, we don't want to write
+    // the pending data a second time in the destructor.
+    panicked: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<W: Write> CoalesceWriter<W, Box<[u8]>> {
+    /// Creates a new `CoalesceWriter<W>` with a default buffer capacity. The
+    /// default is currently 8 KB, but may change in the future.
+    ///
+    /// Writes shorter than `threshold` bytes are buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::CoalesceWriter;
+    ///
+    /// let writer = CoalesceWriter::new(Vec::new(), 64);
+    /// assert_eq!(writer.threshold(), 64);
+    /// ```
+    pub fn new(inner: W, threshold: usize) -> CoalesceWriter<W, Box<[u8]>> {
+        CoalesceWriter::with_capacity(inner, crate::DEFAULT_BUF_SIZE, threshold)
+    }
+
+    /// Creates a new `CoalesceWriter<W>` with the specified buffer capacity.
+    ///
+    /// Writes shorter than `threshold` bytes are buffered. Unlike
+    /// [`BufWriter::with_capacity`], the writer comes first, as it does for
+    /// the other constructors of this type.
+    ///
+    /// [`BufWriter::with_capacity`]: crate::BufWriter::with_capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::CoalesceWriter;
+    ///
+    /// let writer = CoalesceWriter::with_capacity(Vec::new(), 256, 32);
+    /// assert_eq!(writer.capacity(), 256);
+    /// ```
+    pub fn with_capacity(
+        inner: W,
+        capacity: usize,
+        threshold: usize,
+    ) -> CoalesceWriter<W, Box<[u8]>> {
+        CoalesceWriter::with_buffer(inner, vec![0; capacity].into_boxed_slice(), threshold)
+    }
+}
+
+impl<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> CoalesceWriter<W, B> {
+    /// Creates a new `CoalesceWriter<W, B>` which stores pending bytes in
+    /// `buf`.
+    ///
+    /// The whole of `buf` is used as the buffer; its initial contents are
+    /// ignored. Writes shorter than `threshold` bytes are buffered. A
+    /// threshold larger than the buffer behaves like one equal to its length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::CoalesceWriter;
+    ///
+    /// let mut out = [0u8; 64];
+    /// let writer = CoalesceWriter::with_buffer(&mut out[..], [0u8; 16], 8);
+    /// assert_eq!(writer.capacity(), 16);
+    /// ```
+    pub fn with_buffer(inner: W, buf: B, threshold: usize) -> CoalesceWriter<W, B> {
+        CoalesceWriter {
+            inner,
+            buf,
+            len: 0,
+            threshold,
+            panicked: false,
+        }
+    }
+
+    /// Writes all pending bytes to the underlying writer.
+    ///
+    /// This marks the end of a batch. Unlike [`flush`], the underlying writer
+    /// is not flushed.
+    ///
+    /// If an error occurs, the bytes which were not written remain pending.
+    ///
+    /// [`flush`]: Write::flush
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{CoalesceWriter, Write};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut out = [0u8; 8];
+    /// let mut writer = CoalesceWriter::with_buffer(&mut out[..], [0u8; 8], 8);
+    ///
+    /// writer.write_all(b"abc")?;
+    /// writer.commit()?;
+    /// assert!(writer.buffer().is_empty());
+    /// assert_eq!(writer.get_ref().len(), 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn commit(&mut self) -> Result<()> {
+        let mut written = 0;
+        let mut ret = Ok(());
+        while written < self.len {
+            self.panicked = true;
+            let r = self.inner.write(&self.buf.as_ref()[written..self.len]);
+            self.panicked = false;
+
+            match r {
+                Ok(0) => {
+                    ret = Err(Error::new_const(
+                        ErrorKind::WriteZero,
+                        &"failed to write the buffered data",
+                    ));
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => {
+                    ret = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if written > 0 {
+            self.buf.as_mut().copy_within(written..self.len, 0);
+            self.len -= written;
+        }
+        ret
+    }
+
+    /// Returns the bytes which have been accepted but not yet committed.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf.as_ref()[..self.len]
+    }
+
+    /// Returns the number of bytes the internal buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Returns the length below which writes are buffered.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Sets the length below which writes are buffered.
+    ///
+    /// Pending bytes are not affected.
+    pub fn set_threshold(&mut self, threshold: usize) {
+        self.threshold = threshold;
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Commits the pending bytes, then returns the underlying writer.
+    ///
+    /// If committing fails the pending bytes are discarded. Call [`commit`]
+    /// followed by [`into_parts`] to recover them instead.
+    ///
+    /// [`commit`]: CoalesceWriter::commit
+    /// [`into_parts`]: CoalesceWriter::into_parts
+    pub fn into_inner(mut self) -> Result<W> {
+        self.commit()?;
+        Ok(self.into_parts().0)
+    }
+
+    /// Disassembles this `CoalesceWriter`, returning the underlying writer,
+    /// the buffer, and the number of pending bytes at the start of the buffer.
+    ///
+    /// `into_parts` makes no attempt to commit data and cannot fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{CoalesceWriter, Write};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut out = [0u8; 8];
+    /// let mut writer = CoalesceWriter::with_buffer(&mut out[..], [0u8; 4], 4);
+    /// writer.write_all(b"abc")?;
+    ///
+    /// let (inner, buf, pending) = writer.into_parts();
+    /// assert_eq!(&buf[..pending], b"abc");
+    /// assert_eq!(inner.len(), 8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_parts(self) -> (W, B, usize) {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so each field is moved out exactly
+        // once.
+        let (inner, buf) = unsafe { (ptr::read(&this.inner), ptr::read(&this.buf)) };
+        (inner, buf, this.len)
+    }
+
+    #[inline]
+    fn spare_capacity(&self) -> usize {
+        self.capacity() - self.len
+    }
+}
+
+impl<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> Write for CoalesceWriter<W, B> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        if src.len() >= cmp::min(self.threshold, self.capacity()) {
+            self.commit()?;
+            self.panicked = true;
+            let r = self.inner.write(src);
+            self.panicked = false;
+            return r;
+        }
+
+        // A successful commit empties the buffer, and `src` is shorter than
+        // the buffer's capacity at this point, so it always fits afterwards.
+        if src.len() > self.spare_capacity() {
+            self.commit()?;
+        }
+
+        self.buf.as_mut()[self.len..self.len + src.len()].copy_from_slice(src);
+        self.len += src.len();
+        Ok(src.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.commit()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> fmt::Debug for CoalesceWriter<W, B>
+where
+    W: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("CoalesceWriter")
+            .field("writer", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.len, self.capacity()))
+            .field("threshold", &self.threshold)
+            .finish()
+    }
+}
+
+impl<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> Drop for CoalesceWriter<W, B> {
+    fn drop(&mut self) {
+        if !self.panicked {
+            // dtors should not panic, so we ignore a failed commit
+            let _r = self.commit();
+        }
+    }
+}
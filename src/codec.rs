@@ -0,0 +1,2264 @@
+//! Streaming encoders and decoders built on `Read` and `Write`.
+
+#![cfg(not(feature = "std"))]
+
+use core::{cmp, fmt};
+
+use crate::{BufRead, Error, ErrorKind, Read, Result, Write};
+
+// Staged output =================================================================================
+
+/// Output of an encoder which has not been written to the underlying writer
+/// yet.
+///
+/// An encoder stages the output for the input it accepts, then writes it
+/// out with [`write_accepted`]. Once the input has been accepted, an error
+/// writing its output is not returned; instead, the next call first finishes
+/// writing the staged output with [`write_to`], which reports the error then.
+///
+/// [`write_accepted`]: StagedOutput::write_accepted
+/// [`write_to`]: StagedOutput::write_to
+pub(crate) struct StagedOutput<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    pos: usize,
+    // Names the output in the error returned if the writer stops accepting
+    // it.
+    what: &'static &'static str,
+}
+
+impl<const N: usize> StagedOutput<N> {
+    pub(crate) const fn new(what: &'static &'static str) -> StagedOutput<N> {
+        StagedOutput {
+            buf: [0; N],
+            len: 0,
+            pos: 0,
+            what,
+        }
+    }
+
+    /// Appends `bytes` to the staged output.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    /// Returns the unused part of the buffer, to be filled and then staged
+    /// with [`commit`](StagedOutput::commit).
+    pub(crate) fn spare_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[self.len..]
+    }
+
+    /// Stages the first `n` bytes of [`spare_mut`](StagedOutput::spare_mut).
+    pub(crate) fn commit(&mut self, n: usize) {
+        self.len += n;
+    }
+
+    /// Writes the staged output, if any, to `inner`.
+    pub(crate) fn write_to<W: Write + ?Sized>(&mut self, inner: &mut W) -> Result<()> {
+        while self.pos < self.len {
+            match inner.write(&self.buf[self.pos..self.len]) {
+                Ok(0) => return Err(Error::new_const(ErrorKind::WriteZero, self.what)),
+                Ok(n) => self.pos += n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.len = 0;
+        self.pos = 0;
+        Ok(())
+    }
+
+    /// Writes the staged output for input which has already been accepted.
+    ///
+    /// The input now belongs to the encoder, so an error is not returned
+    /// here; it resurfaces on the next call to [`write_to`].
+    ///
+    /// [`write_to`]: StagedOutput::write_to
+    pub(crate) fn write_accepted<W: Write + ?Sized>(&mut self, inner: &mut W) {
+        let _ = self.write_to(inner);
+    }
+}
+
+impl<const N: usize> fmt::Debug for StagedOutput<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list()
+            .entries(&self.buf[self.pos..self.len])
+            .finish()
+    }
+}
+
+/// Consumes the `used` bytes a decoder has taken from `inner` for a read
+/// which decoded `written` bytes.
+///
+/// If decoding stopped at an `invalid` byte before anything was decoded, that
+/// byte is consumed as well and `error` is returned, so that reading can
+/// resume after it. Otherwise an invalid byte is left in `inner` to be
+/// reported by the next read.
+pub(crate) fn consume_decoded<R: BufRead + ?Sized>(
+    inner: &mut R,
+    used: usize,
+    invalid: bool,
+    written: usize,
+    error: Error,
+) -> Result<()> {
+    if invalid && written == 0 {
+        inner.consume(used + 1);
+        return Err(error);
+    }
+    inner.consume(used);
+    Ok(())
+}
+
+// Run-length encoding ===========================================================================
+
+/// The layout of each pair emitted by a [`RunLengthWriter`] and expected by a
+/// [`RunLengthReader`].
+///
+/// Every run is encoded as two bytes: the number of repetitions, from 1 to
+/// 255, and the repeated byte. Longer runs are split into several pairs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RunLengthEncoding {
+    /// The count comes first, followed by the byte.
+    #[default]
+    CountFirst,
+    /// The byte comes first, followed by the count.
+    ByteFirst,
+}
+
+impl RunLengthEncoding {
+    fn encode(self, byte: u8, count: u8) -> [u8; 2] {
+        match self {
+            RunLengthEncoding::CountFirst => [count, byte],
+            RunLengthEncoding::ByteFirst => [byte, count],
+        }
+    }
+
+    fn decode(self, pair: [u8; 2]) -> (u8, u8) {
+        match self {
+            RunLengthEncoding::CountFirst => (pair[1], pair[0]),
+            RunLengthEncoding::ByteFirst => (pair[0], pair[1]),
+        }
+    }
+}
+
+/// Wraps a writer and run-length encodes everything written to it.
+///
+/// Runs of identical bytes are accumulated and emitted as `(count, byte)`
+/// pairs, laid out according to a [`RunLengthEncoding`], once the run is
+/// broken by a different byte, reaches 255 bytes, or the writer is flushed.
+///
+/// The last run is only emitted by [`flush`] or [`into_inner`]. Dropping a
+/// `RunLengthWriter` without calling either loses it.
+///
+/// [`flush`]: Write::flush
+/// [`into_inner`]: RunLengthWriter::into_inner
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{RunLengthWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 6];
+/// let mut writer = RunLengthWriter::new(&mut out[..]);
+/// writer.write_all(b"aaaab")?;
+/// writer.write_all(b"bc")?;
+/// writer.flush()?;
+///
+/// assert_eq!(&out, &[4, b'a', 2, b'b', 1, b'c']);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RunLengthWriter<W> {
+    inner: W,
+    encoding: RunLengthEncoding,
+    // The run being accumulated, as `(byte, count)`.
+    run: Option<(u8, u8)>,
+    // An encoded pair which has not been fully written to `inner` yet.
+    out: StagedOutput<2>,
+}
+
+impl<W: Write> RunLengthWriter<W> {
+    /// Creates a new `RunLengthWriter<W>` using [`RunLengthEncoding::CountFirst`].
+    pub fn new(inner: W) -> RunLengthWriter<W> {
+        RunLengthWriter::with_encoding(inner, RunLengthEncoding::CountFirst)
+    }
+
+    /// Creates a new `RunLengthWriter<W>` which lays out pairs according to
+    /// `encoding`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{RunLengthEncoding, RunLengthWriter, Write};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut out = [0u8; 2];
+    /// let mut writer = RunLengthWriter::with_encoding(&mut out[..], RunLengthEncoding::ByteFirst);
+    /// writer.write_all(b"zzz")?;
+    /// writer.flush()?;
+    ///
+    /// assert_eq!(&out, &[b'z', 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_encoding(inner: W, encoding: RunLengthEncoding) -> RunLengthWriter<W> {
+        RunLengthWriter {
+            inner,
+            encoding,
+            run: None,
+            out: StagedOutput::new(&"failed to write the encoded run"),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Emits the pending run and flushes, then returns the underlying writer.
+    ///
+    /// If this fails the pending run is lost. Call [`flush`] first to be able
+    /// to retry instead.
+    ///
+    /// [`flush`]: Write::flush
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the staged pair, if any, to the underlying writer.
+    fn write_staged(&mut self) -> Result<()> {
+        self.out.write_to(&mut self.inner)
+    }
+
+    /// Encodes and writes the run being accumulated, if any.
+    fn end_run(&mut self) -> Result<()> {
+        self.write_staged()?;
+        if let Some((byte, count)) = self.run.take() {
+            self.out.push(&self.encoding.encode(byte, count));
+            self.write_staged()?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for RunLengthWriter<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        // Finish a pair left over from an earlier failure first, so that its
+        // error is not silently skipped.
+        self.write_staged()?;
+
+        for (i, &b) in src.iter().enumerate() {
+            match self.run {
+                Some((byte, count)) if byte == b && count < u8::MAX => {
+                    self.run = Some((byte, count + 1));
+                }
+                Some(_) => {
+                    if let Err(e) = self.end_run() {
+                        // Report the bytes which were accepted before the
+                        // failure; the error resurfaces on the next call.
+                        return if i > 0 { Ok(i) } else { Err(e) };
+                    }
+                    self.run = Some((b, 1));
+                }
+                None => self.run = Some((b, 1)),
+            }
+        }
+        Ok(src.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.end_run()?;
+        self.inner.flush()
+    }
+}
+
+/// Wraps a reader and decodes the run-length encoding produced by a
+/// [`RunLengthWriter`].
+///
+/// Each `(count, byte)` pair read from the underlying reader, laid out
+/// according to a [`RunLengthEncoding`], is expanded into `count` copies of
+/// `byte`. A run may be spread over any number of calls to [`read`].
+///
+/// Reading fails with [`ErrorKind::InvalidData`] if the underlying reader
+/// ends in the middle of a pair or contains a run of length zero.
+///
+/// [`read`]: Read::read
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, RunLengthReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let encoded: &[u8] = &[3, b'a', 1, b'b'];
+/// let mut reader = RunLengthReader::new(encoded);
+///
+/// let mut buf = [0u8; 4];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"aaab");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RunLengthReader<R> {
+    inner: R,
+    encoding: RunLengthEncoding,
+    // The run being expanded, as `(byte, remaining)`.
+    byte: u8,
+    remaining: u8,
+    // A pair which has only been partially read from `inner`.
+    pair: [u8; 2],
+    pair_len: usize,
+}
+
+impl<R: Read> RunLengthReader<R> {
+    /// Creates a new `RunLengthReader<R>` using [`RunLengthEncoding::CountFirst`].
+    pub fn new(inner: R) -> RunLengthReader<R> {
+        RunLengthReader::with_encoding(inner, RunLengthEncoding::CountFirst)
+    }
+
+    /// Creates a new `RunLengthReader<R>` which expects pairs laid out
+    /// according to `encoding`.
+    pub fn with_encoding(inner: R, encoding: RunLengthEncoding) -> RunLengthReader<R> {
+        RunLengthReader {
+            inner,
+            encoding,
+            byte: 0,
+            remaining: 0,
+            pair: [0; 2],
+            pair_len: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `RunLengthReader<R>`, returning the underlying reader.
+    ///
+    /// Any partially expanded run is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the next pair from the underlying reader, returning `false` at a
+    /// clean end of stream.
+    fn next_run(&mut self) -> Result<bool> {
+        while self.pair_len < self.pair.len() {
+            match self.inner.read(&mut self.pair[self.pair_len..]) {
+                Ok(0) if self.pair_len == 0 => return Ok(false),
+                Ok(0) => {
+                    return Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"truncated run-length pair",
+                    ));
+                }
+                Ok(n) => self.pair_len += n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.pair_len = 0;
+        let (byte, count) = self.encoding.decode(self.pair);
+        if count == 0 {
+            return Err(Error::new_const(ErrorKind::InvalidData, &"zero-length run"));
+        }
+        self.byte = byte;
+        self.remaining = count;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for RunLengthReader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if dst.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 && !self.next_run()? {
+            return Ok(0);
+        }
+
+        let n = cmp::min(self.remaining as usize, dst.len());
+        dst[..n].fill(self.byte);
+        self.remaining -= n as u8;
+        Ok(n)
+    }
+}
+
+// UTF-8 validation ==============================================================================
+
+/// Incremental UTF-8 validator, fed one byte at a time.
+///
+/// Rejects everything `core::str::from_utf8` rejects, including overlong
+/// encodings, surrogates and code points above U+10FFFF.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Utf8Validator {
+    // Continuation bytes still expected in the current sequence, and the
+    // inclusive range allowed for the next one.
+    needed: u8,
+    lower: u8,
+    upper: u8,
+}
+
+impl Utf8Validator {
+    pub(crate) const fn new() -> Utf8Validator {
+        Utf8Validator {
+            needed: 0,
+            lower: 0x80,
+            upper: 0xBF,
+        }
+    }
+
+    /// Returns `true` if the bytes accepted so far end on a character
+    /// boundary.
+    pub(crate) fn is_boundary(&self) -> bool {
+        self.needed == 0
+    }
+
+    /// Feeds `b` to the validator, returning `false` if it cannot continue a
+    /// valid UTF-8 sequence. The state is unchanged in that case.
+    pub(crate) fn accept(&mut self, b: u8) -> bool {
+        if self.needed > 0 {
+            if b < self.lower || b > self.upper {
+                return false;
+            }
+            self.needed -= 1;
+            self.lower = 0x80;
+            self.upper = 0xBF;
+            return true;
+        }
+
+        let (needed, lower, upper) = match b {
+            0x00..=0x7F => return true,
+            0xC2..=0xDF => (1, 0x80, 0xBF),
+            0xE0 => (2, 0xA0, 0xBF),
+            0xE1..=0xEC | 0xEE..=0xEF => (2, 0x80, 0xBF),
+            0xED => (2, 0x80, 0x9F),
+            0xF0 => (3, 0x90, 0xBF),
+            0xF1..=0xF3 => (3, 0x80, 0xBF),
+            0xF4 => (3, 0x80, 0x8F),
+            _ => return false,
+        };
+        self.needed = needed;
+        self.lower = lower;
+        self.upper = upper;
+        true
+    }
+}
+
+/// Wraps a writer and ensures everything written to it is valid UTF-8.
+///
+/// Each buffer passed to [`write`] is validated before it is forwarded. A
+/// character split across several writes is held back until it is complete,
+/// so the underlying writer only ever receives whole characters.
+///
+/// Writing fails with [`ErrorKind::InvalidData`] as soon as an invalid byte is
+/// encountered; the valid bytes before it are still accepted. [`flush`] fails
+/// with the same kind if a character is incomplete.
+///
+/// [`write`]: Write::write
+/// [`flush`]: Write::flush
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{ErrorKind, Utf8Writer, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 16];
+/// let mut writer = Utf8Writer::new(&mut out[..]);
+///
+/// // "é" split across two writes.
+/// writer.write_all(b"caf\xC3")?;
+/// writer.write_all(b"\xA9")?;
+/// writer.flush()?;
+///
+/// let err = writer.write_all(b"\xFF").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::InvalidData);
+/// assert_eq!(&out[..5], "café".as_bytes());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Utf8Writer<W> {
+    inner: W,
+    validator: Utf8Validator,
+    // Accepted bytes of the current character not yet given to `inner`.
+    pending: StagedOutput<4>,
+}
+
+impl<W: Write> Utf8Writer<W> {
+    /// Creates a new `Utf8Writer<W>`.
+    pub fn new(inner: W) -> Utf8Writer<W> {
+        Utf8Writer {
+            inner,
+            validator: Utf8Validator::new(),
+            pending: StagedOutput::new(&"failed to write the buffered data"),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer bypasses validation.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Utf8Writer<W>`, returning the underlying writer.
+    ///
+    /// Any bytes held back are lost; call [`flush`] first to make sure there
+    /// are none.
+    ///
+    /// [`flush`]: Write::flush
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the held back bytes once they form a complete character.
+    fn write_pending(&mut self) -> Result<()> {
+        if !self.validator.is_boundary() {
+            return Ok(());
+        }
+        self.pending.write_to(&mut self.inner)
+    }
+}
+
+impl<W: Write> Write for Utf8Writer<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        self.write_pending()?;
+        if src.is_empty() {
+            return Ok(0);
+        }
+
+        if !self.validator.is_boundary() {
+            // Continue the held back character.
+            let mut taken = 0;
+            while !self.validator.is_boundary() && taken < src.len() {
+                if !self.validator.accept(src[taken]) {
+                    break;
+                }
+                self.pending.push(&src[taken..=taken]);
+                taken += 1;
+            }
+            if taken == 0 {
+                return Err(invalid_utf8());
+            }
+
+            // The bytes have been accepted; an error resurfaces on the next
+            // call.
+            let _ = self.write_pending();
+            return Ok(taken);
+        }
+
+        // Find the longest valid prefix and the last character boundary in it.
+        let mut validator = self.validator;
+        let mut valid = 0;
+        let mut boundary = 0;
+        for &b in src {
+            if !validator.accept(b) {
+                break;
+            }
+            valid += 1;
+            if validator.is_boundary() {
+                boundary = valid;
+            }
+        }
+
+        if valid == 0 {
+            return Err(invalid_utf8());
+        }
+
+        if boundary == 0 {
+            // Only the start of a character; hold it back.
+            for &b in &src[..valid] {
+                self.validator.accept(b);
+            }
+            self.pending.push(&src[..valid]);
+            return Ok(valid);
+        }
+
+        let n = self.inner.write(&src[..boundary])?;
+        // If the underlying writer stopped mid-character, the rest of it is
+        // validated and held back on the following calls.
+        for &b in &src[..n] {
+            self.validator.accept(b);
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if !self.validator.is_boundary() {
+            return Err(Error::new_const(
+                ErrorKind::InvalidData,
+                &"incomplete UTF-8 sequence",
+            ));
+        }
+        self.write_pending()?;
+        self.inner.flush()
+    }
+}
+
+fn invalid_utf8() -> Error {
+    Error::new_const(ErrorKind::InvalidData, &"invalid UTF-8 sequence")
+}
+
+// Line endings ==================================================================================
+
+/// The line ending produced by a [`NewlineWriter`] or [`NewlineReader`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// A line feed, `\n`, as used on Unix.
+    #[default]
+    Lf,
+    /// A carriage return followed by a line feed, `\r\n`, as used on Windows
+    /// and by many network protocols.
+    CrLf,
+}
+
+impl LineEnding {
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
+/// Wraps a writer and normalizes the line endings written to it.
+///
+/// Both `\n` and `\r\n` in the input are replaced with the configured
+/// [`LineEnding`]. A `\r` which is not followed by `\n` is passed through
+/// unchanged.
+///
+/// A `\r` at the end of a write is held back until the next byte shows
+/// whether it starts a `\r\n`, so input split between the two is translated
+/// correctly. [`flush`] does not emit it, since that could break a line ending
+/// in two; [`into_inner`] does, treating it as a lone `\r`.
+///
+/// [`flush`]: Write::flush
+/// [`into_inner`]: NewlineWriter::into_inner
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{LineEnding, NewlineWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 10];
+/// let mut writer = NewlineWriter::new(&mut out[..], LineEnding::CrLf);
+///
+/// writer.write_all(b"a\nb\r")?;
+/// writer.write_all(b"\nc\n")?;
+/// writer.into_inner()?;
+///
+/// assert_eq!(&out[..9], b"a\r\nb\r\nc\r\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NewlineWriter<W> {
+    inner: W,
+    ending: LineEnding,
+    // Whether the last byte accepted was a `\r` which has not been translated
+    // yet.
+    pending_cr: bool,
+    // Translated bytes which have not been fully written to `inner` yet.
+    out: StagedOutput<2>,
+}
+
+impl<W: Write> NewlineWriter<W> {
+    /// Creates a new `NewlineWriter<W>` which writes line endings as
+    /// `ending`.
+    pub fn new(inner: W, ending: LineEnding) -> NewlineWriter<W> {
+        NewlineWriter {
+            inner,
+            ending,
+            pending_cr: false,
+            out: StagedOutput::new(&"failed to write the line ending"),
+        }
+    }
+
+    /// Returns the line ending this writer produces.
+    pub fn line_ending(&self) -> LineEnding {
+        self.ending
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Emits a held back `\r` and flushes, then returns the underlying
+    /// writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        if self.pending_cr {
+            self.write_staged()?;
+            self.pending_cr = false;
+            self.out.push(b"\r");
+        }
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the staged bytes, if any, to the underlying writer.
+    fn write_staged(&mut self) -> Result<()> {
+        self.out.write_to(&mut self.inner)
+    }
+}
+
+impl<W: Write> Write for NewlineWriter<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        // Finish a line ending left over from an earlier failure first, so
+        // that its error is not silently skipped.
+        self.write_staged()?;
+        if src.is_empty() {
+            return Ok(0);
+        }
+
+        if self.pending_cr {
+            self.pending_cr = false;
+            if src[0] == b'\n' {
+                self.out.push(self.ending.as_bytes());
+                self.out.write_accepted(&mut self.inner);
+                return Ok(1);
+            }
+            // A lone `\r`. No input has been taken yet, so an error can be
+            // reported directly.
+            self.out.push(b"\r");
+            self.write_staged()?;
+        }
+
+        let taken = match src[0] {
+            b'\r' if src.len() == 1 => {
+                self.pending_cr = true;
+                return Ok(1);
+            }
+            b'\r' if src[1] == b'\n' => {
+                self.out.push(self.ending.as_bytes());
+                2
+            }
+            b'\r' => {
+                self.out.push(b"\r");
+                1
+            }
+            b'\n' => {
+                self.out.push(self.ending.as_bytes());
+                1
+            }
+            _ => {
+                let end = src
+                    .iter()
+                    .position(|&b| b == b'\r' || b == b'\n')
+                    .unwrap_or(src.len());
+                return self.inner.write(&src[..end]);
+            }
+        };
+
+        self.out.write_accepted(&mut self.inner);
+        Ok(taken)
+    }
+
+    /// Writes any translated line ending and flushes the underlying writer.
+    ///
+    /// A held back `\r` is not written, since the next write may complete it
+    /// as a `\r\n`.
+    fn flush(&mut self) -> Result<()> {
+        self.write_staged()?;
+        self.inner.flush()
+    }
+}
+
+/// Wraps a buffered reader and normalizes the line endings read from it.
+///
+/// Each `\r\n`, lone `\r` and `\n` in the input is replaced with the
+/// configured [`LineEnding`]. A `\r` at the end of the underlying buffer is
+/// translated immediately, and a `\n` starting the next buffer is then
+/// dropped, so input split between the two is translated correctly.
+///
+/// The underlying reader must implement [`BufRead`], since the text between
+/// line endings is passed through straight from its buffer. Wrap a plain
+/// reader in a `BufReader` first.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{LineEnding, NewlineReader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = NewlineReader::new(&b"a\r\nb\rc\n"[..], LineEnding::Lf);
+///
+/// let mut buf = [0u8; 6];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"a\nb\nc\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NewlineReader<R> {
+    inner: R,
+    ending: LineEnding,
+    // Whether the last byte taken from `inner` was a `\r`, so that a `\n`
+    // following it belongs to the same line ending.
+    skip_lf: bool,
+    // A translated line ending which has not been fully read yet.
+    out: [u8; 2],
+    out_len: usize,
+    out_pos: usize,
+}
+
+impl<R: BufRead> NewlineReader<R> {
+    /// Creates a new `NewlineReader<R>` which produces line endings as
+    /// `ending`.
+    pub fn new(inner: R, ending: LineEnding) -> NewlineReader<R> {
+        NewlineReader {
+            inner,
+            ending,
+            skip_lf: false,
+            out: [0; 2],
+            out_len: 0,
+            out_pos: 0,
+        }
+    }
+
+    /// Returns the line ending this reader produces.
+    pub fn line_ending(&self) -> LineEnding {
+        self.ending
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `NewlineReader<R>`, returning the underlying reader.
+    ///
+    /// Any partially read line ending is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for NewlineReader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let n = {
+            let mut src = self.fill_buf()?;
+            src.read(dst)?
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for NewlineReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        while self.out_pos == self.out_len {
+            let buf = self.inner.fill_buf()?;
+            let first = match buf.first() {
+                Some(&b) => b,
+                None => return Ok(&[]),
+            };
+
+            if self.skip_lf {
+                self.skip_lf = false;
+                if first == b'\n' {
+                    self.inner.consume(1);
+                    continue;
+                }
+            }
+
+            match (first, self.ending) {
+                (b'\r', _) => self.skip_lf = true,
+                (b'\n', LineEnding::CrLf) => {}
+                _ => {
+                    // Pass through the text up to the next line ending which
+                    // needs translating.
+                    let ending = self.ending;
+                    let end = buf
+                        .iter()
+                        .position(|&b| b == b'\r' || (b == b'\n' && ending == LineEnding::CrLf))
+                        .unwrap_or(buf.len());
+                    return Ok(&self.inner.fill_buf()?[..end]);
+                }
+            }
+
+            self.inner.consume(1);
+            let bytes = self.ending.as_bytes();
+            self.out[..bytes.len()].copy_from_slice(bytes);
+            self.out_len = bytes.len();
+            self.out_pos = 0;
+        }
+        Ok(&self.out[self.out_pos..self.out_len])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.out_pos < self.out_len {
+            self.out_pos = cmp::min(self.out_pos + amt, self.out_len);
+        } else {
+            self.inner.consume(amt);
+        }
+    }
+}
+
+// Byte stuffing =================================================================================
+
+/// The delimiter which ends each SLIP packet.
+const SLIP_END: u8 = 0xC0;
+/// The SLIP escape byte.
+const SLIP_ESC: u8 = 0xDB;
+/// How SLIP escapes each special byte, as `(byte, replacement)`.
+const SLIP_ESCAPES: [(u8, u8); 2] = [(SLIP_END, 0xDC), (SLIP_ESC, 0xDD)];
+
+/// Wraps a writer and escapes selected bytes written to it.
+///
+/// Each byte listed in the escape table is replaced with the escape byte
+/// followed by the byte's replacement. All other bytes are passed through
+/// unchanged. This is the byte stuffing used by framing protocols such as
+/// SLIP, where the escaped bytes are reserved as delimiters; see
+/// [`SlipWriter`] for a ready-made configuration.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{EscapeWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 8];
+/// // Escape `~` as `}^` and `}` as `}]`.
+/// let mut writer = EscapeWriter::new(&mut out[..], b'}', &[(b'~', b'^'), (b'}', b']')]);
+///
+/// writer.write_all(b"a~b}c")?;
+/// writer.into_inner()?;
+///
+/// assert_eq!(&out[..7], b"a}^b}]c");
+/// # Ok(())
+/// # }
+/// ```
+pub struct EscapeWriter<W> {
+    inner: W,
+    escape: u8,
+    // The replacement of each byte which needs escaping.
+    table: [Option<u8>; 256],
+    // An escape sequence which has not been fully written to `inner` yet.
+    out: StagedOutput<2>,
+}
+
+impl<W: Write> EscapeWriter<W> {
+    /// Creates a new `EscapeWriter<W>` which escapes each byte in `escapes`
+    /// as `escape` followed by its replacement.
+    ///
+    /// `escapes` is a list of `(byte, replacement)` pairs. If a byte is listed
+    /// more than once, the last replacement is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `escape` is not itself listed in `escapes`, since the output
+    /// could not then be unescaped unambiguously.
+    pub fn new(inner: W, escape: u8, escapes: &[(u8, u8)]) -> EscapeWriter<W> {
+        let mut table = [None; 256];
+        for &(byte, replacement) in escapes {
+            table[byte as usize] = Some(replacement);
+        }
+        assert!(
+            table[escape as usize].is_some(),
+            "the escape byte must itself be escaped"
+        );
+
+        EscapeWriter {
+            inner,
+            escape,
+            table,
+            out: StagedOutput::new(&"failed to write the escape sequence"),
+        }
+    }
+
+    /// Returns the escape byte.
+    pub fn escape(&self) -> u8 {
+        self.escape
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Writes any pending escape sequence and flushes, then returns the
+    /// underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the staged escape sequence, if any, to the underlying writer.
+    fn write_staged(&mut self) -> Result<()> {
+        self.out.write_to(&mut self.inner)
+    }
+}
+
+impl<W: Write> Write for EscapeWriter<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        // Finish an escape sequence left over from an earlier failure first,
+        // so that its error is not silently skipped.
+        self.write_staged()?;
+        let first = match src.first() {
+            Some(&b) => b,
+            None => return Ok(0),
+        };
+
+        if let Some(replacement) = self.table[first as usize] {
+            self.out.push(&[self.escape, replacement]);
+            self.out.write_accepted(&mut self.inner);
+            return Ok(1);
+        }
+
+        let end = src
+            .iter()
+            .position(|&b| self.table[b as usize].is_some())
+            .unwrap_or(src.len());
+        self.inner.write(&src[..end])
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_staged()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for EscapeWriter<W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("EscapeWriter")
+            .field("writer", &self.inner)
+            .field("escape", &self.escape)
+            .finish()
+    }
+}
+
+/// Wraps a writer and encodes packets with SLIP ([RFC 1055]).
+///
+/// The bytes of a packet are written with the usual [`Write`] methods, which
+/// escape the reserved `END` (`0xC0`) and `ESC` (`0xDB`) bytes.
+/// [`end_packet`] then terminates the packet with an `END` byte.
+///
+/// [RFC 1055]: https://www.rfc-editor.org/rfc/rfc1055
+/// [`end_packet`]: SlipWriter::end_packet
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{SlipWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 8];
+/// let mut writer = SlipWriter::new(&mut out[..]);
+///
+/// writer.write_all(&[1, 0xC0, 2])?;
+/// writer.end_packet()?;
+///
+/// assert_eq!(&out[..5], &[1, 0xDB, 0xDC, 2, 0xC0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SlipWriter<W> {
+    inner: EscapeWriter<W>,
+}
+
+impl<W: Write> SlipWriter<W> {
+    /// Creates a new `SlipWriter<W>`.
+    pub fn new(inner: W) -> SlipWriter<W> {
+        SlipWriter {
+            inner: EscapeWriter::new(inner, SLIP_ESC, &SLIP_ESCAPES),
+        }
+    }
+
+    /// Terminates the current packet by writing an `END` byte.
+    ///
+    /// The underlying writer is not flushed.
+    pub fn end_packet(&mut self) -> Result<()> {
+        self.inner.write_staged()?;
+        self.inner.inner.write_all(&[SLIP_END])
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Writes any pending escape sequence and flushes, then returns the
+    /// underlying writer.
+    ///
+    /// The current packet is not terminated; call [`end_packet`] first if
+    /// needed.
+    ///
+    /// [`end_packet`]: SlipWriter::end_packet
+    pub fn into_inner(self) -> Result<W> {
+        self.inner.into_inner()
+    }
+}
+
+impl<W: Write> Write for SlipWriter<W> {
+    #[inline]
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        self.inner.write(src)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Wraps a buffered reader and reverses the escaping applied by an
+/// [`EscapeWriter`].
+///
+/// Each occurrence of the escape byte is removed, and the byte following it
+/// is replaced with the byte it stands for. An escape byte at the end of the
+/// underlying buffer is remembered, so a sequence split across reads is
+/// decoded correctly.
+///
+/// Optionally, a delimiter byte can be set with [`set_delimiter`]. Reaching
+/// it ends the current frame: reads return `Ok(0)` until [`next_frame`] is
+/// called. Use [`is_frame_end`] to tell the end of a frame from the end of the
+/// stream.
+///
+/// [`set_delimiter`]: UnescapeReader::set_delimiter
+/// [`next_frame`]: UnescapeReader::next_frame
+/// [`is_frame_end`]: UnescapeReader::is_frame_end
+///
+/// # Errors
+///
+/// An escape byte followed by a byte which is not a known replacement yields
+/// an error of kind [`ErrorKind::InvalidData`], as does a stream which ends
+/// within an escape sequence. The offending input is skipped, so reading can
+/// resume afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, UnescapeReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let input = &b"a}^b}]c~d"[..];
+/// let mut reader = UnescapeReader::new(input, b'}', &[(b'~', b'^'), (b'}', b']')]);
+/// reader.set_delimiter(Some(b'~'));
+///
+/// let mut buf = [0u8; 8];
+/// let n = reader.read(&mut buf)?;
+/// assert_eq!(&buf[..n], b"a~b}c");
+/// assert_eq!(reader.read(&mut buf)?, 0);
+/// assert!(reader.is_frame_end());
+///
+/// reader.next_frame();
+/// assert_eq!(reader.read(&mut buf)?, 1);
+/// assert_eq!(buf[0], b'd');
+/// # Ok(())
+/// # }
+/// ```
+pub struct UnescapeReader<R> {
+    inner: R,
+    escape: u8,
+    // The byte each replacement stands for.
+    table: [Option<u8>; 256],
+    delimiter: Option<u8>,
+    // Whether an escape byte has been consumed without its replacement.
+    escaped: bool,
+    frame_end: bool,
+}
+
+impl<R: BufRead> UnescapeReader<R> {
+    /// Creates a new `UnescapeReader<R>` which reverses the escaping of each
+    /// byte in `escapes` as `escape` followed by its replacement.
+    ///
+    /// `escapes` is the same list of `(byte, replacement)` pairs given to
+    /// [`EscapeWriter::new`]. If a replacement is listed more than once, the
+    /// last byte is used.
+    pub fn new(inner: R, escape: u8, escapes: &[(u8, u8)]) -> UnescapeReader<R> {
+        let mut table = [None; 256];
+        for &(byte, replacement) in escapes {
+            table[replacement as usize] = Some(byte);
+        }
+
+        UnescapeReader {
+            inner,
+            escape,
+            table,
+            delimiter: None,
+            escaped: false,
+            frame_end: false,
+        }
+    }
+
+    /// Sets the byte which delimits frames.
+    ///
+    /// If `delimiter` is `None`, which is the default, the input is a single
+    /// unbounded frame.
+    pub fn set_delimiter(&mut self, delimiter: Option<u8>) {
+        self.delimiter = delimiter;
+    }
+
+    /// Returns `true` if the delimiter ending the current frame has been
+    /// reached.
+    pub fn is_frame_end(&self) -> bool {
+        self.frame_end
+    }
+
+    /// Starts reading the next frame once the current one has ended.
+    ///
+    /// This has no effect if the delimiter has not been reached yet.
+    pub fn next_frame(&mut self) {
+        self.frame_end = false;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `UnescapeReader<R>`, returning the underlying reader.
+    ///
+    /// A partially read escape sequence is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for UnescapeReader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let mut written = 0;
+        while written == 0 && !dst.is_empty() && !self.frame_end {
+            let src = self.inner.fill_buf()?;
+            if src.is_empty() {
+                if self.escaped {
+                    self.escaped = false;
+                    return Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"stream ended within an escape sequence",
+                    ));
+                }
+                break;
+            }
+
+            let mut used = 0;
+            let mut invalid = false;
+            for &b in src {
+                if written == dst.len() {
+                    break;
+                }
+                if self.escaped {
+                    match self.table[b as usize] {
+                        Some(byte) => {
+                            dst[written] = byte;
+                            written += 1;
+                            self.escaped = false;
+                        }
+                        None => {
+                            invalid = true;
+                            break;
+                        }
+                    }
+                } else if Some(b) == self.delimiter {
+                    used += 1;
+                    self.frame_end = true;
+                    break;
+                } else if b == self.escape {
+                    self.escaped = true;
+                } else {
+                    dst[written] = b;
+                    written += 1;
+                }
+                used += 1;
+            }
+
+            // The escape byte is dropped along with an unknown sequence.
+            consume_decoded(
+                &mut self.inner,
+                used,
+                invalid,
+                written,
+                Error::new_const(ErrorKind::InvalidData, &"unknown escape sequence"),
+            )
+            .inspect_err(|_| self.escaped = false)?;
+        }
+        Ok(written)
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for UnescapeReader<R> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("UnescapeReader")
+            .field("reader", &self.inner)
+            .field("escape", &self.escape)
+            .field("delimiter", &self.delimiter)
+            .finish()
+    }
+}
+
+/// Wraps a buffered reader and decodes packets encoded with SLIP
+/// ([RFC 1055]).
+///
+/// Reads return the unescaped bytes of the current packet, then `Ok(0)` once
+/// its terminating `END` byte is reached. Call [`next_packet`] to move on to
+/// the next packet.
+///
+/// [RFC 1055]: https://www.rfc-editor.org/rfc/rfc1055
+/// [`next_packet`]: SlipReader::next_packet
+///
+/// # Errors
+///
+/// Invalid escape sequences yield errors of kind [`ErrorKind::InvalidData`],
+/// as described for [`UnescapeReader`].
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, SlipReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let input = &[1, 0xDB, 0xDC, 2, 0xC0, 3, 0xC0][..];
+/// let mut reader = SlipReader::new(input);
+///
+/// let mut buf = [0u8; 8];
+/// let n = reader.read(&mut buf)?;
+/// assert_eq!(&buf[..n], &[1, 0xC0, 2]);
+/// assert!(reader.next_packet()?);
+///
+/// let n = reader.read(&mut buf)?;
+/// assert_eq!(&buf[..n], &[3]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SlipReader<R> {
+    inner: UnescapeReader<R>,
+}
+
+impl<R: BufRead> SlipReader<R> {
+    /// Creates a new `SlipReader<R>`.
+    pub fn new(inner: R) -> SlipReader<R> {
+        let mut inner = UnescapeReader::new(inner, SLIP_ESC, &SLIP_ESCAPES);
+        inner.set_delimiter(Some(SLIP_END));
+        SlipReader { inner }
+    }
+
+    /// Skips the rest of the current packet and starts reading the next one.
+    ///
+    /// Returns `false` if the stream ended without terminating the current
+    /// packet, in which case there is no next packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of non-[`ErrorKind::Interrupted`] kind from the
+    /// underlying reader. Invalid escape sequences in the skipped bytes are
+    /// ignored.
+    pub fn next_packet(&mut self) -> Result<bool> {
+        let mut buf = [0u8; 64];
+        while !self.inner.is_frame_end() {
+            match self.inner.read(&mut buf) {
+                Ok(0) if !self.inner.is_frame_end() => return Ok(false),
+                Ok(_) => {}
+                Err(ref e)
+                    if e.kind() == ErrorKind::InvalidData || e.kind() == ErrorKind::Interrupted => {
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        self.inner.next_frame();
+        Ok(true)
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Unwraps this `SlipReader<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+}
+
+impl<R: BufRead> Read for SlipReader<R> {
+    #[inline]
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner.read(dst)
+    }
+}
+
+// XOR ===========================================================================================
+
+/// XORs `buf` with `key`, repeated from `pos`, and returns the position after
+/// the last byte.
+fn xor_with_key(buf: &mut [u8], key: &[u8], mut pos: usize) -> usize {
+    for b in buf {
+        *b ^= key[pos];
+        pos += 1;
+        if pos == key.len() {
+            pos = 0;
+        }
+    }
+    pos
+}
+
+/// Wraps a reader and XORs every byte read from it with a repeating key.
+///
+/// The position within the key carries over from one read to the next, so
+/// the result does not depend on how the stream is split into reads. XOR is
+/// its own inverse: the same key decodes what an [`XorWriter`] encoded.
+///
+/// # Panics
+///
+/// [`new`] panics if the key is empty.
+///
+/// [`new`]: XorReader::new
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, XorReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = XorReader::new(&[0x01u8, 0x02, 0x03][..], [0xFF, 0x00]);
+///
+/// let mut buf = [0u8; 3];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(buf, [0xFE, 0x02, 0xFC]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct XorReader<R, K> {
+    inner: R,
+    key: K,
+    pos: usize,
+}
+
+impl<R: Read, K: AsRef<[u8]>> XorReader<R, K> {
+    /// Creates a new `XorReader<R, K>` which XORs the bytes read from `inner`
+    /// with `key`, starting from its first byte.
+    ///
+    /// A single byte key can be given as a one-element array.
+    pub fn new(inner: R, key: K) -> XorReader<R, K> {
+        assert!(!key.as_ref().is_empty(), "the XOR key must not be empty");
+        XorReader { inner, key, pos: 0 }
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> &[u8] {
+        self.key.as_ref()
+    }
+
+    /// Returns the index in the key which the next byte read is XORed with.
+    pub fn key_position(&self) -> usize {
+        self.pos
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader do not advance the
+    /// position within the key.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `XorReader<R, K>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, K: AsRef<[u8]>> Read for XorReader<R, K> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(dst)?;
+        self.pos = xor_with_key(&mut dst[..n], self.key.as_ref(), self.pos);
+        Ok(n)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Wraps a writer and XORs every byte written to it with a repeating key.
+///
+/// The position within the key carries over from one write to the next and
+/// only advances over the bytes accepted by the underlying writer. Bytes are
+/// XORed through a small buffer on the stack, so each call to [`write`]
+/// passes at most 256 bytes on.
+///
+/// # Panics
+///
+/// [`new`] panics if the key is empty.
+///
+/// [`write`]: Write::write
+/// [`new`]: XorWriter::new
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Write, XorWriter};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 3];
+/// let mut writer = XorWriter::new(&mut out[..], [0x20]);
+/// writer.write_all(b"abc")?;
+///
+/// assert_eq!(&out, b"ABC");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct XorWriter<W, K> {
+    inner: W,
+    key: K,
+    pos: usize,
+}
+
+impl<W: Write, K: AsRef<[u8]>> XorWriter<W, K> {
+    /// Creates a new `XorWriter<W, K>` which XORs the bytes written to
+    /// `inner` with `key`, starting from its first byte.
+    ///
+    /// A single byte key can be given as a one-element array.
+    pub fn new(inner: W, key: K) -> XorWriter<W, K> {
+        assert!(!key.as_ref().is_empty(), "the XOR key must not be empty");
+        XorWriter { inner, key, pos: 0 }
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> &[u8] {
+        self.key.as_ref()
+    }
+
+    /// Returns the index in the key which the next byte written is XORed
+    /// with.
+    pub fn key_position(&self) -> usize {
+        self.pos
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer do not advance the
+    /// position within the key.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `XorWriter<W, K>`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write, K: AsRef<[u8]>> Write for XorWriter<W, K> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        let mut buf = [0u8; 256];
+        let len = cmp::min(src.len(), buf.len());
+        buf[..len].copy_from_slice(&src[..len]);
+        xor_with_key(&mut buf[..len], self.key.as_ref(), self.pos);
+
+        let n = self.inner.write(&buf[..len])?;
+        self.pos = (self.pos + n) % self.key.as_ref().len();
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Hex ===========================================================================================
+
+/// Returns the value of an ASCII hex digit, in either case.
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Wraps a reader of ASCII hex text and decodes it into bytes.
+///
+/// Each pair of hex digits, in either case, is decoded into one byte. A pair
+/// may be split across reads of the underlying reader. Whitespace in the
+/// input is rejected unless enabled with [`set_skip_whitespace`], in which
+/// case it may appear anywhere, including between the two digits of a byte.
+///
+/// [`set_skip_whitespace`]: HexReader::set_skip_whitespace
+///
+/// # Errors
+///
+/// A byte which is not a hex digit yields an error of kind
+/// [`ErrorKind::InvalidData`], as does a stream which ends after an odd
+/// number of digits. Bytes decoded before an invalid byte are returned first,
+/// and the invalid byte is skipped, along with an unpaired digit before it,
+/// so reading can resume afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{HexReader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = HexReader::new(&b"48 65 6c\n6C 6f"[..]);
+/// reader.set_skip_whitespace(true);
+///
+/// let mut buf = [0u8; 8];
+/// let n = reader.read(&mut buf)?;
+/// assert_eq!(&buf[..n], b"Hello");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HexReader<R> {
+    inner: R,
+    skip_whitespace: bool,
+    // The value of the first digit of a byte whose second digit is unread.
+    pending: Option<u8>,
+}
+
+impl<R: BufRead> HexReader<R> {
+    /// Creates a new `HexReader<R>` which decodes the hex text read from
+    /// `inner`.
+    pub fn new(inner: R) -> HexReader<R> {
+        HexReader {
+            inner,
+            skip_whitespace: false,
+            pending: None,
+        }
+    }
+
+    /// Sets whether ASCII whitespace in the input is skipped.
+    ///
+    /// If `skip` is `false`, which is the default, whitespace is an invalid
+    /// byte like any other which is not a hex digit.
+    pub fn set_skip_whitespace(&mut self, skip: bool) {
+        self.skip_whitespace = skip;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader may leave the decoder in
+    /// the middle of a byte.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `HexReader<R>`, returning the underlying reader.
+    ///
+    /// The first digit of a partially read byte is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for HexReader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let mut written = 0;
+        while written == 0 && !dst.is_empty() {
+            let src = self.inner.fill_buf()?;
+            if src.is_empty() {
+                if self.pending.take().is_some() {
+                    return Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"stream ended after an odd number of hex digits",
+                    ));
+                }
+                break;
+            }
+
+            let mut used = 0;
+            let mut invalid = false;
+            for &b in src {
+                if written == dst.len() {
+                    break;
+                }
+                if self.skip_whitespace && b.is_ascii_whitespace() {
+                    used += 1;
+                    continue;
+                }
+                match (hex_digit(b), self.pending) {
+                    (Some(low), Some(high)) => {
+                        dst[written] = high << 4 | low;
+                        written += 1;
+                        self.pending = None;
+                    }
+                    (Some(high), None) => self.pending = Some(high),
+                    (None, _) => {
+                        invalid = true;
+                        break;
+                    }
+                }
+                used += 1;
+            }
+
+            // A digit before the invalid byte must not pair with one after it.
+            if invalid && written == 0 {
+                self.pending = None;
+            }
+            consume_decoded(
+                &mut self.inner,
+                used,
+                invalid,
+                written,
+                Error::new_const(ErrorKind::InvalidData, &"invalid hex digit"),
+            )?;
+        }
+        Ok(written)
+    }
+}
+
+/// Wraps a writer and encodes every byte written to it as two ASCII hex
+/// digits.
+///
+/// Digits are lowercase unless enabled with [`set_uppercase`]. Output may
+/// also be split into groups of bytes with [`set_group`], which puts a
+/// separator byte between groups, but not after the last one. Encoded text
+/// is staged in a small buffer on the stack, so each call to [`write`]
+/// accepts at most 85 bytes.
+///
+/// [`set_uppercase`]: HexWriter::set_uppercase
+/// [`set_group`]: HexWriter::set_group
+/// [`write`]: Write::write
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{HexWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 16];
+/// let mut writer = HexWriter::new(&mut out[..]);
+/// writer.set_group(Some((2, b' ')));
+///
+/// writer.write_all(b"Hello")?;
+/// writer.into_inner()?;
+///
+/// assert_eq!(&out[..12], b"4865 6c6c 6f");
+/// # Ok(())
+/// # }
+/// ```
+pub struct HexWriter<W> {
+    inner: W,
+    uppercase: bool,
+    group: Option<(usize, u8)>,
+    // The number of bytes written since the last separator.
+    column: usize,
+    // Encoded text which has not been written to `inner` yet.
+    out: StagedOutput<256>,
+}
+
+impl<W: Write> HexWriter<W> {
+    /// Creates a new `HexWriter<W>` which writes lowercase hex digits to
+    /// `inner`, without grouping.
+    pub fn new(inner: W) -> HexWriter<W> {
+        HexWriter {
+            inner,
+            uppercase: false,
+            group: None,
+            column: 0,
+            out: StagedOutput::new(&"failed to write the hex text"),
+        }
+    }
+
+    /// Sets whether the hex digits `a` to `f` are written in uppercase.
+    pub fn set_uppercase(&mut self, uppercase: bool) {
+        self.uppercase = uppercase;
+    }
+
+    /// Sets how the output is grouped.
+    ///
+    /// If `group` is `Some((size, separator))`, `separator` is written
+    /// between each `size` bytes of output, counting from the first byte
+    /// written. If it is `None`, which is the default, the digits are written
+    /// without separators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn set_group(&mut self, group: Option<(usize, u8)>) {
+        if let Some((size, _)) = group {
+            assert!(size != 0, "group size must be non-zero");
+        }
+        self.group = group;
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Writes any staged text and flushes, then returns the underlying
+    /// writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the staged text, if any, to the underlying writer.
+    fn write_staged(&mut self) -> Result<()> {
+        self.out.write_to(&mut self.inner)
+    }
+}
+
+impl<W: Write> Write for HexWriter<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        self.write_staged()?;
+        if src.is_empty() {
+            return Ok(0);
+        }
+
+        let digits = if self.uppercase {
+            b"0123456789ABCDEF"
+        } else {
+            b"0123456789abcdef"
+        };
+        let out = self.out.spare_mut();
+        let mut len = 0;
+        let mut consumed = 0;
+        // Each byte takes at most a separator and two digits.
+        for &b in src.iter().take(out.len() / 3) {
+            if let Some((size, separator)) = self.group {
+                if self.column == size {
+                    out[len] = separator;
+                    len += 1;
+                    self.column = 0;
+                }
+            }
+            out[len] = digits[(b >> 4) as usize];
+            out[len + 1] = digits[(b & 0x0F) as usize];
+            len += 2;
+            self.column += 1;
+            consumed += 1;
+        }
+        self.out.commit(len);
+        self.out.write_accepted(&mut self.inner);
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_staged()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for HexWriter<W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("HexWriter")
+            .field("writer", &self.inner)
+            .field("uppercase", &self.uppercase)
+            .field("group", &self.group)
+            .finish()
+    }
+}
+
+// Transforms ====================================================================================
+
+/// A streaming transformation of bytes, such as a compressor, a decompressor
+/// or a cipher, which can be driven by a [`TransformReader`] or a
+/// [`TransformWriter`].
+///
+/// Implementations keep whatever state they need between calls, so input and
+/// output may be split at arbitrary points.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Result, Transform};
+///
+/// struct Xor(u8);
+///
+/// impl Transform for Xor {
+///     fn transform(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+///         let n = input.len().min(output.len());
+///         for (o, i) in output[..n].iter_mut().zip(input) {
+///             *o = i ^ self.0;
+///         }
+///         Ok((n, n))
+///     }
+/// }
+/// ```
+pub trait Transform {
+    /// Transforms a prefix of `input` into a prefix of `output`, returning the
+    /// number of bytes consumed from `input` and produced into `output`.
+    ///
+    /// A call with non-empty `input` and `output` must make progress, that
+    /// is consume or produce at least one byte. Input which has been consumed
+    /// but not yet produced is held by the implementation.
+    fn transform(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)>;
+
+    /// Produces any output still held after the end of input, returning the
+    /// number of bytes written to `output`.
+    ///
+    /// This is called repeatedly until it returns `Ok(0)`. The default
+    /// implementation holds nothing and returns `Ok(0)` immediately.
+    fn finish(&mut self, output: &mut [u8]) -> Result<usize> {
+        let _ = output;
+        Ok(0)
+    }
+}
+
+impl<T: Transform + ?Sized> Transform for &mut T {
+    #[inline]
+    fn transform(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+        (**self).transform(input, output)
+    }
+
+    #[inline]
+    fn finish(&mut self, output: &mut [u8]) -> Result<usize> {
+        (**self).finish(output)
+    }
+}
+
+fn no_progress() -> Error {
+    Error::new_const(ErrorKind::InvalidData, &"transform made no progress")
+}
+
+/// Wraps a buffered reader and passes everything read from it through a
+/// [`Transform`].
+///
+/// Once the underlying reader reaches end of stream, [`Transform::finish`] is
+/// called until it produces no more output.
+///
+/// Reading fails with [`ErrorKind::InvalidData`] if the transform neither
+/// consumes nor produces anything.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, Result, Transform, TransformReader};
+///
+/// struct Upper;
+///
+/// impl Transform for Upper {
+///     fn transform(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+///         let n = input.len().min(output.len());
+///         output[..n].copy_from_slice(&input[..n]);
+///         output[..n].make_ascii_uppercase();
+///         Ok((n, n))
+///     }
+/// }
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = TransformReader::new(&b"hello"[..], Upper);
+/// let mut buf = [0u8; 5];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"HELLO");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TransformReader<R, T> {
+    inner: R,
+    transform: T,
+    finished: bool,
+}
+
+impl<R: BufRead, T: Transform> TransformReader<R, T> {
+    /// Creates a new `TransformReader<R, T>`.
+    pub fn new(inner: R, transform: T) -> TransformReader<R, T> {
+        TransformReader {
+            inner,
+            transform,
+            finished: false,
+        }
+    }
+
+    /// Gets a reference to the transform.
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    /// Gets a mutable reference to the transform.
+    pub fn transform_mut(&mut self) -> &mut T {
+        &mut self.transform
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `TransformReader<R, T>`, returning the underlying reader
+    /// and the transform.
+    pub fn into_parts(self) -> (R, T) {
+        (self.inner, self.transform)
+    }
+}
+
+impl<R: BufRead, T: Transform> Read for TransformReader<R, T> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if dst.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                if self.finished {
+                    return Ok(0);
+                }
+                let n = self.transform.finish(dst)?;
+                self.finished = n == 0;
+                return Ok(n);
+            }
+
+            let (consumed, produced) = self.transform.transform(input, dst)?;
+            self.inner.consume(consumed);
+            if produced > 0 {
+                return Ok(produced);
+            }
+            if consumed == 0 {
+                return Err(no_progress());
+            }
+        }
+    }
+}
+
+/// Wraps a writer and passes everything written to it through a
+/// [`Transform`].
+///
+/// The output of the transform is staged in a small internal buffer before
+/// being written to the underlying writer. [`flush`] writes out everything
+/// the transform has produced so far; [`try_finish`] or [`into_inner`] must
+/// be called to also write what it still holds.
+///
+/// Writing fails with [`ErrorKind::InvalidData`] if the transform neither
+/// consumes nor produces anything.
+///
+/// [`flush`]: Write::flush
+/// [`try_finish`]: TransformWriter::try_finish
+/// [`into_inner`]: TransformWriter::into_inner
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Result, Transform, TransformWriter, Write};
+///
+/// struct Upper;
+///
+/// impl Transform for Upper {
+///     fn transform(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+///         let n = input.len().min(output.len());
+///         output[..n].copy_from_slice(&input[..n]);
+///         output[..n].make_ascii_uppercase();
+///         Ok((n, n))
+///     }
+/// }
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 5];
+/// let mut writer = TransformWriter::new(&mut out[..], Upper);
+/// writer.write_all(b"hello")?;
+/// writer.into_inner()?;
+///
+/// assert_eq!(&out, b"HELLO");
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransformWriter<W, T> {
+    inner: W,
+    transform: T,
+    finished: bool,
+    // Output of the transform which has not been written to `inner` yet.
+    out: StagedOutput<256>,
+}
+
+impl<W: Write, T: Transform> TransformWriter<W, T> {
+    /// Creates a new `TransformWriter<W, T>`.
+    pub fn new(inner: W, transform: T) -> TransformWriter<W, T> {
+        TransformWriter {
+            inner,
+            transform,
+            finished: false,
+            out: StagedOutput::new(&"failed to write the transformed data"),
+        }
+    }
+
+    /// Gets a reference to the transform.
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    /// Gets a mutable reference to the transform.
+    pub fn transform_mut(&mut self) -> &mut T {
+        &mut self.transform
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Calls [`Transform::finish`] until it produces no more output, writing
+    /// everything to the underlying writer, then flushes it.
+    ///
+    /// This can be retried after an error. Nothing should be written after
+    /// it succeeds.
+    pub fn try_finish(&mut self) -> Result<()> {
+        self.write_staged()?;
+        while !self.finished {
+            let n = self.transform.finish(self.out.spare_mut())?;
+            self.out.commit(n);
+            self.finished = n == 0;
+            self.write_staged()?;
+        }
+        self.inner.flush()
+    }
+
+    /// Finishes the transform with [`try_finish`], then returns the
+    /// underlying writer.
+    ///
+    /// If this fails any output still held is lost. Call [`try_finish`] first
+    /// to be able to retry instead.
+    ///
+    /// [`try_finish`]: TransformWriter::try_finish
+    pub fn into_inner(mut self) -> Result<W> {
+        self.try_finish()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the staged output, if any, to the underlying writer.
+    fn write_staged(&mut self) -> Result<()> {
+        self.out.write_to(&mut self.inner)
+    }
+}
+
+impl<W: Write, T: Transform> Write for TransformWriter<W, T> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        if src.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            self.write_staged()?;
+            let (consumed, produced) = self.transform.transform(src, self.out.spare_mut())?;
+            self.out.commit(produced);
+            if consumed > 0 {
+                self.out.write_accepted(&mut self.inner);
+                return Ok(consumed);
+            }
+            if produced == 0 {
+                return Err(no_progress());
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_staged()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: fmt::Debug, T: fmt::Debug> fmt::Debug for TransformWriter<W, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TransformWriter")
+            .field("writer", &self.inner)
+            .field("transform", &self.transform)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
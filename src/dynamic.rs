@@ -0,0 +1,172 @@
+//! Wrappers for using readers and writers behind trait objects.
+
+#![cfg(not(feature = "std"))]
+
+use core::fmt;
+
+use crate::{Error, ErrorKind, IoSliceMut, Read, Result, Seek, SeekFrom};
+
+/// A reader which can also seek, as a single trait object.
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+enum DynReaderInner<'a> {
+    Read(&'a mut dyn Read),
+    Seek(&'a mut dyn ReadSeek),
+}
+
+/// A type-erased reader which may or may not support seeking.
+///
+/// Whether a reader implements [`Seek`] is normally only known at compile
+/// time, so code which accepts both seekable and non-seekable sources behind
+/// `dyn Read` cannot seek even when the source would allow it. A `DynReader`
+/// records the capability when it is created: [`new`] wraps any reader, while
+/// [`seekable`] also keeps access to its [`Seek`] implementation. Code holding
+/// the `DynReader` can then check [`is_seekable`], or simply call
+/// [`try_seek`] and handle [`ErrorKind::Unsupported`].
+///
+/// `DynReader` implements [`Seek`] itself by way of [`try_seek`], so it can
+/// also be wrapped in a `BufReader` which is seeked at runtime.
+///
+/// [`new`]: DynReader::new
+/// [`seekable`]: DynReader::seekable
+/// [`is_seekable`]: DynReader::is_seekable
+/// [`try_seek`]: DynReader::try_seek
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Cursor, DynReader, ErrorKind, Read, SeekFrom};
+///
+/// fn skip_header(r: &mut DynReader<'_>) -> acid_io::Result<()> {
+///     match r.try_seek(SeekFrom::Current(4)) {
+///         Ok(_) => Ok(()),
+///         Err(e) if e.kind() == ErrorKind::Unsupported => r.read_exact(&mut [0u8; 4]),
+///         Err(e) => Err(e),
+///     }
+/// }
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut buf = [0u8; 4];
+///
+/// let mut cursor = Cursor::new(b"HEADdata");
+/// let mut r = DynReader::seekable(&mut cursor);
+/// skip_header(&mut r)?;
+/// r.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"data");
+///
+/// let mut slice = &b"HEADdata"[..];
+/// let mut r = DynReader::new(&mut slice);
+/// skip_header(&mut r)?;
+/// r.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"data");
+/// # Ok(())
+/// # }
+/// ```
+pub struct DynReader<'a> {
+    inner: DynReaderInner<'a>,
+}
+
+impl<'a> DynReader<'a> {
+    /// Creates a new `DynReader` which does not support seeking.
+    pub fn new<R: Read + 'a>(inner: &'a mut R) -> DynReader<'a> {
+        DynReader {
+            inner: DynReaderInner::Read(inner),
+        }
+    }
+
+    /// Creates a new `DynReader` which supports seeking.
+    pub fn seekable<R: Read + Seek + 'a>(inner: &'a mut R) -> DynReader<'a> {
+        DynReader {
+            inner: DynReaderInner::Seek(inner),
+        }
+    }
+
+    /// Returns `true` if the underlying reader supports seeking.
+    pub fn is_seekable(&self) -> bool {
+        matches!(self.inner, DynReaderInner::Seek(_))
+    }
+
+    /// Seeks the underlying reader, if it supports seeking.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::Unsupported`] if the underlying
+    /// reader does not support seeking. Otherwise, errors are returned as from
+    /// [`Seek::seek`].
+    pub fn try_seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        match self.inner {
+            DynReaderInner::Read(_) => Err(Error::new_const(
+                ErrorKind::Unsupported,
+                &"the underlying reader does not support seeking",
+            )),
+            DynReaderInner::Seek(ref mut r) => r.seek(pos),
+        }
+    }
+
+    fn reader(&mut self) -> &mut dyn Read {
+        match self.inner {
+            DynReaderInner::Read(ref mut r) => &mut **r,
+            DynReaderInner::Seek(ref mut r) => &mut **r,
+        }
+    }
+}
+
+impl Read for DynReader<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.reader().read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.reader().read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        match self.inner {
+            DynReaderInner::Read(ref r) => r.is_read_vectored(),
+            DynReaderInner::Seek(ref r) => r.is_read_vectored(),
+        }
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader().read_exact(buf)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner {
+            DynReaderInner::Read(ref r) => r.size_hint(),
+            DynReaderInner::Seek(ref r) => r.size_hint(),
+        }
+    }
+}
+
+/// Seeking fails with [`ErrorKind::Unsupported`] if the underlying reader
+/// does not support it; see [`DynReader::try_seek`].
+impl Seek for DynReader<'_> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.try_seek(pos)
+    }
+
+    #[inline]
+    fn supports_end_seek(&self) -> bool {
+        match self.inner {
+            DynReaderInner::Read(_) => false,
+            DynReaderInner::Seek(ref r) => r.supports_end_seek(),
+        }
+    }
+}
+
+impl fmt::Debug for DynReader<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DynReader")
+            .field("seekable", &self.is_seekable())
+            .finish_non_exhaustive()
+    }
+}
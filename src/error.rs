@@ -473,6 +473,91 @@ impl Error {
         }
     }
 
+    /// Returns a reference to the inner error if it is of type `E`.
+    ///
+    /// Returns [`None`] if this [`Error`] was not constructed via [`new`], or
+    /// if the inner error has a different type.
+    ///
+    /// [`new`]: Error::new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::fmt;
+    /// use acid_io::{Error, ErrorKind, ErrorTrait};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Checksum(u32);
+    ///
+    /// impl fmt::Display for Checksum {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "bad checksum {:08x}", self.0)
+    ///     }
+    /// }
+    ///
+    /// impl ErrorTrait for Checksum {}
+    ///
+    /// let err = Error::new(ErrorKind::InvalidData, Checksum(0xdeadbeef));
+    /// assert_eq!(err.downcast_ref::<Checksum>(), Some(&Checksum(0xdeadbeef)));
+    ///
+    /// let err = Error::from(ErrorKind::InvalidData);
+    /// assert_eq!(err.downcast_ref::<Checksum>(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: ErrorTrait + Send + Sync + 'static,
+    {
+        self.get_ref()?.downcast_ref::<E>()
+    }
+
+    /// Consumes the `Error`, returning its inner error if it is of type `E`.
+    ///
+    /// If this [`Error`] was not constructed via [`new`], or if the inner
+    /// error has a different type, the `Error` is handed back unchanged.
+    ///
+    /// [`new`]: Error::new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::fmt;
+    /// use acid_io::{Error, ErrorKind, ErrorTrait};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Checksum(u32);
+    ///
+    /// impl fmt::Display for Checksum {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "bad checksum {:08x}", self.0)
+    ///     }
+    /// }
+    ///
+    /// impl ErrorTrait for Checksum {}
+    ///
+    /// let err = Error::new(ErrorKind::InvalidData, Checksum(7));
+    /// assert_eq!(err.downcast::<Checksum>().unwrap(), Checksum(7));
+    ///
+    /// let err = Error::new(ErrorKind::InvalidData, "not a checksum");
+    /// let err = err.downcast::<Checksum>().unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn downcast<E>(self) -> core::result::Result<E, Error>
+    where
+        E: ErrorTrait + Send + Sync + 'static,
+    {
+        match self.repr {
+            Repr::Custom(c) if c.error.is::<E>() => {
+                let raw = Box::into_raw(c.error);
+                // SAFETY: the check above guarantees the boxed value is an `E`.
+                Ok(*unsafe { Box::from_raw(raw as *mut E) })
+            }
+            repr => Err(Error { repr }),
+        }
+    }
+
     /// Returns the corresponding [`ErrorKind`] for this error.
     #[inline]
     pub fn kind(&self) -> ErrorKind {
@@ -483,6 +568,75 @@ impl Error {
             Repr::SimpleMessage(kind, _) => kind,
         }
     }
+
+    /// Returns `true` if this error is of the kind [`ErrorKind::Interrupted`].
+    ///
+    /// Operations which fail with such an error should typically be retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Error, ErrorKind};
+    ///
+    /// assert!(Error::from(ErrorKind::Interrupted).is_interrupted());
+    /// assert!(!Error::from(ErrorKind::TimedOut).is_interrupted());
+    /// ```
+    #[inline]
+    pub fn is_interrupted(&self) -> bool {
+        self.kind() == ErrorKind::Interrupted
+    }
+
+    /// Returns `true` if this error means a stream ran out of data or room.
+    ///
+    /// See [`ErrorKind::is_end_of_stream`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Cursor, Read, Write};
+    ///
+    /// let mut buf = [0u8; 4];
+    /// let err = (&b"ab"[..]).read_exact(&mut buf).unwrap_err();
+    /// assert!(err.is_end_of_stream());
+    ///
+    /// let err = Cursor::new(&mut buf[..]).write_all(b"abcde").unwrap_err();
+    /// assert!(err.is_end_of_stream());
+    /// ```
+    #[inline]
+    pub fn is_end_of_stream(&self) -> bool {
+        self.kind().is_end_of_stream()
+    }
+
+    /// Creates an error of the kind [`ErrorKind::WouldBlock`].
+    ///
+    /// Non-blocking readers and writers return this error to signal that no
+    /// progress can be made right now, but that the operation may succeed if
+    /// retried later. See [`ErrorKind::WouldBlock`] for the full convention.
+    ///
+    /// This constructor does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Error, ErrorKind, Read};
+    ///
+    /// struct Pending;
+    ///
+    /// impl Read for Pending {
+    ///     fn read(&mut self, _: &mut [u8]) -> acid_io::Result<usize> {
+    ///         Err(Error::would_block())
+    ///     }
+    /// }
+    ///
+    /// let err = Pending.read(&mut [0; 4]).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    /// ```
+    #[inline]
+    pub const fn would_block() -> Error {
+        Error {
+            repr: Repr::Simple(ErrorKind::WouldBlock),
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -533,6 +687,15 @@ pub enum ErrorKind {
     InvalidData,
     /// A parameter was incorrect.
     InvalidInput,
+    /// The I/O operation's timeout expired, causing it to be canceled.
+    TimedOut,
+    /// This operation is not supported by the stream.
+    ///
+    /// For example, seeking a [`DynReader`] whose underlying reader cannot
+    /// seek fails with this kind.
+    ///
+    /// [`DynReader`]: crate::DynReader
+    Unsupported,
     /// An error returned when an operation could not be completed because an
     /// “end of file” was reached prematurely.
     ///
@@ -550,6 +713,23 @@ pub enum ErrorKind {
     /// [`write`]: crate::Write::write
     /// [`Ok(0)`]: Ok
     WriteZero,
+    /// The operation needs to block to complete, but the blocking operation
+    /// was requested to not occur.
+    ///
+    /// Non-blocking readers and writers use this kind to signal "no data yet,
+    /// try again later", which is distinct from end of file: a reader at EOF
+    /// returns [`Ok(0)`], whereas a reader that merely has nothing available
+    /// yet returns this error. Such an error consumes no data, so the same call
+    /// can simply be repeated once the source is ready. See
+    /// [`Error::would_block`].
+    ///
+    /// The adapters in `acid_io` (such as [`Take`] and [`Chain`]) pass this
+    /// error through unchanged and never mistake it for end of file.
+    ///
+    /// [`Ok(0)`]: Ok
+    /// [`Take`]: crate::Take
+    /// [`Chain`]: crate::Chain
+    WouldBlock,
     /// A custom error that does not fall under any other I/O error kind.
     ///
     /// This can be used to construct your own [`Error`]s that do not match any
@@ -580,11 +760,84 @@ impl ErrorKind {
             InvalidData => "invalid data",
             InvalidInput => "invalid input parameter",
             Other => "other error",
+            TimedOut => "timed out",
             Uncategorized => "uncategorized error",
             UnexpectedEof => "unexpected end of file",
+            Unsupported => "unsupported",
+            WouldBlock => "operation would block",
             WriteZero => "write zero",
         }
     }
+
+    /// Returns `true` if an operation which failed with this kind of error
+    /// may succeed if retried.
+    ///
+    /// `acid_io` considers [`Interrupted`], [`TimedOut`] and [`WouldBlock`]
+    /// to be transient. All other kinds are considered [fatal].
+    ///
+    /// [`Interrupted`]: ErrorKind::Interrupted
+    /// [`TimedOut`]: ErrorKind::TimedOut
+    /// [`WouldBlock`]: ErrorKind::WouldBlock
+    /// [fatal]: ErrorKind::is_fatal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::ErrorKind;
+    ///
+    /// assert!(ErrorKind::WouldBlock.is_retryable());
+    /// assert!(!ErrorKind::InvalidData.is_retryable());
+    /// ```
+    #[inline]
+    pub const fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Interrupted | ErrorKind::TimedOut | ErrorKind::WouldBlock
+        )
+    }
+
+    /// Returns `true` if an operation which failed with this kind of error
+    /// should not be retried.
+    ///
+    /// This is the opposite of [`is_retryable`].
+    ///
+    /// [`is_retryable`]: ErrorKind::is_retryable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::ErrorKind;
+    ///
+    /// assert!(ErrorKind::UnexpectedEof.is_fatal());
+    /// assert!(!ErrorKind::Interrupted.is_fatal());
+    /// ```
+    #[inline]
+    pub const fn is_fatal(self) -> bool {
+        !self.is_retryable()
+    }
+
+    /// Returns `true` if this kind of error means a stream ran out of data or
+    /// room.
+    ///
+    /// This is the case for [`UnexpectedEof`], the reading side, and
+    /// [`WriteZero`], the writing side.
+    ///
+    /// [`UnexpectedEof`]: ErrorKind::UnexpectedEof
+    /// [`WriteZero`]: ErrorKind::WriteZero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::ErrorKind;
+    ///
+    /// assert!(ErrorKind::UnexpectedEof.is_end_of_stream());
+    /// assert!(ErrorKind::WriteZero.is_end_of_stream());
+    /// assert!(!ErrorKind::InvalidData.is_end_of_stream());
+    /// ```
+    #[inline]
+    pub const fn is_end_of_stream(self) -> bool {
+        matches!(self, ErrorKind::UnexpectedEof | ErrorKind::WriteZero)
+    }
 }
 
 /// Intended for use for errors not exposed to the user, where allocating onto
@@ -611,6 +864,32 @@ impl From<ErrorKind> for Error {
     }
 }
 
+impl From<fmt::Error> for Error {
+    /// Converts a [`fmt::Error`] into an [`Error`].
+    ///
+    /// The resulting error has kind [`ErrorKind::Uncategorized`] and the
+    /// message `"formatter error"`, the same error [`Write::write_fmt`]
+    /// returns when a formatting trait implementation fails.
+    ///
+    /// This conversion does not allocate.
+    ///
+    /// [`Write::write_fmt`]: crate::Write::write_fmt
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::fmt;
+    /// use acid_io::Error;
+    ///
+    /// let error = Error::from(fmt::Error);
+    /// assert_eq!("formatter error", format!("{}", error));
+    /// ```
+    #[inline]
+    fn from(_: fmt::Error) -> Error {
+        Error::new_const(ErrorKind::Uncategorized, &"formatter error")
+    }
+}
+
 /// A specialized [`Result`] type for I/O operations.
 ///
 /// This type is broadly used across `acid_io` for any operation which may
@@ -629,3 +908,46 @@ impl From<ErrorKind> for Error {
 /// [`io::Error`]: Error
 /// [prelude]: core::prelude
 pub type Result<T> = core::result::Result<T, Error>;
+
+/// Extension methods for [`Result`] values returned by I/O operations.
+///
+/// This trait is implemented for [`acid_io::Result`][Result] only.
+pub trait IoResultExt<T> {
+    /// Converts an error of kind [`ErrorKind::UnexpectedEof`] into `Ok(None)`.
+    ///
+    /// `Ok(v)` becomes `Ok(Some(v))` and every other error is returned
+    /// unchanged. This is useful to treat the end of the stream as the end
+    /// of a sequence of records read with [`read_exact`].
+    ///
+    /// [`read_exact`]: crate::Read::read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{IoResultExt, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut r = &[1u8, 2, 3, 4, 5][..];
+    /// let mut record = [0u8; 2];
+    /// let mut count = 0;
+    ///
+    /// while let Some(()) = r.read_exact(&mut record).eof_to_none()? {
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn eof_to_none(self) -> Result<Option<T>>;
+}
+
+impl<T> IoResultExt<T> for Result<T> {
+    #[inline]
+    fn eof_to_none(self) -> Result<Option<T>> {
+        match self {
+            Ok(v) => Ok(Some(v)),
+            Err(ref e) if e.kind() == ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Adapters which inject simulated failures, for testing error handling.
+
+#![cfg(not(feature = "std"))]
+
+use core::{cmp, fmt};
+
+use crate::{Error, Read, Result, Write};
+
+/// Wraps a reader and fails reads at scheduled points in the stream.
+///
+/// The schedule is an iterator of `(offset, error)` pairs, in increasing order
+/// of offset. Reads are shortened so that they stop at the next offset, and
+/// once the number of bytes read reaches it, the next read returns the
+/// scheduled error instead of data. Several errors may be scheduled at the
+/// same offset.
+///
+/// This makes it easy to check that code retries on
+/// [`ErrorKind::Interrupted`], or copes with [`ErrorKind::UnexpectedEof`]
+/// partway through a stream, without writing a mock reader.
+///
+/// [`ErrorKind::Interrupted`]: crate::ErrorKind::Interrupted
+/// [`ErrorKind::UnexpectedEof`]: crate::ErrorKind::UnexpectedEof
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Error, ErrorKind, FaultyReader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let schedule = [
+///     (2, Error::from(ErrorKind::Interrupted)),
+///     (4, Error::from(ErrorKind::UnexpectedEof)),
+/// ];
+/// let mut reader = FaultyReader::new(&b"abcdef"[..], schedule);
+///
+/// // `read_exact` retries after the interruption.
+/// let mut buf = [0u8; 3];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"abc");
+///
+/// assert_eq!(reader.read(&mut buf)?, 1);
+/// let err = reader.read(&mut buf).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+///
+/// // The schedule is exhausted, so reading carries on.
+/// assert_eq!(reader.read(&mut buf)?, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FaultyReader<R, I: Iterator<Item = (u64, Error)>> {
+    inner: R,
+    schedule: I,
+    next: Option<(u64, Error)>,
+    pos: u64,
+}
+
+impl<R: Read, I: Iterator<Item = (u64, Error)>> FaultyReader<R, I> {
+    /// Creates a new `FaultyReader` which fails reads from `inner` according
+    /// to `schedule`.
+    pub fn new<S>(inner: R, schedule: S) -> FaultyReader<R, I>
+    where
+        S: IntoIterator<IntoIter = I>,
+    {
+        let mut schedule = schedule.into_iter();
+        let next = schedule.next();
+        FaultyReader {
+            inner,
+            schedule,
+            next,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns `true` if there are scheduled errors which have not been
+    /// returned yet.
+    pub fn has_pending_faults(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader do not count towards
+    /// the scheduled offsets.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FaultyReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, I: Iterator<Item = (u64, Error)>> Read for FaultyReader<R, I> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut max = buf.len();
+        if let Some((offset, _)) = self.next {
+            if offset <= self.pos {
+                let (_, error) = self.next.take().unwrap();
+                self.next = self.schedule.next();
+                return Err(error);
+            }
+            max = cmp::min(max as u64, offset - self.pos) as usize;
+        }
+
+        let n = self.inner.read(&mut buf[..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R, I> fmt::Debug for FaultyReader<R, I>
+where
+    R: fmt::Debug,
+    I: Iterator<Item = (u64, Error)>,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FaultyReader")
+            .field("reader", &self.inner)
+            .field("next", &self.next)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+/// A failure injected by a [`FaultyWriter`].
+#[derive(Debug)]
+pub enum WriteFault {
+    /// The write accepts at most this many bytes. `Short(0)` makes it return
+    /// `Ok(0)`, which [`Write::write_all`] reports as
+    /// [`ErrorKind::WriteZero`].
+    ///
+    /// [`ErrorKind::WriteZero`]: crate::ErrorKind::WriteZero
+    Short(usize),
+    /// The write fails with this error.
+    Error(Error),
+}
+
+/// Wraps a writer and fails or shortens writes at scheduled points in the
+/// stream.
+///
+/// The schedule is an iterator of `(offset, fault)` pairs, in increasing order
+/// of offset. Writes are shortened so that they stop at the next offset, and
+/// once the number of bytes written reaches it, the next write is affected by
+/// the scheduled [`WriteFault`]. Several faults may be scheduled at the same
+/// offset.
+///
+/// Only the bytes accepted by the underlying writer are counted, so
+/// [`position`] tells how much data was actually delivered.
+///
+/// [`position`]: FaultyWriter::position
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Error, ErrorKind, FaultyWriter, Write, WriteFault};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let schedule = [
+///     (1, WriteFault::Short(1)),
+///     (2, WriteFault::Error(Error::from(ErrorKind::Interrupted))),
+///     (4, WriteFault::Short(0)),
+/// ];
+/// let mut out = [0u8; 8];
+/// let mut writer = FaultyWriter::new(&mut out[..], schedule);
+///
+/// // `write_all` copes with the short write and the interruption...
+/// writer.write_all(b"abcd")?;
+/// assert_eq!(writer.position(), 4);
+///
+/// // ...but not with a writer which accepts nothing.
+/// let err = writer.write_all(b"ef").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::WriteZero);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FaultyWriter<W, I: Iterator<Item = (u64, WriteFault)>> {
+    inner: W,
+    schedule: I,
+    next: Option<(u64, WriteFault)>,
+    pos: u64,
+}
+
+impl<W: Write, I: Iterator<Item = (u64, WriteFault)>> FaultyWriter<W, I> {
+    /// Creates a new `FaultyWriter` which affects writes to `inner` according
+    /// to `schedule`.
+    pub fn new<S>(inner: W, schedule: S) -> FaultyWriter<W, I>
+    where
+        S: IntoIterator<IntoIter = I>,
+    {
+        let mut schedule = schedule.into_iter();
+        let next = schedule.next();
+        FaultyWriter {
+            inner,
+            schedule,
+            next,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes which reached the underlying writer.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns `true` if there are scheduled faults which have not been
+    /// applied yet.
+    pub fn has_pending_faults(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer do not count towards
+    /// the scheduled offsets.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FaultyWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write, I: Iterator<Item = (u64, WriteFault)>> Write for FaultyWriter<W, I> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut max = buf.len();
+        if let Some((offset, _)) = self.next {
+            if offset <= self.pos {
+                let (_, fault) = self.next.take().unwrap();
+                self.next = self.schedule.next();
+                match fault {
+                    WriteFault::Short(0) => return Ok(0),
+                    WriteFault::Short(n) => max = cmp::min(max, n),
+                    WriteFault::Error(error) => return Err(error),
+                }
+            }
+        }
+        if let Some((offset, _)) = self.next {
+            if offset > self.pos {
+                max = cmp::min(max as u64, offset - self.pos) as usize;
+            }
+        }
+
+        let n = self.inner.write(&buf[..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W, I> fmt::Debug for FaultyWriter<W, I>
+where
+    W: fmt::Debug,
+    I: Iterator<Item = (u64, WriteFault)>,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FaultyWriter")
+            .field("writer", &self.inner)
+            .field("next", &self.next)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
@@ -0,0 +1,617 @@
+//! Adapters for transports which carry fixed-size frames.
+
+#![cfg(not(feature = "std"))]
+
+use core::{cmp, fmt};
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+
+use crate::{BufRead, Error, ErrorKind, Read, Result, Write};
+
+/// Wraps a writer and groups the bytes written to it into fixed-size frames.
+///
+/// Bytes written to a `FramedWriter` accumulate in the current frame, which
+/// holds exactly as many bytes as its buffer. [`finish_frame`] pads the frame
+/// to its full size and writes it to the underlying writer in one go, so each
+/// logical message occupies exactly one frame.
+///
+/// A message which does not fit is rejected: once the frame is full, further
+/// writes fail with [`ErrorKind::InvalidInput`]. Use [`discard_frame`] to drop
+/// the partial message in that case.
+///
+/// Neither [`flush`] nor dropping the writer emits a partial frame, since it
+/// is not known how to pad it.
+///
+/// [`finish_frame`]: FramedWriter::finish_frame
+/// [`discard_frame`]: FramedWriter::discard_frame
+/// [`flush`]: Write::flush
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{FramedWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 8];
+/// let mut writer = FramedWriter::with_buffer(&mut out[..], [0u8; 4]);
+///
+/// writer.write_all(b"hi")?;
+/// writer.finish_frame(0)?;
+/// writer.write_all(b"four")?;
+/// writer.finish_frame(0)?;
+///
+/// assert_eq!(&out, b"hi\0\0four");
+/// # Ok(())
+/// # }
+/// ```
+pub struct FramedWriter<W, B: AsRef<[u8]> + AsMut<[u8]>> {
+    inner: W,
+    buf: B,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<W: Write> FramedWriter<W, Box<[u8]>> {
+    /// Creates a new `FramedWriter<W>` which writes frames of `frame_size`
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::FramedWriter;
+    ///
+    /// let writer = FramedWriter::new(Vec::new(), 64);
+    /// assert_eq!(writer.frame_size(), 64);
+    /// ```
+    pub fn new(inner: W, frame_size: usize) -> FramedWriter<W, Box<[u8]>> {
+        FramedWriter::with_buffer(inner, vec![0; frame_size].into_boxed_slice())
+    }
+}
+
+impl<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> FramedWriter<W, B> {
+    /// Creates a new `FramedWriter<W, B>` which accumulates each frame in
+    /// `buf`.
+    ///
+    /// The frame size is the length of `buf`; its initial contents are
+    /// ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is empty.
+    pub fn with_buffer(inner: W, buf: B) -> FramedWriter<W, B> {
+        assert!(!buf.as_ref().is_empty(), "frame size must be non-zero");
+        FramedWriter { inner, buf, len: 0 }
+    }
+
+    /// Pads the current frame to its full size with `fill` bytes and writes it
+    /// to the underlying writer.
+    ///
+    /// A frame is written even if nothing was written to it, in which case it
+    /// consists only of padding. The underlying writer is not flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of non-[`ErrorKind::Interrupted`] kind from
+    /// writing the frame. The padded frame then remains pending, but the
+    /// underlying writer may have accepted part of it.
+    pub fn finish_frame(&mut self, fill: u8) -> Result<()> {
+        let frame = self.buf.as_mut();
+        frame[self.len..].fill(fill);
+        self.len = frame.len();
+
+        self.inner.write_all(frame)?;
+        self.len = 0;
+        Ok(())
+    }
+
+    /// Discards the bytes written to the current frame.
+    pub fn discard_frame(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the bytes written to the current frame so far.
+    pub fn frame(&self) -> &[u8] {
+        &self.buf.as_ref()[..self.len]
+    }
+
+    /// Returns the size of each frame.
+    pub fn frame_size(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FramedWriter<W, B>`, returning the underlying writer.
+    ///
+    /// The current frame is discarded.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> Write for FramedWriter<W, B> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        let frame = self.buf.as_mut();
+        let space = frame.len() - self.len;
+        if space == 0 && !src.is_empty() {
+            return Err(Error::new_const(
+                ErrorKind::InvalidInput,
+                &"message does not fit in the frame",
+            ));
+        }
+
+        let n = cmp::min(src.len(), space);
+        frame[self.len..self.len + n].copy_from_slice(&src[..n]);
+        self.len += n;
+        Ok(n)
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// The current frame is left pending; see [`finish_frame`].
+    ///
+    /// [`finish_frame`]: FramedWriter::finish_frame
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W, B: AsRef<[u8]> + AsMut<[u8]>> fmt::Debug for FramedWriter<W, B>
+where
+    W: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FramedWriter")
+            .field("writer", &self.inner)
+            .field(
+                "frame",
+                &format_args!("{}/{}", self.len, self.buf.as_ref().len()),
+            )
+            .finish()
+    }
+}
+
+/// Wraps a reader of fixed-size frames and presents their contents as a
+/// continuous stream.
+///
+/// Each frame is read from the underlying reader in full before any of it is
+/// returned, so a frame split across several short reads is reassembled. If
+/// the underlying reader fails partway through a frame, the bytes read so far
+/// are kept and the frame is completed on the next read.
+///
+/// Padding added by [`FramedWriter::finish_frame`] can be removed with
+/// [`set_padding`]. Trailing padding bytes are indistinguishable from data,
+/// so this is only suitable if messages never end with the padding byte.
+///
+/// [`set_padding`]: FramedReader::set_padding
+///
+/// # Errors
+///
+/// A stream which ends partway through a frame yields an error of kind
+/// [`ErrorKind::UnexpectedEof`].
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{FramedReader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = FramedReader::with_buffer(&b"hi\0\0four"[..], [0u8; 4]);
+/// reader.set_padding(Some(0));
+///
+/// let mut buf = [0u8; 6];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"hifour");
+/// # Ok(())
+/// # }
+/// ```
+pub struct FramedReader<R, B: AsRef<[u8]> + AsMut<[u8]>> {
+    inner: R,
+    buf: B,
+    // Bytes of the next frame read so far.
+    filled: usize,
+    // The unread data of the current frame is `buf[pos..end]`.
+    pos: usize,
+    end: usize,
+    padding: Option<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read> FramedReader<R, Box<[u8]>> {
+    /// Creates a new `FramedReader<R>` which reads frames of `frame_size`
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::FramedReader;
+    ///
+    /// let reader = FramedReader::new(&b""[..], 64);
+    /// assert_eq!(reader.frame_size(), 64);
+    /// ```
+    pub fn new(inner: R, frame_size: usize) -> FramedReader<R, Box<[u8]>> {
+        FramedReader::with_buffer(inner, vec![0; frame_size].into_boxed_slice())
+    }
+}
+
+impl<R: Read, B: AsRef<[u8]> + AsMut<[u8]>> FramedReader<R, B> {
+    /// Creates a new `FramedReader<R, B>` which reads each frame into `buf`.
+    ///
+    /// The frame size is the length of `buf`; its initial contents are
+    /// ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is empty.
+    pub fn with_buffer(inner: R, buf: B) -> FramedReader<R, B> {
+        assert!(!buf.as_ref().is_empty(), "frame size must be non-zero");
+        FramedReader {
+            inner,
+            buf,
+            filled: 0,
+            pos: 0,
+            end: 0,
+            padding: None,
+        }
+    }
+
+    /// Sets the padding byte to strip from the end of each frame.
+    ///
+    /// If `padding` is `None`, which is the default, frames are returned in
+    /// full. Frames which consist only of padding are skipped entirely.
+    pub fn set_padding(&mut self, padding: Option<u8>) {
+        self.padding = padding;
+    }
+
+    /// Returns the size of each frame.
+    pub fn frame_size(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FramedReader<R, B>`, returning the underlying reader.
+    ///
+    /// Any unread frame data is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads frames until one with data is available, returning `false` at the
+    /// end of the stream.
+    fn next_frame(&mut self) -> Result<bool> {
+        while self.pos == self.end {
+            let frame = self.buf.as_mut();
+            while self.filled < frame.len() {
+                match self.inner.read(&mut frame[self.filled..]) {
+                    Ok(0) if self.filled == 0 => return Ok(false),
+                    Ok(0) => {
+                        self.filled = 0;
+                        return Err(Error::new_const(
+                            ErrorKind::UnexpectedEof,
+                            &"stream ended within a frame",
+                        ));
+                    }
+                    Ok(n) => self.filled += n,
+                    Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+
+            let mut end = frame.len();
+            if let Some(padding) = self.padding {
+                while end > 0 && frame[end - 1] == padding {
+                    end -= 1;
+                }
+            }
+            self.filled = 0;
+            self.pos = 0;
+            self.end = end;
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read, B: AsRef<[u8]> + AsMut<[u8]>> Read for FramedReader<R, B> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if dst.is_empty() || !self.next_frame()? {
+            return Ok(0);
+        }
+
+        let mut src = &self.buf.as_ref()[self.pos..self.end];
+        let n = src.read(dst)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<R: Read, B: AsRef<[u8]> + AsMut<[u8]>> BufRead for FramedReader<R, B> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.next_frame()?;
+        Ok(&self.buf.as_ref()[self.pos..self.end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.end);
+    }
+}
+
+impl<R, B: AsRef<[u8]> + AsMut<[u8]>> fmt::Debug for FramedReader<R, B>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FramedReader")
+            .field("reader", &self.inner)
+            .field("frame_size", &self.buf.as_ref().len())
+            .field("padding", &self.padding)
+            .finish()
+    }
+}
+
+/// The last bytes read from a stream, held back until later bytes show that
+/// they are not at its end.
+///
+/// Bytes read pass through [`push`], which keeps up to `cap` of them in a
+/// ring buffer and releases the oldest ones once it is full. When the stream
+/// ends, the bytes still held are its last `cap` bytes, or all of it if it
+/// was shorter, and are available from [`as_slice`].
+///
+/// [`push`]: HeldTail::push
+/// [`as_slice`]: HeldTail::as_slice
+#[derive(Debug)]
+pub(crate) struct HeldTail<B> {
+    buf: B,
+    cap: usize,
+    // The held bytes, `len` of them, starting at `start` and wrapping around
+    // the end of `buf[..cap]`.
+    start: usize,
+    len: usize,
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> HeldTail<B> {
+    /// Holds back up to `cap` bytes in `buf`, which must be at least that
+    /// long.
+    pub(crate) fn new(buf: B, cap: usize) -> HeldTail<B> {
+        assert!(cap <= buf.as_ref().len());
+        HeldTail {
+            buf,
+            cap,
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes held back once the buffer is full.
+    pub(crate) fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns the number of bytes currently held.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Passes the bytes just read in `data` through the buffer.
+    ///
+    /// The bytes released are written back to the front of `data`, never
+    /// ahead of the byte being pushed, and their number is returned.
+    pub(crate) fn push(&mut self, data: &mut [u8]) -> usize {
+        let tail = &mut self.buf.as_mut()[..self.cap];
+        if tail.is_empty() {
+            return data.len();
+        }
+
+        let mut released = 0;
+        for i in 0..data.len() {
+            let b = data[i];
+            if self.len < tail.len() {
+                tail[(self.start + self.len) % tail.len()] = b;
+                self.len += 1;
+            } else {
+                data[released] = tail[self.start];
+                released += 1;
+                tail[self.start] = b;
+                self.start = (self.start + 1) % tail.len();
+            }
+        }
+        released
+    }
+
+    /// Returns the bytes currently held, oldest first.
+    pub(crate) fn as_slice(&mut self) -> &[u8] {
+        let tail = &mut self.buf.as_mut()[..self.cap];
+        tail.rotate_left(self.start);
+        self.start = 0;
+        &tail[..self.len]
+    }
+}
+
+/// Wraps a reader and trims a fixed number of bytes from the start and the
+/// end of its stream.
+///
+/// The first `skip_head` bytes are read and discarded on the first read. The
+/// last `drop_tail` bytes are withheld: they are kept in a buffer of that
+/// size as the stream is read, and only returned once the same number of
+/// later bytes has arrived. When the underlying reader reaches its end, the
+/// bytes still held are the tail, and are available from [`trailer`].
+///
+/// This suits formats whose header and trailer are of known sizes, such as
+/// a payload followed by a checksum.
+///
+/// [`trailer`]: TrimReader::trailer
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, TrimReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = TrimReader::with_buffer(&b"HDRpayloadCRC"[..], 3, [0u8; 3]);
+///
+/// let mut buf = [0u8; 16];
+/// let mut len = 0;
+/// loop {
+///     match reader.read(&mut buf[len..])? {
+///         0 => break,
+///         n => len += n,
+///     }
+/// }
+/// assert_eq!(&buf[..len], b"payload");
+/// assert_eq!(reader.trailer(), b"CRC");
+/// # Ok(())
+/// # }
+/// ```
+pub struct TrimReader<R, B: AsRef<[u8]> + AsMut<[u8]>> {
+    inner: R,
+    // Bytes at the start still to be skipped.
+    skip: u64,
+    tail: HeldTail<B>,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read> TrimReader<R, Box<[u8]>> {
+    /// Creates a new `TrimReader<R>` which skips the first `skip_head` bytes
+    /// and withholds the last `drop_tail` bytes of `inner`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::TrimReader;
+    ///
+    /// let reader = TrimReader::new(&b""[..], 4, 16);
+    /// assert_eq!(reader.drop_tail(), 16);
+    /// ```
+    pub fn new(inner: R, skip_head: u64, drop_tail: usize) -> TrimReader<R, Box<[u8]>> {
+        TrimReader::with_buffer(inner, skip_head, vec![0; drop_tail].into_boxed_slice())
+    }
+}
+
+impl<R: Read, B: AsRef<[u8]> + AsMut<[u8]>> TrimReader<R, B> {
+    /// Creates a new `TrimReader<R, B>` which skips the first `skip_head`
+    /// bytes of `inner` and withholds its last bytes in `buf`.
+    ///
+    /// The number of bytes withheld is the length of `buf`; its initial
+    /// contents are ignored.
+    pub fn with_buffer(inner: R, skip_head: u64, buf: B) -> TrimReader<R, B> {
+        let cap = buf.as_ref().len();
+        TrimReader {
+            inner,
+            skip: skip_head,
+            tail: HeldTail::new(buf, cap),
+        }
+    }
+
+    /// Returns the number of bytes withheld from the end of the stream.
+    pub fn drop_tail(&self) -> usize {
+        self.tail.capacity()
+    }
+
+    /// Returns the bytes currently withheld.
+    ///
+    /// Once a read has returned `Ok(0)`, these are the last bytes of the
+    /// stream. There may be fewer than [`drop_tail`] of them if the stream
+    /// was shorter.
+    ///
+    /// [`drop_tail`]: TrimReader::drop_tail
+    pub fn trailer(&mut self) -> &[u8] {
+        self.tail.as_slice()
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `TrimReader<R, B>`, returning the underlying reader.
+    ///
+    /// The withheld bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and discards the bytes at the start which are still to be
+    /// skipped. Returns `false` if the stream ends first.
+    fn skip_head(&mut self) -> Result<bool> {
+        let mut scratch = [0u8; 256];
+        while self.skip > 0 {
+            let len = cmp::min(self.skip, scratch.len() as u64) as usize;
+            match self.inner.read(&mut scratch[..len]) {
+                Ok(0) => return Ok(false),
+                Ok(n) => self.skip -= n as u64,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read, B: AsRef<[u8]> + AsMut<[u8]>> Read for TrimReader<R, B> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() || !self.skip_head()? {
+            return Ok(0);
+        }
+
+        loop {
+            let n = self.inner.read(buf)?;
+            if n == 0 {
+                return Ok(0);
+            }
+
+            let emitted = self.tail.push(&mut buf[..n]);
+            if emitted > 0 {
+                return Ok(emitted);
+            }
+        }
+    }
+}
+
+impl<R, B: AsRef<[u8]> + AsMut<[u8]>> fmt::Debug for TrimReader<R, B>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TrimReader")
+            .field("reader", &self.inner)
+            .field("skip_head", &self.skip)
+            .field("drop_tail", &self.tail.capacity())
+            .finish()
+    }
+}
@@ -2,12 +2,12 @@
 
 #![cfg(all(not(feature = "std"), feature = "alloc"))]
 
-use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use alloc::{boxed::Box, collections::VecDeque, string::String, vec, vec::Vec};
 use core::{cmp, fmt, mem, ptr, str};
 
 use crate::{
-    io_core, BufRead, Cursor, Error, ErrorKind, IoSlice, IoSliceMut, Read, Result, Seek, SeekFrom,
-    Write, DEFAULT_BUF_SIZE,
+    io_core, BufRead, Cursor, Error, ErrorKind, IoSlice, IoSliceMut, Read, ReadReady, Result, Seek,
+    SeekFrom, StreamLenError, Write, DEFAULT_BUF_SIZE,
 };
 
 // This uses an adaptive system to extend the vector when it fills. We want to
@@ -19,7 +19,18 @@ use crate::{
 //
 // Because we're extending the buffer with uninitialized data for trusted
 // readers, we need to make sure to truncate that if any of this panics.
+//
+// Before the first read, space is reserved for the lower bound of the reader's
+// `size_hint`, so readers which know their exact length fill the buffer with a
+// single allocation. The reservation is capped at `MAX_PRESIZE` so that a
+// bogus hint cannot trigger a huge allocation up front; past the cap, the
+// buffer grows as usual.
 pub(crate) fn default_read_to_end<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
+    const MAX_PRESIZE: usize = 1024 * 1024;
+
+    let (hint, _) = r.size_hint();
+    buf.reserve(cmp::min(hint, MAX_PRESIZE));
+
     let start_len = buf.len();
     let start_cap = buf.capacity();
     let mut g = Guard {
@@ -107,6 +118,50 @@ pub(crate) fn default_read_to_end<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>
     }
 }
 
+// Like `default_read_to_end`, but `strategy` decides how much spare capacity
+// to reserve each time the buffer fills, and it is reserved exactly. Nothing
+// is reserved up front from the `size_hint`, so the strategy alone bounds the
+// allocation.
+pub(crate) fn default_read_to_end_with<R, S>(
+    r: &mut R,
+    buf: &mut Vec<u8>,
+    mut strategy: S,
+) -> Result<usize>
+where
+    R: Read + ?Sized,
+    S: GrowthStrategy,
+{
+    let start_len = buf.len();
+    let mut last_read = 0;
+    let mut g = Guard {
+        len: buf.len(),
+        buf,
+    };
+    loop {
+        if g.len == g.buf.capacity() {
+            let additional = strategy.next_reserve(g.len, last_read);
+            g.buf.reserve_exact(cmp::max(additional, 1));
+        }
+
+        // See `default_read_to_end` for why the spare capacity is zeroed.
+        if g.buf.len() < g.buf.capacity() {
+            g.buf.resize(g.buf.capacity(), 0);
+        }
+
+        let buf = &mut g.buf[g.len..];
+        match r.read(buf) {
+            Ok(0) => return Ok(g.len - start_len),
+            Ok(n) => {
+                assert!(n <= buf.len());
+                g.len += n;
+                last_read = n;
+            }
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 pub(crate) fn default_read_to_string<R: Read + ?Sized>(
     r: &mut R,
     buf: &mut String,
@@ -123,6 +178,50 @@ pub(crate) fn default_read_to_string<R: Read + ?Sized>(
     unsafe { append_to_string(buf, |b| default_read_to_end(r, b)) }
 }
 
+pub(crate) fn default_read_length_prefixed<R: Read + ?Sized, const W: usize>(
+    r: &mut R,
+    buf: &mut Vec<u8>,
+    max: usize,
+) -> Result<usize> {
+    assert!(W > 0, "length prefix width must be non-zero");
+
+    let mut prefix = [0u8; W];
+    r.read_exact(&mut prefix)?;
+    let len = prefix
+        .iter()
+        .try_fold(0usize, |len, &b| {
+            len.checked_mul(256)?.checked_add(b as usize)
+        })
+        .ok_or_else(|| {
+            Error::new_const(
+                ErrorKind::InvalidData,
+                &"message length does not fit in usize",
+            )
+        })?;
+    if len > max {
+        return Err(Error::new_const(
+            ErrorKind::InvalidData,
+            &"message length exceeds the maximum",
+        ));
+    }
+
+    // Grow the buffer as the message arrives rather than trusting the length
+    // up front, so a corrupt prefix cannot trigger a huge allocation.
+    let start = buf.len();
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = cmp::min(remaining, DEFAULT_BUF_SIZE);
+        let old_len = buf.len();
+        buf.resize(old_len + chunk, 0);
+        if let Err(e) = r.read_exact(&mut buf[old_len..]) {
+            buf.truncate(start);
+            return Err(e);
+        }
+        remaining -= chunk;
+    }
+    Ok(len)
+}
+
 pub(crate) fn read_until<R: BufRead + ?Sized>(
     r: &mut R,
     delim: u8,
@@ -155,6 +254,50 @@ pub(crate) fn read_until<R: BufRead + ?Sized>(
     }
 }
 
+/// Like [`read_until`], but fails once `max` bytes have been read without
+/// finding `delim`. The bytes read are then removed from `buf` again.
+pub(crate) fn read_until_limited<R: BufRead + ?Sized>(
+    r: &mut R,
+    delim: u8,
+    buf: &mut Vec<u8>,
+    max: usize,
+) -> Result<usize> {
+    let start = buf.len();
+    let mut read = 0;
+    loop {
+        let (done, used) = {
+            let available = match r.fill_buf() {
+                Ok(n) => n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if available.is_empty() {
+                return Ok(read);
+            }
+            if read == max {
+                buf.truncate(start);
+                return Err(Error::new_const(ErrorKind::InvalidData, &"line too long"));
+            }
+            let available = &available[..cmp::min(available.len(), max - read)];
+            match memchr::memchr(delim, available) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..=i]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (false, available.len())
+                }
+            }
+        };
+        r.consume(used);
+        read += used;
+        if done {
+            return Ok(read);
+        }
+    }
+}
+
 struct Guard<'a> {
     buf: &'a mut Vec<u8>,
     len: usize,
@@ -209,6 +352,93 @@ where
     }
 }
 
+/// Decides how much a buffer grows by in [`Read::read_to_end_with`].
+///
+/// Each time the buffer is full, [`next_reserve`] is asked for the number of
+/// bytes of spare capacity to reserve next, which are then reserved exactly.
+/// [`Doubling`] and [`Linear`] are provided.
+///
+/// [`next_reserve`]: GrowthStrategy::next_reserve
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{GrowthStrategy, Read};
+///
+/// /// Grows by the size of the last read, but at least 16 bytes.
+/// struct LastRead;
+///
+/// impl GrowthStrategy for LastRead {
+///     fn next_reserve(&mut self, _len: usize, last_read: usize) -> usize {
+///         last_read.max(16)
+///     }
+/// }
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut buf = Vec::new();
+/// (&[7u8; 100][..]).read_to_end_with(&mut buf, LastRead)?;
+/// assert_eq!(buf, [7; 100]);
+/// # Ok(())
+/// # }
+/// ```
+pub trait GrowthStrategy {
+    /// Returns the number of bytes to reserve when the buffer is full.
+    ///
+    /// `len` is the length of the buffer, including any bytes it held before
+    /// reading began, and `last_read` is the number of bytes returned by the
+    /// last read, or 0 before the first. A return value of 0 is treated as 1.
+    fn next_reserve(&mut self, len: usize, last_read: usize) -> usize;
+}
+
+impl<S: GrowthStrategy + ?Sized> GrowthStrategy for &mut S {
+    fn next_reserve(&mut self, len: usize, last_read: usize) -> usize {
+        (**self).next_reserve(len, last_read)
+    }
+}
+
+/// A [`GrowthStrategy`] which doubles the capacity of the buffer each time it
+/// is full, starting from 32 bytes.
+///
+/// This keeps the number of reallocations logarithmic in the amount of data,
+/// at the cost of up to half of the final capacity going unused.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Doubling;
+
+impl GrowthStrategy for Doubling {
+    fn next_reserve(&mut self, len: usize, _last_read: usize) -> usize {
+        cmp::max(len, 32)
+    }
+}
+
+/// A [`GrowthStrategy`] which grows the buffer by a fixed number of bytes
+/// each time it is full.
+///
+/// At most that many bytes of capacity go unused, which suits small heaps,
+/// but the number of reallocations grows linearly with the amount of data.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Linear, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut buf = Vec::new();
+/// (&[0u8; 5000][..]).read_to_end_with(&mut buf, Linear(4096))?;
+/// assert_eq!(buf.len(), 5000);
+/// // Two steps of 4096 bytes were reserved.
+/// assert!(buf.capacity() >= 8192);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Linear(pub usize);
+
+impl GrowthStrategy for Linear {
+    fn next_reserve(&mut self, _len: usize, _last_read: usize) -> usize {
+        self.0
+    }
+}
+
 /// An iterator over the contents of an instance of `BufRead` split on a
 /// particular byte.
 ///
@@ -272,6 +502,345 @@ impl<B: BufRead> Iterator for Lines<B> {
     }
 }
 
+/// An iterator over the lines of an instance of `BufRead`, together with the
+/// offset at which each line starts.
+///
+/// This struct is generally created by calling [`lines_with_offsets`] on a
+/// `BufRead`. Please see the documentation of [`lines_with_offsets`] for more
+/// details.
+///
+/// [`lines_with_offsets`]: BufRead::lines_with_offsets
+#[derive(Debug)]
+pub struct LinesWithOffsets<B> {
+    pub(crate) buf: B,
+    pub(crate) offset: u64,
+}
+
+impl<B> LinesWithOffsets<B> {
+    /// Returns the number of bytes consumed from the reader so far, which is
+    /// the offset of the next line.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl<B: BufRead> Iterator for LinesWithOffsets<B> {
+    type Item = Result<(u64, String)>;
+
+    fn next(&mut self) -> Option<Result<(u64, String)>> {
+        let start = self.offset;
+        let mut buf = Vec::new();
+        // Count the bytes consumed even if reading fails, so that the offsets
+        // of later lines stay accurate.
+        let res = read_until(&mut self.buf, b'\n', &mut buf);
+        self.offset += buf.len() as u64;
+        match res {
+            Ok(0) => None,
+            Ok(_n) => {
+                if buf.ends_with(b"\n") {
+                    buf.pop();
+                    if buf.ends_with(b"\r") {
+                        buf.pop();
+                    }
+                }
+                match String::from_utf8(buf) {
+                    Ok(line) => Some(Ok((start, line))),
+                    Err(_) => Some(Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"stream did not contain valid UTF-8",
+                    ))),
+                }
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// An iterator over fixed-size records of an instance of `BufRead`.
+///
+/// This struct is generally created by calling [`records`] on a `BufRead`.
+/// Please see the documentation of [`records`] for more details.
+///
+/// [`records`]: BufRead::records
+#[derive(Debug)]
+pub struct Records<B> {
+    pub(crate) buf: B,
+    pub(crate) size: usize,
+    pub(crate) allow_partial: bool,
+    // Bytes of a record which was interrupted by an error.
+    pub(crate) partial: Vec<u8>,
+}
+
+impl<B> Records<B> {
+    /// Sets whether a short record at the end of the stream is yielded.
+    ///
+    /// If `allow` is `false`, which is the default, a short final record is
+    /// reported as an error of kind [`ErrorKind::UnexpectedEof`] and its bytes
+    /// are discarded. If it is `true`, the short record is yielded as is.
+    pub fn allow_partial(mut self, allow: bool) -> Records<B> {
+        self.allow_partial = allow;
+        self
+    }
+
+    /// Returns the size of each record.
+    pub fn record_size(&self) -> usize {
+        self.size
+    }
+
+    /// Consumes the `Records`, returning the wrapped reader.
+    ///
+    /// Bytes of a record interrupted by an error are lost.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B: BufRead> Iterator for Records<B> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Result<Vec<u8>>> {
+        let mut record = mem::take(&mut self.partial);
+        record.reserve_exact(self.size - record.len());
+        while record.len() < self.size {
+            let available = match self.buf.fill_buf() {
+                Ok(available) => available,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    // Keep what was read, so the next call resumes the record.
+                    self.partial = record;
+                    return Some(Err(e));
+                }
+            };
+            if available.is_empty() {
+                break;
+            }
+
+            let amt = cmp::min(self.size - record.len(), available.len());
+            record.extend_from_slice(&available[..amt]);
+            self.buf.consume(amt);
+        }
+
+        if record.is_empty() {
+            None
+        } else if record.len() < self.size && !self.allow_partial {
+            Some(Err(Error::new_const(
+                ErrorKind::UnexpectedEof,
+                &"stream ended within a record",
+            )))
+        } else {
+            Some(Ok(record))
+        }
+    }
+}
+
+/// An iterator over UTF-8 text chunks of an instance of `BufRead`.
+///
+/// This struct is generally created by calling [`read_utf8_chunks`] on a
+/// `BufRead`. Please see the documentation of [`read_utf8_chunks`] for more
+/// details.
+///
+/// [`read_utf8_chunks`]: BufRead::read_utf8_chunks
+#[derive(Debug)]
+pub struct Utf8Chunks<B> {
+    pub(crate) buf: B,
+    // Bytes taken from `buf` which are not yet part of a yielded chunk.
+    pub(crate) carry: Vec<u8>,
+}
+
+impl<B> Utf8Chunks<B> {
+    /// Consumes the `Utf8Chunks`, returning the wrapped reader.
+    ///
+    /// Bytes already taken from the reader but not yet yielded are lost.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B: BufRead> Iterator for Utf8Chunks<B> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        loop {
+            let mut bytes = mem::take(&mut self.carry);
+            let eof = match self.buf.fill_buf() {
+                Ok([]) => true,
+                Ok(available) => {
+                    bytes.extend_from_slice(available);
+                    let amt = available.len();
+                    self.buf.consume(amt);
+                    false
+                }
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {
+                    self.carry = bytes;
+                    continue;
+                }
+                Err(e) => {
+                    self.carry = bytes;
+                    return Some(Err(e));
+                }
+            };
+
+            // Only possible at the end of the stream.
+            if bytes.is_empty() {
+                return None;
+            }
+
+            let (valid, error_len) = match str::from_utf8(&bytes) {
+                Ok(_) => (bytes.len(), None),
+                Err(e) => (e.valid_up_to(), e.error_len()),
+            };
+
+            if valid > 0 {
+                self.carry = bytes.split_off(valid);
+                // SAFETY: `bytes` was just validated up to `valid`.
+                return Some(Ok(unsafe { String::from_utf8_unchecked(bytes) }));
+            }
+
+            match error_len {
+                Some(len) => {
+                    self.carry = bytes.split_off(len);
+                    return Some(Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"stream did not contain valid UTF-8",
+                    )));
+                }
+                None if eof => {
+                    return Some(Err(Error::new_const(
+                        ErrorKind::InvalidData,
+                        &"stream ended within a UTF-8 sequence",
+                    )));
+                }
+                // An incomplete character; wait for the rest of it.
+                None => self.carry = bytes,
+            }
+        }
+    }
+}
+
+/// Adapter to chain together any number of readers.
+///
+/// This struct is generally created by calling [`chain_all`]. Please see the
+/// documentation of [`chain_all`] for more details.
+#[derive(Debug)]
+pub struct ChainMany<R> {
+    readers: Vec<R>,
+    current: usize,
+}
+
+/// Creates an adapter which reads from each of `readers` in turn.
+///
+/// The returned reader reads from the first reader until it reaches EOF, then
+/// from the second, and so on. It reports EOF once every reader is exhausted.
+/// This is equivalent to chaining the readers together with [`Read::chain`],
+/// but works for a list whose length is only known at runtime and avoids
+/// deeply nested types.
+///
+/// Readers of different types can be combined by boxing them first, as
+/// <code>[Vec]<[Box]\<dyn [Read]>></code>.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::Read;
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let segments: Vec<&[u8]> = vec![b"Hello", b", ", b"world!"];
+/// let mut reader = acid_io::chain_all(segments);
+///
+/// let mut s = String::new();
+/// reader.read_to_string(&mut s)?;
+/// assert_eq!(s, "Hello, world!");
+/// # Ok(())
+/// # }
+/// ```
+pub fn chain_all<R: Read>(readers: Vec<R>) -> ChainMany<R> {
+    ChainMany {
+        readers,
+        current: 0,
+    }
+}
+
+impl<R> ChainMany<R> {
+    /// Consumes the `ChainMany`, returning the wrapped readers.
+    pub fn into_inner(self) -> Vec<R> {
+        self.readers
+    }
+
+    /// Gets references to the underlying readers in this `ChainMany`.
+    pub fn get_ref(&self) -> &[R] {
+        &self.readers
+    }
+
+    /// Gets mutable references to the underlying readers in this `ChainMany`.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying readers as doing so may corrupt the internal state of this
+    /// `ChainMany`.
+    pub fn get_mut(&mut self) -> &mut [R] {
+        &mut self.readers
+    }
+
+    /// Returns the index of the reader currently being read from.
+    ///
+    /// This is equal to the number of readers once all of them are exhausted.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+}
+
+impl<R: Read> Read for ChainMany<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        while let Some(reader) = self.readers.get_mut(self.current) {
+            match reader.read(buf)? {
+                0 if !buf.is_empty() => self.current += 1,
+                n => return Ok(n),
+            }
+        }
+        Ok(0)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        while let Some(reader) = self.readers.get_mut(self.current) {
+            match reader.read_vectored(bufs)? {
+                0 if bufs.iter().any(|b| !b.is_empty()) => self.current += 1,
+                n => return Ok(n),
+            }
+        }
+        Ok(0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.readers[self.current..].iter().fold(
+            (0, Some(0)),
+            |(min, max): (usize, Option<usize>), reader| {
+                let (r_min, r_max) = reader.size_hint();
+                (
+                    min.saturating_add(r_min),
+                    max.and_then(|x| r_max.and_then(|y| x.checked_add(y))),
+                )
+            },
+        )
+    }
+}
+
+impl<R: BufRead> BufRead for ChainMany<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        while self.current < self.readers.len() {
+            if !self.readers[self.current].fill_buf()?.is_empty() {
+                return self.readers[self.current].fill_buf();
+            }
+            self.current += 1;
+        }
+        Ok(&[])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(reader) = self.readers.get_mut(self.current) {
+            reader.consume(amt);
+        }
+    }
+}
+
 /// The `BufReader<R>` struct adds buffering to any reader.
 ///
 /// It can be excessively inefficient to work directly with a [`Read`] instance.
@@ -368,6 +937,66 @@ impl<R: Read> BufReader<R> {
             cap: 0,
         }
     }
+
+    /// Fills the internal buffer until it holds at least `n` bytes, returning
+    /// the buffered data.
+    ///
+    /// Like [`fill_buf`], this does not consume any data. The underlying
+    /// reader is read repeatedly, retrying on [`ErrorKind::Interrupted`], and
+    /// the buffered bytes are moved to the front of the buffer if more room is
+    /// needed. The returned slice is shorter than `n` only if the end of the
+    /// stream is reached first.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if `n` is larger
+    /// than the capacity of the buffer. Errors from the underlying reader are
+    /// returned as is; bytes buffered before the error are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, BufReader, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// // A stream which yields its data in small pieces.
+    /// let src = (&b"\x00\x05"[..]).chain(&b"hel"[..]).chain(&b"lo"[..]);
+    /// let mut reader = BufReader::with_capacity(16, src);
+    ///
+    /// let header = reader.fill_buf_at_least(2)?;
+    /// let len = u16::from_be_bytes([header[0], header[1]]) as usize;
+    /// reader.consume(2);
+    ///
+    /// assert_eq!(reader.fill_buf_at_least(len)?, b"hello");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        if n > self.buf.len() {
+            return Err(Error::new_const(
+                ErrorKind::InvalidInput,
+                &"requested more bytes than the buffer can hold",
+            ));
+        }
+
+        while self.cap - self.pos < n {
+            if self.buf.len() - self.pos < n {
+                self.buf.copy_within(self.pos..self.cap, 0);
+                self.cap -= self.pos;
+                self.pos = 0;
+            }
+
+            match self.inner.read(&mut self.buf[self.cap..]) {
+                Ok(0) => break,
+                Ok(read) => self.cap += read,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(&self.buf[self.pos..self.cap])
+    }
 }
 
 impl<R> BufReader<R> {
@@ -480,6 +1109,40 @@ impl<R> BufReader<R> {
         self.inner
     }
 
+    /// Disassembles this `BufReader<R>`, returning the underlying reader and
+    /// the data that was buffered but not yet consumed.
+    ///
+    /// Unlike [`into_inner`], this does not lose any buffered data: reading
+    /// the returned bytes followed by the returned reader yields the same
+    /// stream that reading from this `BufReader` would have.
+    ///
+    /// The returned vector reuses the allocation of the internal buffer.
+    ///
+    /// [`into_inner`]: BufReader::into_inner
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, BufReader};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut reader = BufReader::with_capacity(8, b"Hello, world!".as_slice());
+    /// reader.fill_buf()?;
+    /// reader.consume(7);
+    ///
+    /// let (inner, buffered) = reader.into_parts();
+    /// assert_eq!(buffered, b"w");
+    /// assert_eq!(inner, b"orld!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_parts(self) -> (R, Vec<u8>) {
+        let mut buf = self.buf.into_vec();
+        buf.truncate(self.cap);
+        buf.drain(..self.pos);
+        (self.inner, buf)
+    }
+
     /// Invalidates all data in the internal buffer.
     #[inline]
     fn discard_buffer(&mut self) {
@@ -630,10 +1293,26 @@ impl<R: Read> BufRead for BufReader<R> {
     }
 
     fn consume(&mut self, amt: usize) {
+        debug_assert!(
+            amt <= self.cap - self.pos,
+            "cannot consume {} bytes, only {} are buffered",
+            amt,
+            self.cap - self.pos
+        );
         self.pos = cmp::min(self.pos + amt, self.cap);
     }
 }
 
+impl<R: ReadReady> ReadReady for BufReader<R> {
+    fn read_ready(&mut self) -> Result<bool> {
+        if !self.buffer().is_empty() {
+            return Ok(true);
+        }
+
+        self.inner.read_ready()
+    }
+}
+
 impl<R> fmt::Debug for BufReader<R>
 where
     R: fmt::Debug,
@@ -663,8 +1342,16 @@ impl<R: Seek> Seek for BufReader<R> {
     ///
     /// To seek without discarding the internal buffer, use [`BufReader::seek_relative`].
     ///
+    /// If the internal buffer is empty and the underlying reader reports its
+    /// position through [`Seek::known_position`], a seek to that position is
+    /// skipped rather than passed on. Otherwise, the `BufReader<R>` cannot
+    /// tell where the underlying reader is, and every seek is passed on. If
+    /// seeking is expensive, wrap the underlying reader in a [`CachedSeek`].
+    ///
     /// See [`acid_io::Seek`] for more details.
     ///
+    /// [`CachedSeek`]: crate::CachedSeek
+    ///
     /// Note: In the edge case where you're seeking with <code>[SeekFrom::Current]\(n)</code>
     /// where `n` minus the internal buffer length overflows an `i64`, two
     /// seeks will be performed instead of one. If the second seek returns
@@ -673,6 +1360,19 @@ impl<R: Seek> Seek for BufReader<R> {
     ///
     /// [`acid_io::Seek`]: Seek
     fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        if self.pos == self.cap {
+            if let Some(current) = self.inner.known_position() {
+                let target = match pos {
+                    SeekFrom::Start(n) => Some(n),
+                    SeekFrom::Current(n) => io_core::offset_position(current, n),
+                    SeekFrom::End(_) => None,
+                };
+                if target == Some(current) {
+                    return Ok(current);
+                }
+            }
+        }
+
         let result: u64;
         if let SeekFrom::Current(n) = pos {
             let remainder = (self.cap - self.pos) as i64;
@@ -697,6 +1397,31 @@ impl<R: Seek> Seek for BufReader<R> {
         Ok(result)
     }
 
+    /// Returns the length of the underlying stream (in bytes).
+    ///
+    /// This forwards to the underlying reader's [`Seek::stream_len`], which
+    /// leaves its position unchanged on success. As a result, the internal
+    /// buffer remains valid and is not discarded. For readers which know their
+    /// length without seeking, such as [`Cursor`], no seek is performed at
+    /// all. If the underlying call fails, the internal buffer is discarded, as
+    /// the underlying position may no longer correspond to it.
+    fn stream_len(&mut self) -> Result<u64> {
+        self.inner
+            .stream_len()
+            .inspect_err(|_| self.discard_buffer())
+    }
+
+    /// Returns the length of the underlying stream (in bytes).
+    ///
+    /// This forwards to the underlying reader's [`Seek::stream_len_checked`].
+    /// If restoring the underlying reader's position fails, the internal buffer
+    /// is discarded, as it no longer corresponds to the underlying position.
+    fn stream_len_checked(&mut self) -> core::result::Result<u64, StreamLenError> {
+        self.inner
+            .stream_len_checked()
+            .inspect_err(|_| self.discard_buffer())
+    }
+
     /// Returns the current seek position from the start of the stream.
     ///
     /// The value returned is equivalent to `self.seek(SeekFrom::Current(0))`
@@ -738,6 +1463,18 @@ impl<R: Seek> Seek for BufReader<R> {
             )
         })
     }
+
+    #[inline]
+    fn supports_end_seek(&self) -> bool {
+        self.inner.supports_end_seek()
+    }
+
+    fn known_position(&self) -> Option<u64> {
+        let remainder = (self.cap - self.pos) as u64;
+        self.inner
+            .known_position()
+            .and_then(|pos| pos.checked_sub(remainder))
+    }
 }
 
 /// An error returned by [`BufWriter::into_inner`] which combines an error that
@@ -922,7 +1659,14 @@ impl<W> fmt::Display for IntoInnerError<W> {
 /// ensures that the buffer is empty and thus dropping will not even attempt
 /// file operations.
 ///
+/// A `BufWriter<W>` does not keep errors for later: every error from writing
+/// out the buffer is returned by the call which caused it, except during drop.
+/// Code which cannot call [`flush`] at the right time, such as a guard's own
+/// `Drop`, should use [`into_inner`] instead of dropping the writer, which
+/// returns the error of the final flush together with the writer.
+///
 /// [`flush`]: BufWriter::flush
+/// [`into_inner`]: BufWriter::into_inner
 pub struct BufWriter<W: Write> {
     inner: W,
     // The buffer. Avoid using this like a normal `Vec` in common code paths.
@@ -1504,6 +2248,11 @@ impl<W: Write + Seek> Seek for BufWriter<W> {
         self.flush_buf()?;
         self.get_mut().seek(pos)
     }
+
+    #[inline]
+    fn supports_end_seek(&self) -> bool {
+        self.get_ref().supports_end_seek()
+    }
 }
 
 impl<W: Write> Drop for BufWriter<W> {
@@ -2004,14 +2753,74 @@ where
     }
 }
 
-impl<R: Read> Read for Box<R> {
+impl<R: Read + ?Sized> Read for Box<R> {
     fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
         (**self).read(dst)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (**self).is_read_vectored()
+    }
+
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
         (**self).read_exact(buf)
     }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl<R: ReadReady + ?Sized> ReadReady for Box<R> {
+    fn read_ready(&mut self) -> Result<bool> {
+        (**self).read_ready()
+    }
+}
+
+impl<W: Write + ?Sized> Write for Box<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
+    }
 }
 
 impl Write for Vec<u8> {
@@ -2048,14 +2857,248 @@ impl Write for Vec<u8> {
     }
 }
 
+/// Read is implemented for `VecDeque<u8>` by consuming bytes from the front of
+/// the `VecDeque`.
+impl Read for VecDeque<u8> {
+    /// Fill `buf` with the contents of the "front" slice as returned by
+    /// [`as_slices`][`VecDeque::as_slices`]. If the contained byte slices of
+    /// the `VecDeque` are discontiguous, multiple calls to `read` will be
+    /// needed to read the entire content.
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let (ref mut front, _) = self.as_slices();
+        let n = Read::read(front, buf)?;
+        self.drain(..n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() > self.len() {
+            return Err(Error::new_const(
+                ErrorKind::UnexpectedEof,
+                &"failed to fill whole buffer",
+            ));
+        }
+        let (front, back) = self.as_slices();
+        let split = cmp::min(buf.len(), front.len());
+        let (buf_front, buf_back) = buf.split_at_mut(split);
+        buf_front.copy_from_slice(&front[..split]);
+        buf_back.copy_from_slice(&back[..buf_back.len()]);
+        self.drain(..buf.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        let len = self.len();
+        buf.reserve(len);
+        let (front, back) = self.as_slices();
+        buf.extend_from_slice(front);
+        buf.extend_from_slice(back);
+        self.clear();
+        Ok(len)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl ReadReady for VecDeque<u8> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(!self.is_empty())
+    }
+}
+
+/// BufRead is implemented for `VecDeque<u8>` by reading bytes from the front
+/// of the `VecDeque`.
+impl BufRead for VecDeque<u8> {
+    /// Returns the contents of the "front" slice as returned by
+    /// [`as_slices`][`VecDeque::as_slices`]. If the contained byte slices of
+    /// the `VecDeque` are discontiguous, multiple calls to `fill_buf` will be
+    /// needed to read the entire content.
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        let (front, _) = self.as_slices();
+        Ok(front)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt);
+    }
+}
+
+/// Write is implemented for `VecDeque<u8>` by appending to the `VecDeque`,
+/// growing it as needed.
+impl Write for VecDeque<u8> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.reserve(len);
+        for buf in bufs {
+            self.extend(&**buf);
+        }
+        Ok(len)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.extend(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 // Resizing write implementation
-fn vec_write(pos_mut: &mut u64, vec: &mut Vec<u8>, buf: &[u8]) -> Result<usize> {
-    let pos: usize = (*pos_mut).try_into().map_err(|_| {
+impl Cursor<Vec<u8>> {
+    /// Returns the bytes of the vector up to the furthest point written.
+    ///
+    /// This is the original contents of the vector overlaid with everything
+    /// written since, up to the end of the furthest write or the original
+    /// length, whichever is further. Writes only grow the vector that far, so
+    /// this is the whole vector. Seeking, whether back over written data or
+    /// past the end, does not change it, and spare capacity is never
+    /// included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Cursor, Seek, SeekFrom, Write};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buf = Cursor::new(Vec::with_capacity(64));
+    /// buf.write_all(b"hello")?;
+    /// buf.seek(SeekFrom::Start(1))?;
+    /// buf.write_all(b"E")?;
+    /// buf.seek(SeekFrom::Start(32))?;
+    ///
+    /// assert_eq!(buf.written(), b"hEllo");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn written(&self) -> &[u8] {
+        &self.inner
+    }
+
+    /// Consumes the cursor, returning the vector up to the furthest point
+    /// written.
+    ///
+    /// See [`written`] for exactly which bytes are included.
+    ///
+    /// [`written`]: Cursor::written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Cursor, Write};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buf = Cursor::new(Vec::new());
+    /// buf.set_position(2);
+    /// buf.write_all(b"ab")?;
+    /// buf.set_position(0);
+    ///
+    /// assert_eq!(buf.into_written(), b"\0\0ab");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_written(self) -> Vec<u8> {
+        self.inner
+    }
+
+    /// Inserts `buf` at the current position, shifting the bytes after it to
+    /// the right, and advances the position past the inserted bytes.
+    ///
+    /// Unlike [`write`], which overwrites existing bytes, no data is lost. If
+    /// the position is past the end of the vector, the gap is zero-filled
+    /// first, as it is for writes.
+    ///
+    /// [`write`]: Write::write
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Cursor;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buf = Cursor::new(b"held".to_vec());
+    /// buf.set_position(2);
+    ///
+    /// assert_eq!(buf.insert(b"llo, wor")?, 8);
+    /// assert_eq!(buf.get_ref(), b"hello, world");
+    /// assert_eq!(buf.position(), 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert(&mut self, buf: &[u8]) -> Result<usize> {
+        let pos = vec_position(self.pos)?;
+        if self.inner.len() < pos {
+            self.inner.resize(pos, 0);
+        }
+        self.inner.splice(pos..pos, buf.iter().copied());
+        self.pos = (pos + buf.len()) as u64;
+        Ok(buf.len())
+    }
+
+    /// Removes up to `n` bytes at the current position, shifting the bytes
+    /// after them to the left.
+    ///
+    /// The position is unchanged. Fewer than `n` bytes are removed if the
+    /// vector ends first, and none if the position is at or past the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Cursor;
+    ///
+    /// let mut buf = Cursor::new(b"hello, world".to_vec());
+    /// buf.set_position(5);
+    ///
+    /// buf.remove(7);
+    /// assert_eq!(buf.get_ref(), b"hello");
+    ///
+    /// buf.remove(1);
+    /// assert_eq!(buf.get_ref(), b"hello");
+    /// ```
+    pub fn remove(&mut self, n: usize) {
+        let len = self.inner.len();
+        let start = cmp::min(self.pos, len as u64) as usize;
+        let end = start + cmp::min(n, len - start);
+        self.inner.drain(start..end);
+    }
+}
+
+fn vec_position(pos: u64) -> Result<usize> {
+    pos.try_into().map_err(|_| {
         Error::new_const(
             ErrorKind::InvalidInput,
             &"cursor position exceeds maximum possible vector length",
         )
-    })?;
+    })
+}
+
+fn vec_write<V: AsMut<Vec<u8>>>(cursor: &mut Cursor<V>, buf: &[u8]) -> Result<usize> {
+    let pos = vec_position(cursor.pos)?;
+    let vec = cursor.inner.as_mut();
     // Make sure the internal buffer is as least as big as where we
     // currently are
     let len = vec.len();
@@ -2073,25 +3116,34 @@ fn vec_write(pos_mut: &mut u64, vec: &mut Vec<u8>, buf: &[u8]) -> Result<usize>
     }
 
     // Bump us forward
-    *pos_mut = (pos + buf.len()) as u64;
+    cursor.pos = (pos + buf.len()) as u64;
     Ok(buf.len())
 }
 
-fn vec_write_vectored(pos_mut: &mut u64, vec: &mut Vec<u8>, bufs: &[IoSlice<'_>]) -> Result<usize> {
+fn vec_write_vectored<V: AsMut<Vec<u8>>>(
+    cursor: &mut Cursor<V>,
+    bufs: &[IoSlice<'_>],
+) -> Result<usize> {
     let mut nwritten = 0;
     for buf in bufs {
-        nwritten += vec_write(pos_mut, vec, buf)?;
+        nwritten += vec_write(cursor, buf)?;
     }
     Ok(nwritten)
 }
 
+/// Writes into a borrowed vector, growing it as needed.
+///
+/// Writing before the end of the vector overwrites its contents in place,
+/// while writing past the end extends it, zero-filling any gap between the
+/// old end and the cursor position. Position tracking is identical to that
+/// of `Cursor<Vec<u8>>`.
 impl Write for Cursor<&mut Vec<u8>> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        vec_write(&mut self.pos, self.inner, buf)
+        vec_write(self, buf)
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
-        vec_write_vectored(&mut self.pos, self.inner, bufs)
+        vec_write_vectored(self, bufs)
     }
 
     #[inline]
@@ -2107,11 +3159,11 @@ impl Write for Cursor<&mut Vec<u8>> {
 
 impl Write for Cursor<Vec<u8>> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        vec_write(&mut self.pos, &mut self.inner, buf)
+        vec_write(self, buf)
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
-        vec_write_vectored(&mut self.pos, &mut self.inner, bufs)
+        vec_write_vectored(self, bufs)
     }
 
     #[inline]
@@ -6,11 +6,33 @@ use core::{cmp, fmt, mem, slice};
 use alloc::{string::String, vec::Vec};
 
 #[cfg(feature = "alloc")]
-use crate::{io_alloc, Lines, Split};
+use crate::{io_alloc, GrowthStrategy, Lines, LinesWithOffsets, Records, Split, Utf8Chunks};
 use crate::{Error, ErrorKind, IoSlice, IoSliceMut, Result};
 
 // Read ==========================================================================================
 
+/// Size of the stack buffer used by [`Read::read_vectored_coalesced`] and
+/// [`Write::write_vectored_gathered`].
+const COALESCE_LEN: usize = 512;
+
+/// Returns how many bytes to pass through the stack buffer for a vectored
+/// call on buffers of the given lengths, or `None` if the first nonempty
+/// buffer is large enough to be used directly.
+fn coalesced_len<I>(mut lens: I) -> Option<usize>
+where
+    I: Iterator<Item = usize> + Clone,
+{
+    let total = lens
+        .clone()
+        .fold(0usize, |acc, len| acc.saturating_add(len));
+    let first = lens.find(|&len| len > 0).unwrap_or(0);
+    if first >= total || first >= COALESCE_LEN {
+        None
+    } else {
+        Some(cmp::min(total, COALESCE_LEN))
+    }
+}
+
 pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
 where
     F: FnOnce(&mut [u8]) -> Result<usize>,
@@ -208,6 +230,28 @@ impl<T: Read> Read for Take<T> {
         Ok(n)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        if self.limit == 0 {
+            return Ok(0);
+        }
+
+        // The buffers can only be handed over as a whole if they all fit
+        // within the limit.
+        let total = bufs.iter().map(|b| b.len() as u64).sum::<u64>();
+        if total > self.limit {
+            return default_read_vectored(|b| self.read(b), bufs);
+        }
+
+        let n = self.inner.read_vectored(bufs)?;
+        self.limit -= n as u64;
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (min, max) = self.inner.size_hint();
@@ -240,6 +284,96 @@ impl<T: BufRead> BufRead for Take<T> {
     }
 }
 
+/// Reader adapter which reads exactly a given number of bytes from an
+/// underlying reader.
+///
+/// This struct is generally created by calling [`expect_len`] on a reader.
+/// Please see the documentation of [`expect_len`] for more details.
+///
+/// [`expect_len`]: Read::expect_len
+#[derive(Debug)]
+pub struct ExpectLen<T> {
+    inner: T,
+    remaining: u64,
+}
+
+impl<T> ExpectLen<T> {
+    /// Returns the number of bytes which are still expected.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Consumes the `ExpectLen`, returning the wrapped reader.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the count of remaining bytes.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+fn truncated() -> Error {
+    Error::new_const(
+        ErrorKind::UnexpectedEof,
+        &"stream ended before the expected length",
+    )
+}
+
+impl<T: Read> Read for ExpectLen<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // Don't call into inner reader at all at EOF because it may still block
+        if self.remaining == 0 || buf.is_empty() {
+            return Ok(0);
+        }
+
+        let max = cmp::min(buf.len() as u64, self.remaining) as usize;
+        let n = self.inner.read(&mut buf[..max])?;
+        if n == 0 {
+            return Err(truncated());
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, _) = self.inner.size_hint();
+        let remaining = usize::try_from(self.remaining).unwrap_or(usize::MAX);
+        (cmp::min(min, remaining), Some(remaining))
+    }
+}
+
+impl<T: BufRead> BufRead for ExpectLen<T> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.remaining == 0 {
+            return Ok(&[]);
+        }
+
+        let buf = self.inner.fill_buf()?;
+        if buf.is_empty() {
+            return Err(truncated());
+        }
+        let cap = cmp::min(buf.len() as u64, self.remaining) as usize;
+        Ok(&buf[..cap])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt as u64, self.remaining) as usize;
+        self.remaining -= amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
 /// Adapter to chain together two readers.
 ///
 /// This struct is generally created by calling [`chain`] on a reader.
@@ -343,15 +477,19 @@ impl<T: Read, U: Read> Read for Chain<T, U> {
         self.second.read_vectored(bufs)
     }
 
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.first.is_read_vectored() && self.second.is_read_vectored()
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (min1, max1) = self.first.size_hint();
         let (min2, max2) = self.second.size_hint();
 
         (
-            min1 + min2,
-            max1.map(|x| max2.map(|y| x.checked_add(y)).flatten())
-                .flatten(),
+            min1.saturating_add(min2),
+            max1.and_then(|x| max2.and_then(|y| x.checked_add(y))),
         )
     }
 }
@@ -360,7 +498,7 @@ impl<T: BufRead, U: BufRead> BufRead for Chain<T, U> {
     fn fill_buf(&mut self) -> Result<&[u8]> {
         if !self.done_first {
             match self.first.fill_buf()? {
-                buf if buf.is_empty() => {
+                [] => {
                     self.done_first = true;
                 }
                 buf => return Ok(buf),
@@ -378,1374 +516,5028 @@ impl<T: BufRead, U: BufRead> BufRead for Chain<T, U> {
     }
 }
 
-/// The `Read` trait allows for reading bytes from a source.
-///
-/// Implementors of the `Read` trait are called 'readers'.
-///
-/// Readers are defined by one required method, [`read()`]. Each call to [`read()`]
-/// will attempt to pull bytes from this source into a provided buffer. A
-/// number of other methods are implemented in terms of [`read()`], giving
-/// implementors a number of ways to read bytes while only needing to implement
-/// a single method.
-///
-/// # Examples
-///
-/// Read from [`&str`] because [`&[u8]`][prim@slice] implements `Read`:
-///
-/// ```
-/// use acid_io::prelude::*;
-///
-/// # fn main() -> acid_io::Result<()> {
-/// let mut b = "This string will be read".as_bytes();
-/// let mut buffer = [0; 10];
-///
-/// // read up to 10 bytes
-/// b.read(&mut buffer)?;
+/// Reader adapter which calls a closure with every chunk of bytes read.
 ///
-/// # Ok(())
-/// # }
-/// ```
+/// This struct is generally created by calling [`inspect`] on a reader.
+/// Please see the documentation of [`inspect`] for more details.
 ///
-/// [`read()`]: Read::read
-/// [`&str`]: prim@str
-pub trait Read {
-    /// Pull some bytes from this source into the specified buffer, returning
-    /// how many bytes were read.
-    ///
-    /// This function does not provide any guarantees about whether it blocks
-    /// waiting for data, but if an object needs to block for a read and cannot,
-    /// it will typically signal this via an [`Err`] return value.
-    ///
-    /// If the return value of this method is [`Ok(n)`], then implementations must
-    /// guarantee that `0 <= n <= dst.len()`. A nonzero `n` value indicates
-    /// that the buffer `dst` has been filled in with `n` bytes of data from this
-    /// source. If `n` is `0`, then it can indicate one of two scenarios:
-    ///
-    /// 1. This reader has reached its "end of file" and will likely no longer
-    ///    be able to produce bytes. Note that this does not mean that the
-    ///    reader will *always* no longer be able to produce bytes.
-    /// 2. The buffer specified was 0 bytes in length.
-    ///
-    /// It is not an error if the returned value `n` is smaller than the buffer size,
-    /// even when the reader is not at the end of the stream yet.
-    /// This may happen for example because fewer bytes are actually available right now
-    /// (e. g. being close to end-of-file) or because read() was interrupted by a signal.
-    ///
-    /// As this trait is safe to implement, callers cannot rely on `n <= dst.len()` for safety.
-    /// Extra care needs to be taken when `unsafe` functions are used to access the read bytes.
-    /// Callers have to ensure that no unchecked out-of-bounds accesses are possible even if
-    /// `n > dst.len()`.
-    ///
-    /// No guarantees are provided about the contents of `dst` when this
-    /// function is called, implementations cannot rely on any property of the
-    /// contents of `dst` being true. It is recommended that *implementations*
-    /// only write data to `dst` instead of reading its contents.
-    ///
-    /// Correspondingly, however, *callers* of this method must not assume any guarantees
-    /// about how the implementation uses `dst`. The trait is safe to implement,
-    /// so it is possible that the code that's supposed to write to the buffer might also read
-    /// from it. It is your responsibility to make sure that `dst` is initialized
-    /// before calling `read`. Calling `read` with an uninitialized `dst` (of the kind one
-    /// obtains via [`MaybeUninit<T>`]) is not safe, and can lead to undefined behavior.
-    ///
-    /// [`MaybeUninit<T>`]: core::mem::MaybeUninit
-    ///
-    /// # Errors
-    ///
-    /// If this function encounters any form of I/O or other error, an error
-    /// variant will be returned. If an error is returned then it must be
-    /// guaranteed that no bytes were read.
-    ///
-    /// An error of the [`ErrorKind::Interrupted`] kind is non-fatal and the read
-    /// operation should be retried if there is nothing else to do.
+/// [`inspect`]: Read::inspect
+pub struct Inspect<R, F> {
+    inner: R,
+    f: F,
+}
+
+impl<R, F> Inspect<R, F> {
+    /// Consumes the `Inspect`, returning the wrapped reader.
     ///
     /// # Examples
     ///
-    /// [`Ok(n)`]: Ok
-    ///
     /// ```
-    /// use acid_io::Read;
-    ///
-    /// # fn main() -> acid_io::Result<()> {
-    /// let src = [1u8, 2, 3, 4, 5, 6, 7, 8];
-    /// let mut dst = [0u8; 3];
-    ///
-    /// let mut r = &src[..];
-    ///
-    /// // read up to 3 bytes
-    /// let n = r.read(&mut dst[..])?;
+    /// use acid_io::prelude::*;
     ///
-    /// assert_eq!(dst, [1, 2, 3]);
-    /// # Ok(())
-    /// # }
+    /// let r = &b"abc"[..];
+    /// let inspect = r.inspect(|_| {});
+    /// let r: &[u8] = inspect.into_inner();
+    /// assert_eq!(r, b"abc");
     /// ```
-    fn read(&mut self, dst: &mut [u8]) -> Result<usize>;
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
 
-    /// Like `read`, except that it reads into a slice of buffers.
-    ///
-    /// Data is copied to fill each buffer in order, with the final buffer
-    /// written to possibly being only partially filled. This method must
-    /// behave equivalently to a single call to `read` with concatenated
-    /// buffers.
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
     ///
-    /// The default implementation calls `read` with either the first nonempty
-    /// buffer provided, or an empty one if none exists.
+    /// Bytes read directly from the underlying reader are not passed to the
+    /// closure.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: fmt::Debug, F> fmt::Debug for Inspect<R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inspect")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Read, F: FnMut(&[u8])> Read for Inspect<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        (self.f)(&buf[..n]);
+        Ok(n)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
-        default_read_vectored(|b| self.read(b), bufs)
+        let n = self.inner.read_vectored(bufs)?;
+        let mut left = n;
+        for buf in bufs.iter() {
+            if left == 0 {
+                break;
+            }
+            let filled = cmp::min(left, buf.len());
+            (self.f)(&buf[..filled]);
+            left -= filled;
+        }
+        Ok(n)
     }
 
-    /// Determines if this `Read`er has an efficient `read_vectored`
-    /// implementation.
-    ///
-    /// If a `Read`er does not override the default `read_vectored`
-    /// implementation, code using it may want to avoid the method all together
-    /// and coalesce writes into a single buffer for higher performance.
-    ///
-    /// The default implementation returns `false`.
-    #[doc(hidden)]
     fn is_read_vectored(&self) -> bool {
-        false
+        self.inner.is_read_vectored()
     }
 
-    /// Read all bytes until EOF in this source, placing them into `buf`.
-    ///
-    /// All bytes read from this source will be appended to the specified buffer
-    /// `buf`. This function will continuously call [`read()`] to append more data to
-    /// `buf` until [`read()`] returns either [`Ok(0)`] or an error of
-    /// non-[`ErrorKind::Interrupted`] kind.
-    ///
-    /// If successful, this function will return the total number of bytes read.
-    ///
-    /// # Errors
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Reader adapter which yields a borrowed prefix before the bytes of a reader.
+///
+/// This struct is generally created by calling [`prepend`] on a reader.
+/// Please see the documentation of [`prepend`] for more details.
+///
+/// [`prepend`]: Read::prepend
+#[derive(Debug)]
+pub struct Prepend<'a, R> {
+    prefix: &'a [u8],
+    inner: R,
+}
+
+impl<'a, R> Prepend<'a, R> {
+    /// Returns the part of the prefix which has not been read yet.
+    pub fn prefix(&self) -> &'a [u8] {
+        self.prefix
+    }
+
+    /// Consumes the `Prepend`, returning the wrapped reader.
     ///
-    /// If this function encounters an error of the kind
-    /// [`ErrorKind::Interrupted`] then the error is ignored and the operation
-    /// will continue.
+    /// Any unread part of the prefix is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
     ///
-    /// If any other read error is encountered then this function immediately
+    /// Reading directly from the underlying reader skips any unread part of
+    /// the prefix.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: Read> Read for Prepend<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.prefix.is_empty() {
+            return self.inner.read(buf);
+        }
+        self.prefix.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        if self.prefix.is_empty() {
+            return self.inner.read_vectored(bufs);
+        }
+        self.prefix.read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.inner.size_hint();
+        (
+            min.saturating_add(self.prefix.len()),
+            max.and_then(|max| max.checked_add(self.prefix.len())),
+        )
+    }
+}
+
+impl<R: BufRead> BufRead for Prepend<'_, R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.prefix.is_empty() {
+            return self.inner.fill_buf();
+        }
+        Ok(self.prefix)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.prefix.is_empty() {
+            self.inner.consume(amt)
+        } else {
+            self.prefix.consume(amt)
+        }
+    }
+}
+
+impl<R: ReadReady> ReadReady for Prepend<'_, R> {
+    fn read_ready(&mut self) -> Result<bool> {
+        if !self.prefix.is_empty() {
+            return Ok(true);
+        }
+        self.inner.read_ready()
+    }
+}
+
+/// Reader adapter which fills the whole destination buffer on each read,
+/// unless the end of the stream comes first.
+///
+/// This struct is generally created by calling [`read_full`] on a reader.
+/// Please see the documentation of [`read_full`] for more details.
+///
+/// [`read_full`]: Read::read_full
+#[derive(Debug)]
+pub struct ReadFull<R> {
+    inner: R,
+    // An error hit after some bytes had been read, returned by the next read.
+    error: Option<Error>,
+}
+
+impl<R> ReadFull<R> {
+    /// Consumes the `ReadFull`, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: Read> Read for ReadFull<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        let mut nread = 0;
+        while nread < buf.len() {
+            match self.inner.read(&mut buf[nread..]) {
+                Ok(0) => break,
+                Ok(n) => nread += n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                // Report the bytes already placed in `buf` first. The
+                // underlying reader may not return the error again, so it
+                // is kept for the next read.
+                Err(e) if nread > 0 => {
+                    self.error = Some(e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(nread)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<R: ReadReady> ReadReady for ReadFull<R> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        self.inner.read_ready()
+    }
+}
+
+/// Reader adapter which returns at most a fixed number of bytes per read.
+///
+/// This struct is generally created by calling [`chunked`] on a reader.
+/// Please see the documentation of [`chunked`] for more details.
+///
+/// [`chunked`]: Read::chunked
+#[derive(Debug)]
+pub struct ChunkedReader<R> {
+    inner: R,
+    max: usize,
+}
+
+impl<R> ChunkedReader<R> {
+    /// Returns the largest number of bytes returned by a single read.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Consumes the `ChunkedReader`, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: Read> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let len = cmp::min(buf.len(), self.max);
+        self.inner.read(&mut buf[..len])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<R: BufRead> BufRead for ChunkedReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        Ok(&buf[..cmp::min(buf.len(), self.max)])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: ReadReady> ReadReady for ChunkedReader<R> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        self.inner.read_ready()
+    }
+}
+
+/// Reader adapter which replaces its underlying reader with a fresh one each
+/// time the current one reaches its end.
+///
+/// When the underlying reader returns `Ok(0)`, the closure is called. If it
+/// returns `Some(reader)`, that reader replaces the current one and reading
+/// continues from it; if it returns `None`, the end of the stream is
+/// reported. A later read tries the current reader, and then the closure,
+/// again, which suits following a log file that is rotated or appended to.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, ResumableReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut segments = [&b"def"[..], &b"ghi"[..]].into_iter();
+/// let mut reader = ResumableReader::new(&b"abc"[..], || Ok(segments.next()));
+///
+/// let mut buf = [0u8; 9];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"abcdefghi");
+/// assert_eq!(reader.read(&mut buf)?, 0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResumableReader<R, F> {
+    inner: R,
+    next: F,
+}
+
+impl<R, F> ResumableReader<R, F>
+where
+    R: Read,
+    F: FnMut() -> Result<Option<R>>,
+{
+    /// Creates a new `ResumableReader<R, F>` which starts with `inner` and
+    /// calls `next` to obtain each following reader.
+    pub fn new(inner: R, next: F) -> ResumableReader<R, F> {
+        ResumableReader { inner, next }
+    }
+
+    /// Gets a reference to the current underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the current underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `ResumableReader`, returning the current underlying
+    /// reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: fmt::Debug, F> fmt::Debug for ResumableReader<R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableReader")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, F> Read for ResumableReader<R, F>
+where
+    R: Read,
+    F: FnMut() -> Result<Option<R>>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            let n = self.inner.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                return Ok(n);
+            }
+            match (self.next)()? {
+                Some(inner) => self.inner = inner,
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+/// Reader adapter which fails reads once a deadline has passed.
+///
+/// Before each read from the underlying reader, the closure is called; if
+/// it returns `true`, the deadline has passed and the read fails with
+/// [`ErrorKind::TimedOut`] without touching the underlying reader. The
+/// closure can consult any time source, such as a tick counter, so no clock
+/// abstraction is needed. Loops such as [`read_exact`] check again before
+/// each of their reads, which bounds how long they run.
+///
+/// [`read_exact`]: Read::read_exact
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{DeadlineReader, ErrorKind, Read};
+/// use core::cell::Cell;
+///
+/// let ticks = Cell::new(0);
+/// let mut reader = DeadlineReader::new(&b"abc"[..], || ticks.get() >= 10);
+///
+/// let mut buf = [0u8; 1];
+/// assert_eq!(reader.read(&mut buf).unwrap(), 1);
+///
+/// ticks.set(10);
+/// assert_eq!(reader.read(&mut buf).unwrap_err().kind(), ErrorKind::TimedOut);
+/// ```
+pub struct DeadlineReader<R, F> {
+    inner: R,
+    expired: F,
+}
+
+impl<R, F> DeadlineReader<R, F>
+where
+    R: Read,
+    F: FnMut() -> bool,
+{
+    /// Creates a new `DeadlineReader<R, F>` which reads from `inner` until
+    /// `expired` returns `true`.
+    pub fn new(inner: R, expired: F) -> DeadlineReader<R, F> {
+        DeadlineReader { inner, expired }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `DeadlineReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn check(&mut self) -> Result<()> {
+        if (self.expired)() {
+            Err(Error::new_const(
+                ErrorKind::TimedOut,
+                &"the read deadline has passed",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<R: fmt::Debug, F> fmt::Debug for DeadlineReader<R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadlineReader")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, F> Read for DeadlineReader<R, F>
+where
+    R: Read,
+    F: FnMut() -> bool,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.check()?;
+        self.inner.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.check()?;
+        self.inner.read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Reader adapter which merges two sorted streams into one.
+///
+/// The next byte of each underlying reader is peeked, and the one which the
+/// comparator orders first is read. If they compare equal, the byte of the
+/// first reader is read first. Once either reader reaches its end, the rest
+/// of the other follows. If both inputs are sorted by the comparator, so is
+/// the output.
+///
+/// Each call to [`read`] reads a run of bytes from one side only, so it may
+/// return fewer bytes than requested even if both readers have more.
+///
+/// [`read`]: Read::read
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{MergeReader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut reader = MergeReader::new(&b"aceg"[..], &b"bdf"[..], |a, b| a.cmp(&b));
+///
+/// let mut buf = [0u8; 7];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"abcdefg");
+/// # Ok(())
+/// # }
+/// ```
+pub struct MergeReader<A, B, F> {
+    first: A,
+    second: B,
+    compare: F,
+}
+
+impl<A, B, F> MergeReader<A, B, F>
+where
+    A: BufRead,
+    B: BufRead,
+    F: FnMut(u8, u8) -> cmp::Ordering,
+{
+    /// Creates a new `MergeReader<A, B, F>` which merges `first` and `second`
+    /// in the order given by `compare`.
+    pub fn new(first: A, second: B, compare: F) -> MergeReader<A, B, F> {
+        MergeReader {
+            first,
+            second,
+            compare,
+        }
+    }
+
+    /// Gets references to the underlying readers in this `MergeReader`.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Gets mutable references to the underlying readers in this
+    /// `MergeReader`.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying readers as doing so may corrupt the internal state of this
+    /// `MergeReader`.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes the `MergeReader`, returning the wrapped readers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug, F> fmt::Debug for MergeReader<A, B, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeReader")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A, B, F> Read for MergeReader<A, B, F>
+where
+    A: BufRead,
+    B: BufRead,
+    F: FnMut(u8, u8) -> cmp::Ordering,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let a = self.first.fill_buf()?;
+        let b = self.second.fill_buf()?;
+        let compare = &mut self.compare;
+        let (from_first, n) = match (a.first(), b.first()) {
+            (None, None) => return Ok(0),
+            (Some(_), None) => (true, a.len()),
+            (None, Some(_)) => (false, b.len()),
+            (Some(&x), Some(&y)) if compare(x, y) != cmp::Ordering::Greater => {
+                let n = a
+                    .iter()
+                    .take_while(|&&x| compare(x, y) != cmp::Ordering::Greater)
+                    .count();
+                (true, n)
+            }
+            (Some(&x), Some(_)) => {
+                let n = b
+                    .iter()
+                    .take_while(|&&y| compare(x, y) == cmp::Ordering::Greater)
+                    .count();
+                (false, n)
+            }
+        };
+
+        let n = cmp::min(n, buf.len());
+        if from_first {
+            buf[..n].copy_from_slice(&a[..n]);
+            self.first.consume(n);
+        } else {
+            buf[..n].copy_from_slice(&b[..n]);
+            self.second.consume(n);
+        }
+        Ok(n)
+    }
+}
+
+/// The `Read` trait allows for reading bytes from a source.
+///
+/// Implementors of the `Read` trait are called 'readers'.
+///
+/// Readers are defined by one required method, [`read()`]. Each call to [`read()`]
+/// will attempt to pull bytes from this source into a provided buffer. A
+/// number of other methods are implemented in terms of [`read()`], giving
+/// implementors a number of ways to read bytes while only needing to implement
+/// a single method.
+///
+/// # Examples
+///
+/// Read from [`&str`] because [`&[u8]`][prim@slice] implements `Read`:
+///
+/// ```
+/// use acid_io::prelude::*;
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut b = "This string will be read".as_bytes();
+/// let mut buffer = [0; 10];
+///
+/// // read up to 10 bytes
+/// b.read(&mut buffer)?;
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Trait objects
+///
+/// `Read` can be used as a trait object. Both `&mut dyn Read` and, with the
+/// `alloc` feature, `Box<dyn Read>` implement `Read` themselves, so the
+/// adapters which take `self` by value, such as [`take`] and [`chain`], can be
+/// used on them too:
+///
+/// ```
+/// use acid_io::prelude::*;
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut a = &b"Hello, "[..];
+/// let mut b = &b"world!"[..];
+/// let mut readers: [&mut dyn Read; 2] = [&mut a, &mut b];
+///
+/// let mut buffer = [0; 13];
+/// let mut filled = 0;
+/// for r in readers.iter_mut() {
+///     filled += r.take(7).read(&mut buffer[filled..])?;
+/// }
+/// assert_eq!(&buffer, b"Hello, world!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`read()`]: Read::read
+/// [`&str`]: prim@str
+/// [`take`]: Read::take
+/// [`chain`]: Read::chain
+pub trait Read {
+    /// Pull some bytes from this source into the specified buffer, returning
+    /// how many bytes were read.
+    ///
+    /// This function does not provide any guarantees about whether it blocks
+    /// waiting for data, but if an object needs to block for a read and cannot,
+    /// it will typically signal this via an [`Err`] return value.
+    ///
+    /// If the return value of this method is [`Ok(n)`], then implementations must
+    /// guarantee that `0 <= n <= dst.len()`. A nonzero `n` value indicates
+    /// that the buffer `dst` has been filled in with `n` bytes of data from this
+    /// source. If `n` is `0`, then it can indicate one of two scenarios:
+    ///
+    /// 1. This reader has reached its "end of file" and will likely no longer
+    ///    be able to produce bytes. Note that this does not mean that the
+    ///    reader will *always* no longer be able to produce bytes.
+    /// 2. The buffer specified was 0 bytes in length.
+    ///
+    /// It is not an error if the returned value `n` is smaller than the buffer size,
+    /// even when the reader is not at the end of the stream yet.
+    /// This may happen for example because fewer bytes are actually available right now
+    /// (e. g. being close to end-of-file) or because read() was interrupted by a signal.
+    ///
+    /// As this trait is safe to implement, callers cannot rely on `n <= dst.len()` for safety.
+    /// Extra care needs to be taken when `unsafe` functions are used to access the read bytes.
+    /// Callers have to ensure that no unchecked out-of-bounds accesses are possible even if
+    /// `n > dst.len()`.
+    ///
+    /// No guarantees are provided about the contents of `dst` when this
+    /// function is called, implementations cannot rely on any property of the
+    /// contents of `dst` being true. It is recommended that *implementations*
+    /// only write data to `dst` instead of reading its contents.
+    ///
+    /// Correspondingly, however, *callers* of this method must not assume any guarantees
+    /// about how the implementation uses `dst`. The trait is safe to implement,
+    /// so it is possible that the code that's supposed to write to the buffer might also read
+    /// from it. It is your responsibility to make sure that `dst` is initialized
+    /// before calling `read`. Calling `read` with an uninitialized `dst` (of the kind one
+    /// obtains via [`MaybeUninit<T>`]) is not safe, and can lead to undefined behavior.
+    ///
+    /// [`MaybeUninit<T>`]: core::mem::MaybeUninit
+    ///
+    /// # Errors
+    ///
+    /// If this function encounters any form of I/O or other error, an error
+    /// variant will be returned. If an error is returned then it must be
+    /// guaranteed that no bytes were read.
+    ///
+    /// An error of the [`ErrorKind::Interrupted`] kind is non-fatal and the read
+    /// operation should be retried if there is nothing else to do.
+    ///
+    /// # Examples
+    ///
+    /// [`Ok(n)`]: Ok
+    ///
+    /// ```
+    /// use acid_io::Read;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let src = [1u8, 2, 3, 4, 5, 6, 7, 8];
+    /// let mut dst = [0u8; 3];
+    ///
+    /// let mut r = &src[..];
+    ///
+    /// // read up to 3 bytes
+    /// let n = r.read(&mut dst[..])?;
+    ///
+    /// assert_eq!(dst, [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize>;
+
+    /// Like `read`, except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must
+    /// behave equivalently to a single call to `read` with concatenated
+    /// buffers.
+    ///
+    /// The default implementation calls `read` with either the first nonempty
+    /// buffer provided, or an empty one if none exists.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        default_read_vectored(|b| self.read(b), bufs)
+    }
+
+    /// Determines if this `Read`er has an efficient `read_vectored`
+    /// implementation.
+    ///
+    /// If a `Read`er does not override the default `read_vectored`
+    /// implementation, code using it may want to avoid the method all together
+    /// and coalesce writes into a single buffer for higher performance.
+    ///
+    /// The default implementation returns `false`.
+    ///
+    /// This method mirrors an unstable API in `std::io`, but is a stable part
+    /// of `acid_io`'s API regardless of its status in `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{repeat, Read};
+    ///
+    /// assert!(b"bytes".as_slice().is_read_vectored());
+    /// assert!(repeat(0).is_read_vectored());
+    /// ```
+    fn is_read_vectored(&self) -> bool {
+        false
+    }
+
+    /// Like [`read_vectored`], except that several small buffers are filled
+    /// with a single call to [`read`] even if this reader has no efficient
+    /// vectored implementation.
+    ///
+    /// If [`is_read_vectored`] returns `true`, this simply calls
+    /// [`read_vectored`]. Otherwise, if the first nonempty buffer is shorter
+    /// than the total, at most 512 bytes are read into a buffer on the stack
+    /// and then scattered across `bufs` in order. This saves calls to the
+    /// underlying reader, and may save system calls, when filling many small
+    /// buffers. A first buffer holding at least 512 bytes, or all of the
+    /// requested bytes, is read into directly.
+    ///
+    /// [`read`]: Read::read
+    /// [`read_vectored`]: Read::read_vectored
+    /// [`is_read_vectored`]: Read::is_read_vectored
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{IoSliceMut, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// // A reader without an efficient `read_vectored`.
+    /// let mut r = (&b"abcdef"[..]).chunked(64);
+    ///
+    /// let (mut a, mut b, mut c) = ([0u8; 1], [0u8; 2], [0u8; 4]);
+    /// let mut bufs = [
+    ///     IoSliceMut::new(&mut a),
+    ///     IoSliceMut::new(&mut b),
+    ///     IoSliceMut::new(&mut c),
+    /// ];
+    /// assert_eq!(r.read_vectored_coalesced(&mut bufs)?, 6);
+    /// assert_eq!((&a, &b, &c[..3]), (b"a", b"bc", &b"def"[..]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read_vectored_coalesced(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        if self.is_read_vectored() {
+            return self.read_vectored(bufs);
+        }
+
+        let Some(len) = coalesced_len(bufs.iter().map(|b| b.len())) else {
+            return self.read_vectored(bufs);
+        };
+
+        let mut stack = [0u8; COALESCE_LEN];
+        let n = self.read(&mut stack[..len])?;
+        let mut data = &stack[..n];
+        for buf in bufs.iter_mut() {
+            if data.is_empty() {
+                break;
+            }
+            let amt = cmp::min(data.len(), buf.len());
+            buf[..amt].copy_from_slice(&data[..amt]);
+            data = &data[amt..];
+        }
+        Ok(n)
+    }
+
+    /// Read all bytes until EOF in this source, placing them into `buf`.
+    ///
+    /// All bytes read from this source will be appended to the specified buffer
+    /// `buf`. This function will continuously call [`read()`] to append more data to
+    /// `buf` until [`read()`] returns either [`Ok(0)`] or an error of
+    /// non-[`ErrorKind::Interrupted`] kind.
+    ///
+    /// If successful, this function will return the total number of bytes read.
+    ///
+    /// # Errors
+    ///
+    /// If this function encounters an error of the kind
+    /// [`ErrorKind::Interrupted`] then the error is ignored and the operation
+    /// will continue.
+    ///
+    /// If any other read error is encountered then this function immediately
     /// returns. Any bytes which have already been read will be appended to
     /// `buf`.
     ///
-    /// [`read()`]: Read::read
-    /// [`Ok(0)`]: core::result::Result::Ok
+    /// [`read()`]: Read::read
+    /// [`Ok(0)`]: core::result::Result::Ok
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let data = b"Let's read this entire buffer!";
+    ///
+    /// let mut src = &data[..];
+    /// let mut dst = Vec::new();
+    /// src.read_to_end(&mut dst)?;
+    ///
+    /// assert_eq!(&data[..], &dst);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        io_alloc::default_read_to_end(self, buf)
+    }
+
+    /// Reads all bytes until EOF in this source, appending them to `buf` and
+    /// growing it as decided by `strategy`.
+    ///
+    /// This behaves like [`read_to_end`], except that each time `buf` is full
+    /// the [`GrowthStrategy`] chooses how much spare capacity to reserve, and
+    /// exactly that much is reserved. No space is reserved up front based on
+    /// [`size_hint`]. This lets memory constrained callers trade the number of
+    /// reallocations for a lower peak memory use.
+    ///
+    /// [`read_to_end`]: Read::read_to_end
+    /// [`size_hint`]: Read::size_hint
+    ///
+    /// # Errors
+    ///
+    /// See [`read_to_end`] for error semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Doubling, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buf = Vec::new();
+    /// (&[1u8; 40][..]).read_to_end_with(&mut buf, Doubling)?;
+    /// assert_eq!(buf, [1; 40]);
+    /// // Space was reserved for 32 bytes, then for 32 more.
+    /// assert!(buf.capacity() >= 64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_to_end_with<S: GrowthStrategy>(
+        &mut self,
+        buf: &mut Vec<u8>,
+        strategy: S,
+    ) -> Result<usize>
+    where
+        Self: Sized,
+    {
+        io_alloc::default_read_to_end_with(self, buf, strategy)
+    }
+
+    /// Read all bytes until EOF in this source, appending them to `buf`.
+    ///
+    /// If successful, this function returns the number of bytes which were read
+    /// and appended to `buf`.
+    ///
+    /// # Errors
+    ///
+    /// If the data in this stream is *not* valid UTF-8 then an error is
+    /// returned and `buf` is unchanged.
+    ///
+    /// See [`read_to_end`] for other error semantics.
+    ///
+    /// [`read_to_end`]: Read::read_to_end
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buffer = String::new();
+    ///
+    /// let mut valid = &b"Some valid UTF-8"[..];
+    /// valid.read_to_string(&mut buffer)?;
+    ///
+    /// let mut invalid = &b"\xFF\xFF\xFF\xFF"[..];
+    /// assert!(invalid.read_to_string(&mut buffer).is_err());
+    ///
+    /// assert_eq!(buffer, "Some valid UTF-8");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        io_alloc::default_read_to_string(self, buf)
+    }
+
+    /// Reads a message prefixed with its length, appending it to `buf`.
+    ///
+    /// The length is read as a `W`-byte big-endian integer, followed by that
+    /// many bytes of message. Returns the length of the message. This is the
+    /// inverse of [`Write::write_length_prefixed`].
+    ///
+    /// The buffer grows as the message arrives, so a corrupt length does not
+    /// cause a large allocation up front. Messages longer than `max` bytes are
+    /// rejected before any of their bytes are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidData`] if the length does
+    /// not fit in a `usize` or exceeds `max`, and an error of kind
+    /// [`ErrorKind::UnexpectedEof`] if the stream ends before the whole
+    /// message is read. Other errors are returned as from [`read_exact`].
+    ///
+    /// If an error is returned, `buf` is left as it was, but an unspecified
+    /// part of the message may have been read from the stream.
+    ///
+    /// [`read_exact`]: Read::read_exact
+    ///
+    /// # Panics
+    ///
+    /// Panics if `W` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Read;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut src = &b"\x00\x05hello\x00\x03abc"[..];
+    /// let mut msg = Vec::new();
+    ///
+    /// assert_eq!(src.read_length_prefixed::<2>(&mut msg, 1024)?, 5);
+    /// assert_eq!(msg, b"hello");
+    ///
+    /// // A message longer than the maximum is rejected.
+    /// assert!(src.read_length_prefixed::<2>(&mut msg, 2).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_length_prefixed<const W: usize>(
+        &mut self,
+        buf: &mut Vec<u8>,
+        max: usize,
+    ) -> Result<usize>
+    where
+        Self: Sized,
+    {
+        io_alloc::default_read_length_prefixed::<_, W>(self, buf, max)
+    }
+
+    /// Read the exact number of bytes required to fill `buf`.
+    ///
+    /// This function reads as many bytes as necessary to completely fill the
+    /// specified buffer `buf`.
+    ///
+    /// No guarantees are provided about the contents of `buf` when this
+    /// function is called; implementations cannot rely on any property of the
+    /// contents of `buf` being true. It is recommended that implementations
+    /// only write data to `buf` instead of reading its contents. The
+    /// documentation on [`read`] has a more detailed explanation on this
+    /// subject.
+    ///
+    /// # Errors
+    ///
+    /// If this function encounters an error of the kind
+    /// [`ErrorKind::Interrupted`] then the error is ignored and the operation
+    /// will continue.
+    ///
+    /// If this function encounters an "end of file" before completely filling
+    /// the buffer, it returns an error of the kind [`ErrorKind::UnexpectedEof`].
+    /// The contents of `buf` are unspecified in this case.
+    ///
+    /// If any other read error is encountered then this function immediately
+    /// returns. The contents of `buf` are unspecified in this case.
+    ///
+    /// If this function returns an error, it is unspecified how many bytes it
+    /// has read, but it will never read more than would be necessary to
+    /// completely fill the buffer.
+    ///
+    /// # Examples
+    ///
+    /// [`read`]: Read::read
+    ///
+    /// ```
+    /// use acid_io::Read;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let src = [1u8, 2, 3, 4, 5, 6, 7, 8];
+    /// let mut dst = [0u8; 3];
+    ///
+    /// let mut r = &src[..];
+    ///
+    /// // read exactly 3 bytes
+    /// let n = r.read_exact(&mut dst[..])?;
+    ///
+    /// assert_eq!(dst, [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        default_read_exact(self, buf)
+    }
+
+    /// Pull some bytes from this source into the specified buffer, returning
+    /// `None` at the end of the stream.
+    ///
+    /// This behaves like [`read`], except that the `Ok(0)` which signals the
+    /// end of the stream becomes `Ok(None)`, and every other successful read
+    /// of `n` bytes becomes `Ok(Some(n))`. An empty `buf` always yields
+    /// `Ok(Some(0))`, so it is never mistaken for the end of the stream.
+    ///
+    /// Errors are returned unchanged, including those of kind
+    /// [`ErrorKind::Interrupted`].
+    ///
+    /// [`read`]: Read::read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Read;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut r = &b"Hello, world!"[..];
+    /// let mut buf = [0u8; 4];
+    /// let mut total = 0;
+    ///
+    /// while let Some(n) = r.read_or_eof(&mut buf)? {
+    ///     total += n;
+    /// }
+    /// assert_eq!(total, 13);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read_or_eof(&mut self, buf: &mut [u8]) -> Result<Option<usize>> {
+        match self.read(buf)? {
+            0 if !buf.is_empty() => Ok(None),
+            n => Ok(Some(n)),
+        }
+    }
+
+    /// Read the exact number of bytes required to fill `buf`, unless the
+    /// stream is already at its end.
+    ///
+    /// This behaves like [`read_exact`], except that reaching the end of the
+    /// stream before any byte is read is not an error: `Ok(false)` is returned
+    /// and `buf` is left untouched. A full read returns `Ok(true)`, as does an
+    /// empty `buf`.
+    ///
+    /// This tells an absent optional field apart from a truncated one.
+    ///
+    /// [`read_exact`]: Read::read_exact
+    ///
+    /// # Errors
+    ///
+    /// If the stream ends after some but not all of `buf` has been filled,
+    /// this returns an error of the kind [`ErrorKind::UnexpectedEof`]. The
+    /// contents of `buf` are unspecified in this case.
+    ///
+    /// Errors of the kind [`ErrorKind::Interrupted`] are ignored. Any other
+    /// read error is returned immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{ErrorKind, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut trailer = [0xFFu8; 2];
+    ///
+    /// let mut r = &[1u8, 2][..];
+    /// assert!(r.read_exact_or_default(&mut trailer)?);
+    /// assert_eq!(trailer, [1, 2]);
+    ///
+    /// let mut trailer = [0xFFu8; 2];
+    /// assert!(!r.read_exact_or_default(&mut trailer)?);
+    /// assert_eq!(trailer, [0xFF, 0xFF]);
+    ///
+    /// let mut r = &[3u8][..];
+    /// let err = r.read_exact_or_default(&mut trailer).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read_exact_or_default(&mut self, buf: &mut [u8]) -> Result<bool> {
+        if buf.is_empty() {
+            return Ok(true);
+        }
+        loop {
+            match self.read(buf) {
+                Ok(0) => return Ok(false),
+                Ok(n) => return default_read_exact(self, &mut buf[n..]).map(|()| true),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Creates a "by reference" adapter for this instance of `Read`.
+    ///
+    /// The returned adapter also implements `Read` and will simply borrow this
+    /// current reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let bytes = [1, 1, 2, 3, 5, 8, 13, 21];
+    /// let mut r = &bytes[..];
+    ///
+    /// { // Use the reader by reference.
+    ///     let mut dst = [0u8; 4];
+    ///     let mut r2 = r.by_ref();
+    ///     r2.read(&mut dst)?;
+    ///     assert_eq!(dst, [1, 1, 2, 3]);
+    /// } // Drop the mutable borrow on the reader.
+    ///
+    /// let mut dst = [0u8; 4];
+    /// r.read(&mut dst)?;
+    /// assert_eq!(dst, [5, 8, 13, 21]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Transforms this `Read` instance to an [`Iterator`] over its bytes.
+    ///
+    /// The returned type implements [`Iterator`] where the [`Item`] is
+    /// [`Result<u8>`][Result]; The yielded item is [`Ok`] if a byte was
+    /// successfully read and [`Err`] otherwise. EOF is mapped to returning
+    /// [`None`] from this iterator.
+    ///
+    /// # Examples
+    ///
+    /// [`Item`]: Iterator::Item
+    /// [Result]: crate::Result
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut it = "Hi!".as_bytes().bytes();
+    ///
+    /// assert_eq!(it.next().transpose()?, Some(b'H'));
+    /// assert_eq!(it.next().transpose()?, Some(b'i'));
+    /// assert_eq!(it.next().transpose()?, Some(b'!'));
+    /// assert_eq!(it.next().transpose()?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn bytes(self) -> Bytes<Self>
+    where
+        Self: Sized,
+    {
+        Bytes { inner: self }
+    }
+
+    /// Creates an adapter which will chain this stream with another.
+    ///
+    /// The returned `Read` instance will first read all bytes from this object
+    /// until EOF is encountered. Afterwards the output is equivalent to the
+    /// output of `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    /// use acid_io::Cursor;
+    ///
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() -> acid_io::Result<()> { Ok(()) }
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut first = &b"Hello, ";
+    /// let mut second = Cursor::new("world!");
+    ///
+    /// let mut s = String::new();
+    /// first.chain(second).read_to_string(&mut s)?;
+    ///
+    /// assert_eq!(s, "Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn chain<R: Read>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+            done_first: false,
+        }
+    }
+
+    /// Creates an adapter which will read at most `limit` bytes from it.
+    ///
+    /// This function returns a new instance of `Read` which will read at most
+    /// `limit` bytes, after which it will always return EOF ([`Ok(0)`]). Any
+    /// read errors will not count towards the number of bytes read and future
+    /// calls to [`read()`] may succeed.
+    ///
+    /// # Examples
+    ///
+    /// [`Ok(0)`]: Ok
+    /// [`read()`]: Read::read
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() -> acid_io::Result<()> { Ok(()) }
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut r = &b"Bytes and more bytes";
+    ///
+    /// // read at most five bytes
+    /// let mut handle = r.take(5);
+    ///
+    /// let mut buffer = Vec::new();
+    /// handle.read_to_end(&mut buffer)?;
+    ///
+    /// assert_eq!(&buffer, &b"Bytes");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn take(self, limit: u64) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take { inner: self, limit }
+    }
+
+    /// Creates an adapter which will read exactly `len` bytes from it.
+    ///
+    /// This behaves like [`take`], except that if this reader reaches EOF
+    /// before `len` bytes have been read, the adapter returns an error of
+    /// kind [`ErrorKind::UnexpectedEof`] instead of [`Ok(0)`]. Once `len`
+    /// bytes have been read, it always returns EOF.
+    ///
+    /// This tells a payload of a declared length apart from a truncated
+    /// stream, which [`take`] cannot.
+    ///
+    /// [`take`]: Read::take
+    /// [`Ok(0)`]: Ok
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{ErrorKind, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut payload = (&b"abc"[..]).expect_len(3);
+    /// let mut buf = [0u8; 8];
+    /// assert_eq!(payload.read(&mut buf)?, 3);
+    /// assert_eq!(payload.read(&mut buf)?, 0);
+    ///
+    /// let mut truncated = (&b"ab"[..]).expect_len(3);
+    /// assert_eq!(truncated.read(&mut buf)?, 2);
+    /// let err = truncated.read(&mut buf).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn expect_len(self, len: u64) -> ExpectLen<Self>
+    where
+        Self: Sized,
+    {
+        ExpectLen {
+            inner: self,
+            remaining: len,
+        }
+    }
+
+    /// Creates an adapter which calls a closure with the bytes produced by
+    /// each read.
+    ///
+    /// The closure is called with exactly the region of the destination
+    /// buffer that was filled, after the underlying reader returns and before
+    /// the byte count is handed back to the caller. It is not called when the
+    /// underlying reader returns an error. The bytes and count returned by the
+    /// adapter are the same as those of the underlying reader.
+    ///
+    /// This is the reader equivalent of [`Iterator::inspect`], useful for
+    /// logging or debugging a stream without otherwise changing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut seen = 0;
+    /// let mut r = (&b"Hello, world!"[..]).inspect(|bytes| seen += bytes.len());
+    ///
+    /// let mut buffer = [0; 5];
+    /// r.read_exact(&mut buffer)?;
+    /// assert_eq!(&buffer, b"Hello");
+    ///
+    /// drop(r);
+    /// assert_eq!(seen, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn inspect<F: FnMut(&[u8])>(self, f: F) -> Inspect<Self, F>
+    where
+        Self: Sized,
+    {
+        Inspect { inner: self, f }
+    }
+
+    /// Creates an adapter whose reads fill the destination buffer completely
+    /// unless the end of the stream is reached first.
+    ///
+    /// Each [`read`] on the adapter calls the underlying reader repeatedly,
+    /// retrying on [`ErrorKind::Interrupted`], and returns the number of bytes
+    /// placed in the buffer. That is the buffer's length, except at the end of
+    /// the stream, where it is whatever was left. Unlike [`read_exact`], a
+    /// short final read is not an error.
+    ///
+    /// If the underlying reader fails after some bytes have been placed in the
+    /// buffer, those bytes are returned and the error is returned by the next
+    /// read.
+    ///
+    /// [`read`]: Read::read
+    /// [`read_exact`]: Read::read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// // A reader which returns its data in two pieces.
+    /// let data = (&b"Hel"[..]).chain(&b"lo, world!"[..]);
+    /// let mut r = data.read_full();
+    ///
+    /// let mut buffer = [0; 8];
+    /// assert_eq!(r.read(&mut buffer)?, 8);
+    /// assert_eq!(&buffer, b"Hello, w");
+    /// assert_eq!(r.read(&mut buffer)?, 5);
+    /// assert_eq!(&buffer[..5], b"orld!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read_full(self) -> ReadFull<Self>
+    where
+        Self: Sized,
+    {
+        ReadFull {
+            inner: self,
+            error: None,
+        }
+    }
+
+    /// Creates an adapter which yields the bytes of `prefix` before those of
+    /// this reader.
+    ///
+    /// This "unreads" bytes which were already taken from the reader, for
+    /// example after sniffing a protocol header, so that the code reading
+    /// next sees the whole stream. It behaves like
+    /// <code>prefix.[chain]\(self)</code>, except that the unread part of the
+    /// prefix can be inspected with [`Prepend::prefix`].
+    ///
+    /// [chain]: Read::chain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut stream = &b"GET / HTTP/1.1"[..];
+    ///
+    /// let mut magic = [0u8; 4];
+    /// stream.read_exact(&mut magic)?;
+    /// assert_eq!(&magic, b"GET ");
+    ///
+    /// let mut request = [0u8; 14];
+    /// stream.prepend(&magic).read_exact(&mut request)?;
+    /// assert_eq!(&request, b"GET / HTTP/1.1");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn prepend(self, prefix: &[u8]) -> Prepend<'_, Self>
+    where
+        Self: Sized,
+    {
+        Prepend {
+            prefix,
+            inner: self,
+        }
+    }
+
+    /// Creates an adapter which returns at most `max` bytes from each read.
+    ///
+    /// Each [`read`] on the adapter limits the destination buffer to `max`
+    /// bytes before passing it on, so callers see short reads even when more
+    /// data is available. This is useful for testing that code which consumes
+    /// a reader handles short reads correctly. If the reader implements
+    /// [`BufRead`], [`fill_buf`] is limited in the same way.
+    ///
+    /// Where [`take`] limits the total number of bytes read, this limits the
+    /// number of bytes read per call.
+    ///
+    /// [`read`]: Read::read
+    /// [`fill_buf`]: BufRead::fill_buf
+    /// [`take`]: Read::take
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is 0, since every read would then look like the end of
+    /// the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut r = (&b"Hello, world!"[..]).chunked(4);
+    ///
+    /// let mut buffer = [0; 13];
+    /// assert_eq!(r.read(&mut buffer)?, 4);
+    ///
+    /// // `read_exact` loops over the short reads.
+    /// r.read_exact(&mut buffer[4..])?;
+    /// assert_eq!(&buffer, b"Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn chunked(self, max: usize) -> ChunkedReader<Self>
+    where
+        Self: Sized,
+    {
+        assert!(max != 0, "chunk size must be non-zero");
+        ChunkedReader { inner: self, max }
+    }
+
+    #[doc(hidden)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+impl<R: Read + ?Sized> Read for &mut R {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        (**self).read(dst)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (**self).is_read_vectored()
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        (**self).read_exact(buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl Read for &[u8] {
+    #[inline]
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let copy_len = cmp::min(dst.len(), self.len());
+        let (src, rem) = self.split_at(copy_len);
+
+        // Avoid invoking memcpy for very small copies.
+        if copy_len == 1 {
+            dst[0] = src[0];
+        } else {
+            dst[..copy_len].copy_from_slice(src);
+        }
+
+        *self = rem;
+
+        Ok(copy_len)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let mut nread = 0;
+        for buf in bufs {
+            nread += self.read(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+
+        Ok(nread)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() > self.len() {
+            return Err(Error::new_const(
+                ErrorKind::UnexpectedEof,
+                &"failed to fill whole buffer",
+            ));
+        }
+        let (a, b) = self.split_at(buf.len());
+
+        // First check if the amount of bytes we want to read is small:
+        // `copy_from_slice` will generally expand to a call to `memcpy`, and
+        // for a single byte the overhead is significant.
+        if buf.len() == 1 {
+            buf[0] = a[0];
+        } else {
+            buf.copy_from_slice(a);
+        }
+
+        *self = b;
+        Ok(())
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        buf.extend_from_slice(self);
+        let len = self.len();
+        *self = &self[len..];
+        Ok(len)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+/// An absent reader behaves as if it were always at EOF.
+///
+/// `None` reads as <code>[Ok]\(0)</code>, while `Some(reader)` delegates to
+/// `reader`. This is handy for state machines which only sometimes have a
+/// backing stream.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::Read;
+///
+/// let mut present = Some(&b"abc"[..]);
+/// let mut absent: Option<&[u8]> = None;
+///
+/// let mut buf = [0; 4];
+/// assert_eq!(present.read(&mut buf).unwrap(), 3);
+/// assert_eq!(absent.read(&mut buf).unwrap(), 0);
+/// ```
+impl<R: Read> Read for Option<R> {
+    #[inline]
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        match self {
+            Some(r) => r.read(dst),
+            None => Ok(0),
+        }
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        match self {
+            Some(r) => r.read_vectored(bufs),
+            None => Ok(0),
+        }
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        match self {
+            Some(r) => r.is_read_vectored(),
+            None => true,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Some(r) => r.size_hint(),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+// ReadReady =====================================================================================
+
+/// A `ReadReady` is a [`Read`]er which can report whether a call to
+/// [`read`] would return data without blocking.
+///
+/// This is a non-blocking hint intended for cooperative schedulers and event
+/// loops: a reader which reports `false` should not be read from until it is
+/// polled again. The hint is advisory; a reader which reports `true` may still
+/// return fewer bytes than requested.
+///
+/// The default implementation returns `Ok(true)`, which is the correct answer
+/// for any reader that never blocks. Readers which may block should override
+/// it.
+///
+/// [`read`]: Read::read
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Cursor, ReadReady};
+///
+/// let mut c = Cursor::new(b"data");
+/// assert!(c.read_ready().unwrap());
+///
+/// c.set_position(4);
+/// assert!(!c.read_ready().unwrap());
+/// ```
+pub trait ReadReady: Read {
+    /// Returns `true` if data is immediately available to be read.
+    ///
+    /// # Errors
+    ///
+    /// This function returns any error encountered while determining the
+    /// readiness of the reader.
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+impl<R: ReadReady + ?Sized> ReadReady for &mut R {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        (**self).read_ready()
+    }
+}
+
+impl ReadReady for &[u8] {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(!self.is_empty())
+    }
+}
+
+impl<T> ReadReady for Cursor<T>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(!self.is_empty())
+    }
+}
+
+impl<T: ReadReady> ReadReady for Take<T> {
+    fn read_ready(&mut self) -> Result<bool> {
+        // A `Take` at its limit always reads as EOF.
+        if self.limit == 0 {
+            return Ok(false);
+        }
+
+        self.inner.read_ready()
+    }
+}
+
+impl<R: ReadReady, F: FnMut(&[u8])> ReadReady for Inspect<R, F> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        self.inner.read_ready()
+    }
+}
+
+impl<R: ReadReady> ReadReady for Option<R> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        match self {
+            Some(r) => r.read_ready(),
+            None => Ok(false),
+        }
+    }
+}
+
+impl<T: ReadReady, U: ReadReady> ReadReady for Chain<T, U> {
+    fn read_ready(&mut self) -> Result<bool> {
+        if !self.done_first {
+            if self.first.read_ready()? {
+                return Ok(true);
+            }
+
+            // Only look past the first reader if it is known to be exhausted;
+            // otherwise a read would wait on the first reader.
+            if self.first.size_hint().1 != Some(0) {
+                return Ok(false);
+            }
+        }
+
+        self.second.read_ready()
+    }
+}
+
+// BufRead =======================================================================================
+
+/// Reads into `buf` until the delimiter `byte` or EOF is reached, returning
+/// `None` if `buf` fills up first.
+fn read_until_slice<R: BufRead + ?Sized>(
+    r: &mut R,
+    byte: u8,
+    buf: &mut [u8],
+) -> Result<Option<usize>> {
+    let mut read = 0;
+    loop {
+        let (done, used) = {
+            let available = match r.fill_buf() {
+                Ok(n) => n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if available.is_empty() {
+                return Ok(Some(read));
+            }
+            if read == buf.len() {
+                return Ok(None);
+            }
+            let available = &available[..cmp::min(available.len(), buf.len() - read)];
+            let (done, used) = match memchr::memchr(byte, available) {
+                Some(i) => (true, i + 1),
+                None => (false, available.len()),
+            };
+            buf[read..read + used].copy_from_slice(&available[..used]);
+            (done, used)
+        };
+        r.consume(used);
+        read += used;
+        if done {
+            return Ok(Some(read));
+        }
+    }
+}
+
+/// Consumes bytes up to, and including, the delimiter `byte` or EOF.
+fn skip_until<R: BufRead + ?Sized>(r: &mut R, byte: u8) -> Result<()> {
+    loop {
+        let (done, used) = {
+            let available = match r.fill_buf() {
+                Ok(n) => n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            match memchr::memchr(byte, available) {
+                Some(i) => (true, i + 1),
+                None => (available.is_empty(), available.len()),
+            }
+        };
+        r.consume(used);
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+fn line_too_long() -> Error {
+    Error::new_const(ErrorKind::InvalidData, &"line too long for the buffer")
+}
+
+/// A `BufRead` is a type of `Read`er which has an internal buffer, allowing it
+/// to perform extra ways of reading.
+///
+/// # Examples
+///
+/// In-memory buffers implement `BufRead`:
+///
+/// ```
+/// use acid_io::prelude::*;
+///
+/// let input = b"Here\nare\nsome\nlines\n";
+///
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// for line in input.lines() {
+///     println!("{}", line.unwrap());
+/// }
+/// # }
+/// ```
+///
+/// If you have something that implements [`Read`], you can use the [`BufReader`
+/// type][`BufReader`] to turn it into a `BufRead`.
+///
+/// [`BufReader`]: crate::BufReader
+pub trait BufRead {
+    /// Returns the contents of the internal buffer, filling it with more data
+    /// from the inner reader if it is empty.
+    ///
+    /// This function is a lower-level call. It needs to be paired with the
+    /// [`consume`] method to function properly. When calling this
+    /// method, none of the contents will be "read" in the sense that later
+    /// calling `read` may return the same contents. As such, [`consume`] must
+    /// be called with the number of bytes that are consumed from this buffer to
+    /// ensure that the bytes are never returned twice.
+    ///
+    /// [`consume`]: BufRead::consume
+    ///
+    /// An empty buffer returned indicates that the stream has reached EOF.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an I/O error if the underlying reader was
+    /// read, but returned an error.
+    fn fill_buf(&mut self) -> Result<&[u8]>;
+
+    /// Tells this buffer that `amt` bytes have been consumed from the buffer,
+    /// so they should no longer be returned in calls to `read`.
+    ///
+    /// This function is a lower-level call. It needs to be paired with the
+    /// [`fill_buf`] method to function properly. This function does
+    /// not perform any I/O, it simply informs this object that some amount of
+    /// its buffer, returned from [`fill_buf`], has been consumed and should
+    /// no longer be returned. As such, this function may do odd things if
+    /// [`fill_buf`] isn't called before calling it.
+    ///
+    /// The `amt` must be `<=` the number of bytes in the buffer returned by
+    /// [`fill_buf`].
+    ///
+    /// # Examples
+    ///
+    /// Since `consume()` is meant to be used with [`fill_buf`],
+    /// that method's example includes an example of `consume()`.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    fn consume(&mut self, amt: usize);
+
+    /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached.
+    ///
+    /// This function will read bytes from the underlying stream until the
+    /// delimiter or EOF is found. Once found, all bytes up to, and including,
+    /// the delimiter (if found) will be appended to `buf`.
+    ///
+    /// If successful, this function will return the total number of bytes read.
+    ///
+    /// This function is blocking and should be used carefully: it is possible for
+    /// an attacker to continuously send bytes without ever sending the delimiter
+    /// or EOF.
+    ///
+    /// # Errors
+    ///
+    /// This function will ignore all instances of [`ErrorKind::Interrupted`] and
+    /// will otherwise return any errors returned by [`fill_buf`].
+    ///
+    /// If an I/O error is encountered then all bytes read so far will be
+    /// present in `buf` and its length will have been adjusted appropriately.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    ///
+    /// # Examples
+    ///
+    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
+    /// this example, we use [`Cursor`] to read all the bytes in a byte slice
+    /// in hyphen delimited segments:
+    ///
+    /// ```
+    /// use acid_io::BufRead;
+    ///
+    /// let mut cursor = acid_io::Cursor::new(b"lorem-ipsum");
+    /// let mut buf = vec![];
+    ///
+    /// // cursor is at 'l'
+    /// let num_bytes = cursor.read_until(b'-', &mut buf)
+    ///     .expect("reading from cursor won't fail");
+    /// assert_eq!(num_bytes, 6);
+    /// assert_eq!(buf, b"lorem-");
+    /// buf.clear();
+    ///
+    /// // cursor is at 'i'
+    /// let num_bytes = cursor.read_until(b'-', &mut buf)
+    ///     .expect("reading from cursor won't fail");
+    /// assert_eq!(num_bytes, 5);
+    /// assert_eq!(buf, b"ipsum");
+    /// buf.clear();
+    ///
+    /// // cursor is at EOF
+    /// let num_bytes = cursor.read_until(b'-', &mut buf)
+    ///     .expect("reading from cursor won't fail");
+    /// assert_eq!(num_bytes, 0);
+    /// assert_eq!(buf, b"");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        io_alloc::read_until(self, byte, buf)
+    }
+
+    /// Read all bytes until a newline (the `0xA` byte) is reached, and append
+    /// them to the provided buffer.
+    ///
+    /// This function will read bytes from the underlying stream until the
+    /// newline delimiter (the `0xA` byte) or EOF is found. Once found, all bytes
+    /// up to, and including, the delimiter (if found) will be appended to
+    /// `buf`.
+    ///
+    /// If successful, this function will return the total number of bytes read.
+    ///
+    /// If this function returns [`Ok(0)`], the stream has reached EOF.
+    ///
+    /// This function is blocking and should be used carefully: it is possible for
+    /// an attacker to continuously send bytes without ever sending a newline
+    /// or EOF.
+    ///
+    /// [`Ok(0)`]: Ok
+    ///
+    /// # Errors
+    ///
+    /// This function has the same error semantics as [`read_until`] and will
+    /// also return an error if the read bytes are not valid UTF-8. If an I/O
+    /// error is encountered then `buf` may contain some bytes already read in
+    /// the event that all data read so far was valid UTF-8.
+    ///
+    /// [`read_until`]: BufRead::read_until
+    ///
+    /// # Examples
+    ///
+    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
+    /// this example, we use [`Cursor`] to read all the lines in a byte slice:
+    ///
+    /// ```
+    /// use acid_io::{self, BufRead};
+    ///
+    /// let mut cursor = acid_io::Cursor::new(b"foo\nbar");
+    /// let mut buf = String::new();
+    ///
+    /// // cursor is at 'f'
+    /// let num_bytes = cursor.read_line(&mut buf)
+    ///     .expect("reading from cursor won't fail");
+    /// assert_eq!(num_bytes, 4);
+    /// assert_eq!(buf, "foo\n");
+    /// buf.clear();
+    ///
+    /// // cursor is at 'b'
+    /// let num_bytes = cursor.read_line(&mut buf)
+    ///     .expect("reading from cursor won't fail");
+    /// assert_eq!(num_bytes, 3);
+    /// assert_eq!(buf, "bar");
+    /// buf.clear();
+    ///
+    /// // cursor is at EOF
+    /// let num_bytes = cursor.read_line(&mut buf)
+    ///     .expect("reading from cursor won't fail");
+    /// assert_eq!(num_bytes, 0);
+    /// assert_eq!(buf, "");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        // Note that we are not calling the `.read_until` method here, but
+        // rather our hardcoded implementation. For more details as to why, see
+        // the comments in `read_to_end`.
+        unsafe { io_alloc::append_to_string(buf, |b| io_alloc::read_until(self, b'\n', b)) }
+    }
+
+    /// Reads a line of at most `max` bytes, appending it to `buf`.
+    ///
+    /// This behaves like [`read_line`], except that it gives up once `max`
+    /// bytes have been read without finding a newline. The limit includes the
+    /// newline itself. This protects against a peer which never sends a
+    /// newline, which would make [`read_line`] buffer an unbounded amount of
+    /// data.
+    ///
+    /// [`read_line`]: BufRead::read_line
+    ///
+    /// # Errors
+    ///
+    /// If the line is longer than `max` bytes, an error of kind
+    /// [`ErrorKind::InvalidData`] is returned. Exactly `max` bytes of the line
+    /// have then been consumed from the stream and discarded, and `buf` is left
+    /// unchanged; the rest of the line is still to be read.
+    ///
+    /// Otherwise, this function has the same error semantics as
+    /// [`read_line`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, ErrorKind};
+    ///
+    /// let mut cursor = acid_io::Cursor::new(b"foo\nextremely long\n");
+    /// let mut buf = String::new();
+    ///
+    /// assert_eq!(cursor.read_line_limited(&mut buf, 8).unwrap(), 4);
+    /// assert_eq!(buf, "foo\n");
+    ///
+    /// let err = cursor.read_line_limited(&mut buf, 8).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// assert_eq!(buf, "foo\n");
+    /// assert_eq!(cursor.position(), 12);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_line_limited(&mut self, buf: &mut String, max: usize) -> Result<usize> {
+        // See `read_line` for why the hardcoded implementation is used.
+        unsafe {
+            io_alloc::append_to_string(buf, |b| io_alloc::read_until_limited(self, b'\n', b, max))
+        }
+    }
+
+    /// Read bytes into the fixed buffer `buf` until the delimiter `byte` or
+    /// EOF is reached.
+    ///
+    /// This behaves like [`read_until`], except that the bytes up to, and
+    /// including, the delimiter (if found) are stored at the start of `buf`
+    /// rather than appended to a vector, so it does not need an allocator.
+    ///
+    /// If successful, this function will return the number of bytes stored in
+    /// `buf`. If this function returns [`Ok(0)`], the stream has reached EOF.
+    ///
+    /// [`read_until`]: BufRead::read_until
+    /// [`Ok(0)`]: Ok
+    ///
+    /// # Errors
+    ///
+    /// If `buf` fills up before the delimiter is found and the stream has not
+    /// ended, an error of kind [`ErrorKind::InvalidData`] is returned. `buf`
+    /// then holds the first `buf.len()` bytes of the line, which have been
+    /// consumed from the stream; the rest of the line is still to be read.
+    ///
+    /// This function will ignore all instances of [`ErrorKind::Interrupted`]
+    /// and will otherwise return any errors returned by [`fill_buf`]. The
+    /// bytes read so far are lost in that case.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, ErrorKind};
+    ///
+    /// let mut cursor = acid_io::Cursor::new(b"ok\nway too long\n");
+    /// let mut buf = [0u8; 8];
+    ///
+    /// let n = cursor.read_until_slice(b'\n', &mut buf).unwrap();
+    /// assert_eq!(&buf[..n], b"ok\n");
+    ///
+    /// let err = cursor.read_until_slice(b'\n', &mut buf).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// assert_eq!(&buf, b"way too ");
+    /// ```
+    fn read_until_slice(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+        read_until_slice(self, byte, buf)?.ok_or_else(line_too_long)
+    }
+
+    /// Read a line into the fixed buffer `buf`.
+    ///
+    /// This behaves like [`read_until_slice`] with the newline delimiter (the
+    /// `0xA` byte), except that a line which does not fit is skipped entirely,
+    /// so the next call starts at the beginning of the next line. The bytes
+    /// are not checked to be valid UTF-8; use [`core::str::from_utf8`] on the
+    /// result if needed.
+    ///
+    /// If successful, this function will return the number of bytes stored in
+    /// `buf`, including the newline if found. If this function returns
+    /// [`Ok(0)`], the stream has reached EOF.
+    ///
+    /// [`read_until_slice`]: BufRead::read_until_slice
+    /// [`Ok(0)`]: Ok
+    ///
+    /// # Errors
+    ///
+    /// If the line, including its newline, is longer than `buf`, an error of
+    /// kind [`ErrorKind::InvalidData`] is returned once the whole line has
+    /// been consumed from the stream. `buf` then holds the first `buf.len()`
+    /// bytes of the line.
+    ///
+    /// Otherwise, this function has the same error semantics as
+    /// [`read_until_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, ErrorKind};
+    ///
+    /// let mut cursor = acid_io::Cursor::new(b"help\nway too long\nexit\n");
+    /// let mut buf = [0u8; 8];
+    ///
+    /// let n = cursor.read_line_slice(&mut buf).unwrap();
+    /// assert_eq!(&buf[..n], b"help\n");
+    ///
+    /// let err = cursor.read_line_slice(&mut buf).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    ///
+    /// let n = cursor.read_line_slice(&mut buf).unwrap();
+    /// assert_eq!(&buf[..n], b"exit\n");
+    /// ```
+    fn read_line_slice(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match read_until_slice(self, b'\n', buf)? {
+            Some(n) => Ok(n),
+            None => {
+                skip_until(self, b'\n')?;
+                Err(line_too_long())
+            }
+        }
+    }
+
+    /// Returns an iterator over the contents of this reader split on the byte
+    /// `byte`.
+    ///
+    /// The iterator returned from this function will return instances of
+    /// <code>[io::Result]<[Vec]\<u8>></code>. Each vector returned will *not* have
+    /// the delimiter byte at the end.
+    ///
+    /// This function will yield errors whenever [`read_until`] would have
+    /// also yielded an error.
+    ///
+    /// [io::Result]: self::Result "io::Result"
+    /// [`read_until`]: BufRead::read_until
+    ///
+    /// # Examples
+    ///
+    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
+    /// this example, we use [`Cursor`] to iterate over all hyphen delimited
+    /// segments in a byte slice
+    ///
+    /// ```
+    /// use acid_io::BufRead;
+    ///
+    /// let cursor = acid_io::Cursor::new(b"lorem-ipsum-dolor");
+    ///
+    /// let mut split_iter = cursor.split(b'-').map(|l| l.unwrap());
+    /// assert_eq!(split_iter.next(), Some(b"lorem".to_vec()));
+    /// assert_eq!(split_iter.next(), Some(b"ipsum".to_vec()));
+    /// assert_eq!(split_iter.next(), Some(b"dolor".to_vec()));
+    /// assert_eq!(split_iter.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn split(self, byte: u8) -> Split<Self>
+    where
+        Self: Sized,
+    {
+        Split {
+            buf: self,
+            delim: byte,
+        }
+    }
+
+    /// Returns an iterator over the lines of this reader.
+    ///
+    /// The iterator returned from this function will yield instances of
+    /// <code>[io::Result]<[String]></code>. Each string returned will *not* have a newline
+    /// byte (the `0xA` byte) or `CRLF` (`0xD`, `0xA` bytes) at the end.
+    ///
+    /// [io::Result]: self::Result "io::Result"
+    ///
+    /// # Examples
+    ///
+    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
+    /// this example, we use [`Cursor`] to iterate over all the lines in a byte
+    /// slice.
+    ///
+    /// ```
+    /// use acid_io::BufRead;
+    ///
+    /// let cursor = acid_io::Cursor::new(b"lorem\nipsum\r\ndolor");
+    ///
+    /// let mut lines_iter = cursor.lines().map(|l| l.unwrap());
+    /// assert_eq!(lines_iter.next(), Some(String::from("lorem")));
+    /// assert_eq!(lines_iter.next(), Some(String::from("ipsum")));
+    /// assert_eq!(lines_iter.next(), Some(String::from("dolor")));
+    /// assert_eq!(lines_iter.next(), None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Each line of the iterator has the same error semantics as [`BufRead::read_line`].
+    #[cfg(feature = "alloc")]
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        Lines { buf: self }
+    }
+
+    /// Returns an iterator over the lines of this reader, together with the
+    /// offset of each line.
+    ///
+    /// This behaves like [`lines`], but each item is a pair of the line and
+    /// the number of bytes consumed from the reader before it, counting the
+    /// stripped line endings. This makes it possible to map errors found while
+    /// parsing a line back to a position in the stream.
+    ///
+    /// [`lines`]: BufRead::lines
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::BufRead;
+    ///
+    /// let cursor = acid_io::Cursor::new(b"lorem\nipsum\r\ndolor");
+    ///
+    /// let mut lines_iter = cursor.lines_with_offsets().map(|l| l.unwrap());
+    /// assert_eq!(lines_iter.next(), Some((0, String::from("lorem"))));
+    /// assert_eq!(lines_iter.next(), Some((6, String::from("ipsum"))));
+    /// assert_eq!(lines_iter.next(), Some((13, String::from("dolor"))));
+    /// assert_eq!(lines_iter.next(), None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Each line of the iterator has the same error semantics as
+    /// [`BufRead::read_line`]. The bytes of a line which fails to read are
+    /// still counted, so later offsets remain accurate.
+    #[cfg(feature = "alloc")]
+    fn lines_with_offsets(self) -> LinesWithOffsets<Self>
+    where
+        Self: Sized,
+    {
+        LinesWithOffsets {
+            buf: self,
+            offset: 0,
+        }
+    }
+
+    /// Returns an iterator over fixed-size records of this reader.
+    ///
+    /// The iterator returned from this function will yield instances of
+    /// <code>[io::Result]<[Vec]\<u8>></code>, each exactly `size` bytes long.
+    ///
+    /// By default, a trailing record shorter than `size` at the end of the
+    /// stream is reported as an error of kind [`ErrorKind::UnexpectedEof`].
+    /// Use [`Records::allow_partial`] to yield it instead.
+    ///
+    /// [io::Result]: self::Result "io::Result"
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::BufRead;
+    ///
+    /// let cursor = acid_io::Cursor::new(b"aaabbbcc");
+    ///
+    /// let mut records = cursor.records(3).allow_partial(true).map(|r| r.unwrap());
+    /// assert_eq!(records.next(), Some(b"aaa".to_vec()));
+    /// assert_eq!(records.next(), Some(b"bbb".to_vec()));
+    /// assert_eq!(records.next(), Some(b"cc".to_vec()));
+    /// assert_eq!(records.next(), None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Each record will yield an error whenever [`fill_buf`] returns an error
+    /// of a kind other than [`ErrorKind::Interrupted`]. The bytes of the
+    /// record read before the error are kept, and the next call to `next`
+    /// resumes the record, so errors such as [`ErrorKind::WouldBlock`] do not
+    /// lose data.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    #[cfg(feature = "alloc")]
+    fn records(self, size: usize) -> Records<Self>
+    where
+        Self: Sized,
+    {
+        assert!(size != 0, "record size must be non-zero");
+        Records {
+            buf: self,
+            size,
+            allow_partial: false,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Returns an iterator over chunks of UTF-8 text from this reader.
+    ///
+    /// The iterator returned from this function will yield instances of
+    /// <code>[io::Result]<[String]></code>. Each chunk ends on a character
+    /// boundary: a character split across reads is carried over to the next
+    /// chunk rather than cut in half. Chunk sizes otherwise follow the
+    /// reader's internal buffer, so the whole stream is never held in memory
+    /// at once.
+    ///
+    /// The method is not called `utf8_chunks`, as `<[u8]>::utf8_chunks` of
+    /// the standard library would shadow it for byte slices.
+    ///
+    /// [io::Result]: self::Result "io::Result"
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, BufReader};
+    ///
+    /// let text = "na\u{ef}ve caf\u{e9}";
+    /// // A tiny buffer splits the accented characters across reads.
+    /// let reader = BufReader::with_capacity(3, text.as_bytes());
+    ///
+    /// let chunks: Vec<String> = reader.read_utf8_chunks().map(|c| c.unwrap()).collect();
+    /// assert!(chunks.len() > 1);
+    /// assert_eq!(chunks.concat(), text);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid UTF-8 sequence yields an error of kind
+    /// [`ErrorKind::InvalidData`], after any valid text before it has been
+    /// yielded. The invalid bytes are skipped, so iteration may continue past
+    /// the error. A character left incomplete at the end of the stream yields
+    /// the same kind of error.
+    ///
+    /// Each chunk will also yield an error whenever [`fill_buf`] returns an
+    /// error of a kind other than [`ErrorKind::Interrupted`].
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    #[cfg(feature = "alloc")]
+    fn read_utf8_chunks(self) -> Utf8Chunks<Self>
+    where
+        Self: Sized,
+    {
+        Utf8Chunks {
+            buf: self,
+            carry: Vec::new(),
+        }
+    }
+}
+
+impl BufRead for &[u8] {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(*self)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        assert!(
+            amt <= self.len(),
+            "cannot consume {} bytes, only {} are available",
+            amt,
+            self.len()
+        );
+        *self = &self[amt..]
+    }
+}
+
+impl<B: BufRead + ?Sized> BufRead for &mut B {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line(buf)
+    }
+}
+
+impl<B: BufRead> BufRead for Option<B> {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        match self {
+            Some(b) => b.fill_buf(),
+            None => Ok(&[]),
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        if let Some(b) = self {
+            b.consume(amt)
+        }
+    }
+}
+
+impl<T> BufRead for Cursor<T>
+where
+    T: AsRef<[u8]>,
+{
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(self.remaining_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+// Write =========================================================================================
+
+pub(crate) fn default_write_vectored<F>(write: F, bufs: &[IoSlice<'_>]) -> Result<usize>
+where
+    F: FnOnce(&[u8]) -> Result<usize>,
+{
+    let buf = bufs
+        .iter()
+        .find(|b| !b.is_empty())
+        .map_or(&[][..], |b| &**b);
+    write(buf)
+}
+
+/// Writer adapter which accepts at most a fixed number of bytes per write.
+///
+/// This struct is generally created by calling [`chunked`] on a writer.
+/// Please see the documentation of [`chunked`] for more details.
+///
+/// [`chunked`]: Write::chunked
+#[derive(Debug)]
+pub struct ChunkedWriter<W> {
+    inner: W,
+    max: usize,
+}
+
+impl<W> ChunkedWriter<W> {
+    /// Returns the largest number of bytes accepted by a single write.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Consumes the `ChunkedWriter`, returning the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: Write> Write for ChunkedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let len = cmp::min(buf.len(), self.max);
+        self.inner.write(&buf[..len])
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        // Pass on as many whole buffers as fit; if not even the first
+        // non-empty one does, write a prefix of it instead.
+        let mut total = 0;
+        let mut count = 0;
+        for buf in bufs {
+            if total + buf.len() > self.max {
+                break;
+            }
+            total += buf.len();
+            count += 1;
+        }
+
+        if count == bufs.len() || total > 0 {
+            return self.inner.write_vectored(&bufs[..count]);
+        }
+
+        let buf = &bufs[count];
+        self.inner.write(&buf[..self.max])
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A trait for objects which are byte-oriented sinks.
+///
+/// Implementors of the `Write` trait are sometimes called 'writers'.
+///
+/// Writers are defined by two required methods, [`write`] and [`flush`]:
+///
+/// * The [`write`] method will attempt to write some data into the object,
+///   returning how many bytes were successfully written.
+///
+/// * The [`flush`] method is useful for adapters and explicit buffers
+///   themselves for ensuring that all buffered data has been pushed out to the
+///   'true sink'.
+///
+/// Writers are intended to be composable with one another. Many implementors
+/// throughout take and provide types which implement the `Write`
+/// trait.
+///
+/// [`write`]: Write::write
+/// [`flush`]: Write::flush
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Cursor, Write as _};
+/// # fn main() -> acid_io::Result<()> {
+/// let data = b"some bytes";
+///
+/// let mut arr = [0u8; 10];
+/// let mut buffer = Cursor::new(&mut arr[..]);
+/// buffer.write(data)?;
+///
+/// assert_eq!(buffer.get_ref(), data);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The trait also provides convenience methods like [`write_all`], which calls
+/// `write` in a loop until its entire input has been written.
+///
+/// [`write_all`]: Write::write_all
+pub trait Write {
+    /// Write a buffer into this writer, returning how many bytes were written.
+    ///
+    /// This function will attempt to write the entire contents of `buf`, but
+    /// the entire write might not succeed, or the write may also generate an
+    /// error. A call to `write` represents *at most one* attempt to write to
+    /// any wrapped object.
+    ///
+    /// Calls to `write` are not guaranteed to block waiting for data to be
+    /// written, and a write which would otherwise block can be indicated through
+    /// an [`Err`] variant.
+    ///
+    /// If the return value is [`Ok(n)`] then it must be guaranteed that
+    /// `n <= buf.len()`. A return value of `0` typically means that the
+    /// underlying object is no longer able to accept bytes and will likely not
+    /// be able to in the future as well, or that the buffer provided is empty.
+    ///
+    /// # Errors
+    ///
+    /// Each call to `write` may generate an I/O error indicating that the
+    /// operation could not be completed. If an error is returned then no bytes
+    /// in the buffer were written to this writer.
+    ///
+    /// It is **not** considered an error if the entire buffer could not be
+    /// written to this writer.
+    ///
+    /// An error of the [`ErrorKind::Interrupted`] kind is non-fatal and the
+    /// write operation should be retried if there is nothing else to do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::prelude::*;
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut dst = [0u8; 16];
+    ///
+    /// dst.as_mut_slice().write(b"some bytes")?;
+    ///
+    /// assert_eq!(&dst[..10], b"some bytes");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ok(n)`]: Ok
+    fn write(&mut self, src: &[u8]) -> Result<usize>;
+
+    /// Like [`write`], except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer
+    /// read from possibly being only partially consumed. This method must
+    /// behave as a call to [`write`] with the buffers concatenated would.
+    ///
+    /// The default implementation calls [`write`] with either the first nonempty
+    /// buffer provided, or an empty one if none exists.
     ///
     /// # Examples
     ///
     /// ```
     /// use acid_io::prelude::*;
+    /// use acid_io::{Cursor, IoSlice};
     ///
     /// # fn main() -> acid_io::Result<()> {
-    /// let data = b"Let's read this entire buffer!";
+    /// let mut data1 = [1; 8];
+    /// let mut data2 = [15; 8];
+    /// let io_slice1 = IoSlice::new(&mut data1);
+    /// let io_slice2 = IoSlice::new(&mut data2);
     ///
-    /// let mut src = &data[..];
-    /// let mut dst = Vec::new();
-    /// src.read_to_end(&mut dst)?;
+    /// let mut dst = [0u8; 16];
     ///
-    /// assert_eq!(&data[..], &dst);
+    /// dst.as_mut_slice().write_vectored(&[io_slice1, io_slice2])?;
+    /// assert_eq!(&dst[..8], &data1);
+    /// assert_eq!(&dst[8..], &data2);
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "alloc")]
-    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
-        io_alloc::default_read_to_end(self, buf)
+    ///
+    /// [`write`]: Write::write
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        default_write_vectored(|b| self.write(b), bufs)
     }
 
-    /// Read all bytes until EOF in this source, appending them to `buf`.
+    /// Determines if this `Write`r has an efficient [`write_vectored`]
+    /// implementation.
     ///
-    /// If successful, this function returns the number of bytes which were read
-    /// and appended to `buf`.
+    /// If a `Write`r does not override the default [`write_vectored`]
+    /// implementation, code using it may want to avoid the method all together
+    /// and coalesce writes into a single buffer for higher performance.
     ///
-    /// # Errors
+    /// The default implementation returns `false`.
     ///
-    /// If the data in this stream is *not* valid UTF-8 then an error is
-    /// returned and `buf` is unchanged.
+    /// This method mirrors an unstable API in `std::io`, but is a stable part
+    /// of `acid_io`'s API regardless of its status in `std`.
     ///
-    /// See [`read_to_end`] for other error semantics.
+    /// [`write_vectored`]: Write::write_vectored
     ///
-    /// [`read_to_end`]: Read::read_to_end
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{sink, Write};
+    ///
+    /// let mut dst = [0u8; 16];
+    /// assert!(dst.as_mut_slice().is_write_vectored());
+    /// assert!(sink().is_write_vectored());
+    /// ```
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
+    /// Like [`write_vectored`], except that several small buffers are written
+    /// with a single call to [`write`] even if this writer has no efficient
+    /// vectored implementation.
+    ///
+    /// If [`is_write_vectored`] returns `true`, this simply calls
+    /// [`write_vectored`]. Otherwise, if the first nonempty buffer is shorter
+    /// than the total, at most 512 bytes of `bufs` are gathered in order into
+    /// a buffer on the stack and written at once. This saves calls to the
+    /// underlying writer, and may save system calls, when writing many small
+    /// buffers. A first buffer holding at least 512 bytes, or all of the
+    /// bytes, is written directly.
+    ///
+    /// Like [`write_vectored`], this may write only part of the data and
+    /// returns the number of bytes written.
+    ///
+    /// [`write`]: Write::write
+    /// [`write_vectored`]: Write::write_vectored
+    /// [`is_write_vectored`]: Write::is_write_vectored
     ///
     /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
+    /// use acid_io::{IoSlice, Write};
     ///
-    /// # fn main() -> acid_io::Result<()> {
-    /// let mut buffer = String::new();
+    /// // A writer without an efficient `write_vectored`.
+    /// struct Unvectored<'a>(&'a mut [u8]);
     ///
-    /// let mut valid = &b"Some valid UTF-8"[..];
-    /// valid.read_to_string(&mut buffer)?;
+    /// impl Write for Unvectored<'_> {
+    ///     fn write(&mut self, buf: &[u8]) -> acid_io::Result<usize> {
+    ///         self.0.write(buf)
+    ///     }
     ///
-    /// let mut invalid = &b"\xFF\xFF\xFF\xFF"[..];
-    /// assert!(invalid.read_to_string(&mut buffer).is_err());
+    ///     fn flush(&mut self) -> acid_io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
     ///
-    /// assert_eq!(buffer, "Some valid UTF-8");
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut dst = [0u8; 16];
+    /// let mut w = Unvectored(&mut dst);
+    ///
+    /// let bufs = [IoSlice::new(b"a"), IoSlice::new(b"bc"), IoSlice::new(b"def")];
+    /// assert_eq!(w.write_vectored_gathered(&bufs)?, 6);
+    /// assert_eq!(&dst[..6], b"abcdef");
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "alloc")]
-    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
-        io_alloc::default_read_to_string(self, buf)
+    fn write_vectored_gathered(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        if self.is_write_vectored() {
+            return self.write_vectored(bufs);
+        }
+
+        let Some(len) = coalesced_len(bufs.iter().map(|b| b.len())) else {
+            return self.write_vectored(bufs);
+        };
+
+        let mut stack = [0u8; COALESCE_LEN];
+        let mut filled = 0;
+        for buf in bufs {
+            if filled == len {
+                break;
+            }
+            let amt = cmp::min(len - filled, buf.len());
+            stack[filled..filled + amt].copy_from_slice(&buf[..amt]);
+            filled += amt;
+        }
+        self.write(&stack[..len])
     }
 
-    /// Read the exact number of bytes required to fill `buf`.
+    /// Flush this output stream, ensuring that all intermediately buffered
+    /// contents reach their destination.
     ///
-    /// This function reads as many bytes as necessary to completely fill the
-    /// specified buffer `buf`.
+    /// # Errors
     ///
-    /// No guarantees are provided about the contents of `buf` when this
-    /// function is called; implementations cannot rely on any property of the
-    /// contents of `buf` being true. It is recommended that implementations
-    /// only write data to `buf` instead of reading its contents. The
-    /// documentation on [`read`] has a more detailed explanation on this
-    /// subject.
+    /// It is considered an error if not all bytes could be written due to
+    /// I/O errors or EOF being reached.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// If this function encounters an error of the kind
-    /// [`ErrorKind::Interrupted`] then the error is ignored and the operation
-    /// will continue.
+    /// ```
+    /// #[cfg(not(feature = "alloc"))]
+    /// # fn main() -> acid_io::Result<()> { Ok(()) }
+    /// #[cfg(feature = "alloc")]
+    /// # fn main() -> acid_io::Result<()> {
+    /// use acid_io::prelude::*;
+    /// use acid_io::BufWriter;
     ///
-    /// If this function encounters an "end of file" before completely filling
-    /// the buffer, it returns an error of the kind [`ErrorKind::UnexpectedEof`].
-    /// The contents of `buf` are unspecified in this case.
+    /// let mut dst = [0u8; 16];
+    /// let mut buffer = BufWriter::new(dst.as_mut_slice());
     ///
-    /// If any other read error is encountered then this function immediately
-    /// returns. The contents of `buf` are unspecified in this case.
+    /// buffer.write_all(b"some bytes")?;
+    /// buffer.flush()?;
+    /// drop(buffer);
     ///
-    /// If this function returns an error, it is unspecified how many bytes it
-    /// has read, but it will never read more than would be necessary to
-    /// completely fill the buffer.
+    /// assert_eq!(&dst[..10], b"some bytes");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn flush(&mut self) -> Result<()>;
+
+    /// Attempts to write an entire buffer into this writer.
     ///
-    /// # Examples
+    /// This method will continuously call [`write`] until there is no more data
+    /// to be written or an error of non-[`ErrorKind::Interrupted`] kind is
+    /// returned. This method will not return until the entire buffer has been
+    /// successfully written or such an error occurs. The first error that is
+    /// not of [`ErrorKind::Interrupted`] kind generated from this method will be
+    /// returned.
     ///
-    /// [`read`]: Read::read
+    /// If the buffer contains no data, this will never call [`write`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error of
+    /// non-[`ErrorKind::Interrupted`] kind that [`write`] returns.
+    ///
+    /// [`write`]: Write::write
     ///
     /// ```
-    /// use acid_io::Read;
+    /// use acid_io::prelude::*;
     ///
     /// # fn main() -> acid_io::Result<()> {
-    /// let src = [1u8, 2, 3, 4, 5, 6, 7, 8];
-    /// let mut dst = [0u8; 3];
+    /// let mut src = b"some bytes";
     ///
-    /// let mut r = &src[..];
+    /// let mut large = [0u8; 16];
+    /// large.as_mut_slice().write_all(src)?;
     ///
-    /// // read exactly 3 bytes
-    /// let n = r.read_exact(&mut dst[..])?;
+    /// // write_all to a buffer that's not large enough
+    /// let mut small = [0u8; 4];
+    /// let res = small.as_mut_slice().write_all(src);
+    /// assert!(res.is_err());
     ///
-    /// assert_eq!(dst, [1, 2, 3]);
     /// # Ok(())
     /// # }
     /// ```
-    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
-        default_read_exact(self, buf)
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf) {
+                Ok(0) => {
+                    return Err(Error::new_const(
+                        ErrorKind::WriteZero,
+                        &"failed to write whole buffer",
+                    ));
+                }
+                Ok(n) => buf = &buf[n..],
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
     }
 
-    /// Creates a "by reference" adapter for this instance of `Read`.
+    /// Attempts to write an entire buffer into this writer, flushing after
+    /// every `flush_every` bytes.
     ///
-    /// The returned adapter also implements `Read` and will simply borrow this
-    /// current reader.
+    /// `buf` is written with [`write_all`] in segments of `flush_every` bytes,
+    /// the last one possibly shorter, and [`flush`] is called after each
+    /// segment. This bounds how much data is left unflushed in intermediate
+    /// buffers during a large write.
+    ///
+    /// If the buffer contains no data, this will never call [`write`] or
+    /// [`flush`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error of
+    /// non-[`ErrorKind::Interrupted`] kind that [`write`] or [`flush`]
+    /// returns. It is unspecified how many bytes have been written in that
+    /// case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `flush_every` is 0.
+    ///
+    /// [`write`]: Write::write
+    /// [`write_all`]: Write::write_all
+    /// [`flush`]: Write::flush
     ///
     /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
+    /// use acid_io::Write;
     ///
     /// # fn main() -> acid_io::Result<()> {
-    /// let bytes = [1, 1, 2, 3, 5, 8, 13, 21];
-    /// let mut r = &bytes[..];
+    /// let mut out = [0u8; 100];
+    /// let mut writer = &mut out[..];
     ///
-    /// { // Use the reader by reference.
-    ///     let mut dst = [0u8; 4];
-    ///     let mut r2 = r.by_ref();
-    ///     r2.read(&mut dst)?;
-    ///     assert_eq!(dst, [1, 1, 2, 3]);
-    /// } // Drop the mutable borrow on the reader.
-    ///
-    /// let mut dst = [0u8; 4];
-    /// r.read(&mut dst)?;
-    /// assert_eq!(dst, [5, 8, 13, 21]);
+    /// // Flushes after bytes 32, 64, 96 and 100.
+    /// writer.write_all_flushing(&[1u8; 100], 32)?;
+    /// assert_eq!(out, [1u8; 100]);
     /// # Ok(())
     /// # }
     /// ```
-    fn by_ref(&mut self) -> &mut Self
-    where
-        Self: Sized,
-    {
-        self
+    fn write_all_flushing(&mut self, buf: &[u8], flush_every: usize) -> Result<()> {
+        assert!(flush_every > 0, "flush interval must be non-zero");
+
+        for segment in buf.chunks(flush_every) {
+            self.write_all(segment)?;
+            self.flush()?;
+        }
+        Ok(())
     }
 
-    /// Transforms this `Read` instance to an [`Iterator`] over its bytes.
+    /// Writes a message prefixed with its length.
     ///
-    /// The returned type implements [`Iterator`] where the [`Item`] is
-    /// [`Result<u8>`][Result]; The yielded item is [`Ok`] if a byte was
-    /// successfully read and [`Err`] otherwise. EOF is mapped to returning
-    /// [`None`] from this iterator.
+    /// The length of `data` is written as a `W`-byte big-endian integer,
+    /// followed by `data` itself. This is the inverse of
+    /// [`Read::read_length_prefixed`].
     ///
-    /// # Examples
+    /// # Errors
     ///
-    /// [`Item`]: Iterator::Item
-    /// [Result]: crate::Result
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if the length of
+    /// `data` does not fit in `W` bytes; nothing is written in that case.
+    /// Other errors are returned as from [`write_all`].
+    ///
+    /// [`write_all`]: Write::write_all
+    ///
+    /// # Panics
+    ///
+    /// Panics if `W` is 0.
+    ///
+    /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
+    /// use acid_io::Write;
     ///
     /// # fn main() -> acid_io::Result<()> {
-    /// let mut it = "Hi!".as_bytes().bytes();
+    /// let mut out = [0u8; 8];
+    /// let mut w = &mut out[..];
     ///
-    /// assert_eq!(it.next().transpose()?, Some(b'H'));
-    /// assert_eq!(it.next().transpose()?, Some(b'i'));
-    /// assert_eq!(it.next().transpose()?, Some(b'!'));
-    /// assert_eq!(it.next().transpose()?, None);
+    /// w.write_length_prefixed::<2>(b"hello")?;
+    /// assert_eq!(&out[..7], b"\x00\x05hello");
     /// # Ok(())
     /// # }
     /// ```
-    fn bytes(self) -> Bytes<Self>
+    fn write_length_prefixed<const W: usize>(&mut self, data: &[u8]) -> Result<()>
     where
         Self: Sized,
     {
-        Bytes { inner: self }
+        assert!(W > 0, "length prefix width must be non-zero");
+
+        let mut prefix = [0u8; W];
+        let mut len = data.len();
+        for b in prefix.iter_mut().rev() {
+            *b = len as u8;
+            len >>= 8;
+        }
+        if len != 0 {
+            return Err(Error::new_const(
+                ErrorKind::InvalidInput,
+                &"message too long for the length prefix",
+            ));
+        }
+
+        self.write_all(&prefix)?;
+        self.write_all(data)
     }
 
-    /// Creates an adapter which will chain this stream with another.
+    /// Attempts to write multiple buffers into this writer.
+    ///
+    /// This method will continuously call [`write_vectored`] until there is no
+    /// more data to be written or an error of non-[`ErrorKind::Interrupted`]
+    /// kind is returned. This method will not return until all buffers have
+    /// been successfully written or such an error occurs. The first error that
+    /// is not of [`ErrorKind::Interrupted`] kind generated from this method
+    /// will be returned.
+    ///
+    /// If the buffer contains no data, this will never call [`write_vectored`].
+    ///
+    /// # Notes
+    ///
+    /// Unlike [`write_vectored`], this takes a *mutable* reference to
+    /// a slice of [`IoSlice`]s, not an immutable one. That's because we need to
+    /// modify the slice to keep track of the bytes already written.
+    ///
+    /// Once this function returns, the contents of `bufs` are unspecified, as
+    /// this depends on how many calls to [`write_vectored`] were necessary. It is
+    /// best to understand this function as taking ownership of `bufs` and to
+    /// not use `bufs` afterwards. The underlying buffers, to which the
+    /// [`IoSlice`]s point (but not the [`IoSlice`]s themselves), are unchanged and
+    /// can be reused.
     ///
-    /// The returned `Read` instance will first read all bytes from this object
-    /// until EOF is encountered. Afterwards the output is equivalent to the
-    /// output of `next`.
+    /// This method mirrors an unstable API in `std::io`, but is a stable part
+    /// of `acid_io`'s API regardless of its status in `std`.
+    ///
+    /// [`write_vectored`]: Write::write_vectored
     ///
     /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
-    /// use acid_io::Cursor;
-    ///
     /// # #[cfg(not(feature = "alloc"))]
     /// # fn main() -> acid_io::Result<()> { Ok(()) }
     /// # #[cfg(feature = "alloc")]
     /// # fn main() -> acid_io::Result<()> {
-    /// let mut first = &b"Hello, ";
-    /// let mut second = Cursor::new("world!");
     ///
-    /// let mut s = String::new();
-    /// first.chain(second).read_to_string(&mut s)?;
+    /// use acid_io::{Write, IoSlice};
     ///
-    /// assert_eq!(s, "Hello, world!");
-    /// # Ok(())
-    /// # }
+    /// let mut writer = Vec::new();
+    /// let bufs = &mut [
+    ///     IoSlice::new(&[1]),
+    ///     IoSlice::new(&[2, 3]),
+    ///     IoSlice::new(&[4, 5, 6]),
+    /// ];
+    ///
+    /// writer.write_all_vectored(bufs)?;
+    /// // Note: the contents of `bufs` is now undefined, see the Notes section.
+    ///
+    /// assert_eq!(writer, &[1, 2, 3, 4, 5, 6]);
+    /// # Ok(()) }
     /// ```
-    fn chain<R: Read>(self, next: R) -> Chain<Self, R>
-    where
-        Self: Sized,
-    {
-        Chain {
-            first: self,
-            second: next,
-            done_first: false,
+    fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        // Guarantee that bufs is empty if it contains no data,
+        // to avoid calling write_vectored if there is no data to be written.
+        IoSlice::advance_slices(&mut bufs, 0);
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs) {
+                Ok(0) => {
+                    return Err(Error::new_const(
+                        ErrorKind::WriteZero,
+                        &"failed to write whole buffer",
+                    ));
+                }
+                Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
         }
+        Ok(())
     }
 
-    /// Creates an adapter which will read at most `limit` bytes from it.
+    /// Writes a formatted string into this writer, returning any error
+    /// encountered.
     ///
-    /// This function returns a new instance of `Read` which will read at most
-    /// `limit` bytes, after which it will always return EOF ([`Ok(0)`]). Any
-    /// read errors will not count towards the number of bytes read and future
-    /// calls to [`read()`] may succeed.
+    /// This method is primarily used to interface with the
+    /// [`format_args!()`] macro, and it is rare that this should
+    /// explicitly be called. The [`write!()`] macro should be favored to
+    /// invoke this method instead.
+    ///
+    /// This function internally uses the [`write_all`] method on
+    /// this trait and hence will continuously write data so long as no errors
+    /// are received. This also means that partial writes are not indicated in
+    /// this signature.
+    ///
+    /// [`write_all`]: Write::write_all
+    ///
+    /// # Errors
+    ///
+    /// This function will return any I/O error reported while formatting.
     ///
     /// # Examples
-
-    /// [`Ok(0)`]: Ok
-    /// [`read()`]: Read::read
     ///
     /// ```
     /// use acid_io::prelude::*;
     ///
-    /// # #[cfg(not(feature = "alloc"))]
-    /// # fn main() -> acid_io::Result<()> { Ok(()) }
-    /// # #[cfg(feature = "alloc")]
     /// # fn main() -> acid_io::Result<()> {
-    /// let mut r = &b"Bytes and more bytes";
-    ///
-    /// // read at most five bytes
-    /// let mut handle = r.take(5);
-    ///
-    /// let mut buffer = Vec::new();
-    /// handle.read_to_end(&mut buffer)?;
+    /// let mut buffer = [0u8; 32];
     ///
-    /// assert_eq!(&buffer, &b"Bytes");
+    /// // this call
+    /// write!(buffer.as_mut_slice(), "{:.*}", 2, 1.234567)?;
+    /// // turns into this:
+    /// buffer.as_mut_slice().write_fmt(format_args!("{:.*}", 2, 1.234567))?;
     /// # Ok(())
     /// # }
     /// ```
-    fn take(self, limit: u64) -> Take<Self>
-    where
-        Self: Sized,
-    {
-        Take { inner: self, limit }
-    }
-
-    #[doc(hidden)]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, None)
-    }
-}
-
-impl<R: Read> Read for &mut R {
-    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
-        (*self).read(dst)
-    }
-
-    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
-        (*self).read_exact(buf)
-    }
-}
-
-impl Read for &[u8] {
-    #[inline]
-    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
-        let copy_len = cmp::min(dst.len(), self.len());
-        let (src, rem) = self.split_at(copy_len);
-
-        // Avoid invoking memcpy for very small copies.
-        if copy_len == 1 {
-            dst[0] = src[0];
-        } else {
-            dst[..copy_len].copy_from_slice(src);
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        // Create a shim which translates a Write to a fmt::Write and saves
+        // off I/O errors. instead of discarding them
+        struct Adapter<'a, T: ?Sized + 'a> {
+            inner: &'a mut T,
+            error: Result<()>,
         }
 
-        *self = rem;
-
-        Ok(copy_len)
-    }
-
-    #[inline]
-    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
-        let mut nread = 0;
-        for buf in bufs {
-            nread += self.read(buf)?;
-            if self.is_empty() {
-                break;
+        impl<T: Write + ?Sized> fmt::Write for Adapter<'_, T> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                match self.inner.write_all(s.as_bytes()) {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        self.error = Err(e);
+                        Err(fmt::Error)
+                    }
+                }
             }
         }
 
-        Ok(nread)
-    }
-
-    #[inline]
-    fn is_read_vectored(&self) -> bool {
-        true
-    }
-
-    #[inline]
-    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
-        if buf.len() > self.len() {
-            return Err(Error::new_const(
-                ErrorKind::UnexpectedEof,
-                &"failed to fill whole buffer",
-            ));
-        }
-        let (a, b) = self.split_at(buf.len());
-
-        // First check if the amount of bytes we want to read is small:
-        // `copy_from_slice` will generally expand to a call to `memcpy`, and
-        // for a single byte the overhead is significant.
-        if buf.len() == 1 {
-            buf[0] = a[0];
-        } else {
-            buf.copy_from_slice(a);
+        let mut output = Adapter {
+            inner: self,
+            error: Ok(()),
+        };
+        match fmt::write(&mut output, fmt) {
+            Ok(()) => Ok(()),
+            Err(..) => {
+                // check if the error came from the underlying `Write` or not
+                if output.error.is_err() {
+                    output.error
+                } else {
+                    Err(fmt::Error.into())
+                }
+            }
         }
+    }
 
-        *self = b;
-        Ok(())
-    }
-
-    #[inline]
-    #[cfg(feature = "alloc")]
-    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
-        buf.extend_from_slice(*self);
-        let len = self.len();
-        *self = &self[len..];
-        Ok(len)
-    }
-
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len(), Some(self.len()))
-    }
-}
-
-// BufRead =======================================================================================
-
-/// A `BufRead` is a type of `Read`er which has an internal buffer, allowing it
-/// to perform extra ways of reading.
-///
-/// # Examples
-///
-/// In-memory buffers implement `BufRead`:
-///
-/// ```
-/// use acid_io::prelude::*;
-///
-/// let input = b"Here\nare\nsome\nlines\n";
-///
-/// # #[cfg(feature = "alloc")]
-/// # {
-/// for line in input.lines() {
-///     println!("{}", line.unwrap());
-/// }
-/// # }
-/// ```
-///
-/// If you have something that implements [`Read`], you can use the [`BufReader`
-/// type][`BufReader`] to turn it into a `BufRead`.
-///
-/// [`BufReader`]: crate::BufReader
-pub trait BufRead {
-    /// Returns the contents of the internal buffer, filling it with more data
-    /// from the inner reader if it is empty.
-    ///
-    /// This function is a lower-level call. It needs to be paired with the
-    /// [`consume`] method to function properly. When calling this
-    /// method, none of the contents will be "read" in the sense that later
-    /// calling `read` may return the same contents. As such, [`consume`] must
-    /// be called with the number of bytes that are consumed from this buffer to
-    /// ensure that the bytes are never returned twice.
-    ///
-    /// [`consume`]: BufRead::consume
-    ///
-    /// An empty buffer returned indicates that the stream has reached EOF.
-    ///
-    /// # Errors
-    ///
-    /// This function will return an I/O error if the underlying reader was
-    /// read, but returned an error.
-    fn fill_buf(&mut self) -> Result<&[u8]>;
-
-    /// Tells this buffer that `amt` bytes have been consumed from the buffer,
-    /// so they should no longer be returned in calls to `read`.
+    /// Like [`write_fmt`], but collects the formatted output before writing
+    /// it, so that it reaches this writer in a single call to [`write_all`].
     ///
-    /// This function is a lower-level call. It needs to be paired with the
-    /// [`fill_buf`] method to function properly. This function does
-    /// not perform any I/O, it simply informs this object that some amount of
-    /// its buffer, returned from [`fill_buf`], has been consumed and should
-    /// no longer be returned. As such, this function may do odd things if
-    /// [`fill_buf`] isn't called before calling it.
+    /// [`write_fmt`] writes each fragment produced by the formatter
+    /// separately, which can mean many tiny writes to an unbuffered writer.
+    /// This method instead formats into a 256-byte buffer on the stack. With
+    /// the `alloc` feature, output which does not fit spills into a `Vec`, and
+    /// everything is written at once; without it, the buffer is written out
+    /// each time it fills, so long output takes one write per 256 bytes.
     ///
-    /// The `amt` must be `<=` the number of bytes in the buffer returned by
-    /// [`fill_buf`].
+    /// [`write_fmt`]: Write::write_fmt
+    /// [`write_all`]: Write::write_all
     ///
-    /// # Examples
+    /// # Errors
     ///
-    /// Since `consume()` is meant to be used with [`fill_buf`],
-    /// that method's example includes an example of `consume()`.
+    /// This function will return any I/O error reported while writing, or an
+    /// error if a formatting trait implementation fails. In the latter case,
+    /// output which did not fit in the stack buffer may have been written
+    /// already if the `alloc` feature is disabled.
     ///
-    /// [`fill_buf`]: BufRead::fill_buf
-    fn consume(&mut self, amt: usize);
-
-    /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached.
+    /// # Examples
     ///
-    /// This function will read bytes from the underlying stream until the
-    /// delimiter or EOF is found. Once found, all bytes up to, and including,
-    /// the delimiter (if found) will be appended to `buf`.
+    /// ```
+    /// use acid_io::Write;
     ///
-    /// If successful, this function will return the total number of bytes read.
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buffer = [0u8; 32];
+    /// let mut out = buffer.as_mut_slice();
     ///
-    /// This function is blocking and should be used carefully: it is possible for
-    /// an attacker to continuously send bytes without ever sending the delimiter
-    /// or EOF.
+    /// out.write_fmt_buffered(format_args!("{}-{:03}", "id", 7))?;
+    /// assert_eq!(&buffer[..6], b"id-007");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn write_fmt_buffered(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        // Collects the output, writing it to `inner` early only if it does
+        // not fit.
+        struct Adapter<'a, T: ?Sized + 'a> {
+            inner: &'a mut T,
+            buf: [u8; 256],
+            len: usize,
+            #[cfg(feature = "alloc")]
+            spill: Vec<u8>,
+            error: Result<()>,
+        }
+
+        impl<T: Write + ?Sized> Adapter<'_, T> {
+            #[cfg(feature = "alloc")]
+            fn overflow(&mut self, s: &[u8]) -> Result<()> {
+                if self.spill.is_empty() {
+                    self.spill.extend_from_slice(&self.buf[..self.len]);
+                }
+                self.spill.extend_from_slice(s);
+                Ok(())
+            }
+
+            #[cfg(not(feature = "alloc"))]
+            fn overflow(&mut self, s: &[u8]) -> Result<()> {
+                self.inner.write_all(&self.buf[..self.len])?;
+                self.len = 0;
+                if s.len() > self.buf.len() {
+                    return self.inner.write_all(s);
+                }
+                self.buf[..s.len()].copy_from_slice(s);
+                self.len = s.len();
+                Ok(())
+            }
+
+            fn finish(self) -> Result<()> {
+                #[cfg(feature = "alloc")]
+                if !self.spill.is_empty() {
+                    return self.inner.write_all(&self.spill);
+                }
+                self.inner.write_all(&self.buf[..self.len])
+            }
+        }
+
+        impl<T: Write + ?Sized> fmt::Write for Adapter<'_, T> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let s = s.as_bytes();
+                #[cfg(feature = "alloc")]
+                let spilled = !self.spill.is_empty();
+                #[cfg(not(feature = "alloc"))]
+                let spilled = false;
+
+                if spilled || self.len + s.len() > self.buf.len() {
+                    if let Err(e) = self.overflow(s) {
+                        self.error = Err(e);
+                        return Err(fmt::Error);
+                    }
+                } else {
+                    self.buf[self.len..self.len + s.len()].copy_from_slice(s);
+                    self.len += s.len();
+                }
+                Ok(())
+            }
+        }
+
+        let mut output = Adapter {
+            inner: self,
+            buf: [0; 256],
+            len: 0,
+            #[cfg(feature = "alloc")]
+            spill: Vec::new(),
+            error: Ok(()),
+        };
+        match fmt::write(&mut output, fmt) {
+            Ok(()) => output.finish(),
+            Err(..) => {
+                // check if the error came from the underlying `Write` or not
+                if output.error.is_err() {
+                    output.error
+                } else {
+                    Err(fmt::Error.into())
+                }
+            }
+        }
+    }
+
+    /// Writes `fill` bytes until an offset of `current` would be advanced to
+    /// the next multiple of `align`, returning the number of bytes written.
     ///
-    /// # Errors
+    /// `current` is the caller's notion of how many bytes have already been
+    /// written, which lets this pad writers that cannot report their own
+    /// position. Nothing is written if `current` is already aligned, or if
+    /// `align` is 0 or 1. For writers which implement [`Seek`], see
+    /// [`Seek::align_to`].
     ///
-    /// This function will ignore all instances of [`ErrorKind::Interrupted`] and
-    /// will otherwise return any errors returned by [`fill_buf`].
+    /// # Errors
     ///
-    /// If an I/O error is encountered then all bytes read so far will be
-    /// present in `buf` and its length will have been adjusted appropriately.
+    /// This function will return the first error of non-[`ErrorKind::Interrupted`]
+    /// kind that [`write`] returns.
     ///
-    /// [`fill_buf`]: BufRead::fill_buf
+    /// [`write`]: Write::write
     ///
     /// # Examples
     ///
-    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
-    /// this example, we use [`Cursor`] to read all the bytes in a byte slice
-    /// in hyphen delimited segments:
-    ///
     /// ```
-    /// use acid_io::BufRead;
-    ///
-    /// let mut cursor = acid_io::Cursor::new(b"lorem-ipsum");
-    /// let mut buf = vec![];
+    /// use acid_io::Write;
     ///
-    /// // cursor is at 'l'
-    /// let num_bytes = cursor.read_until(b'-', &mut buf)
-    ///     .expect("reading from cursor won't fail");
-    /// assert_eq!(num_bytes, 6);
-    /// assert_eq!(buf, b"lorem-");
-    /// buf.clear();
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buffer = [0u8; 8];
+    /// let mut w = buffer.as_mut_slice();
     ///
-    /// // cursor is at 'i'
-    /// let num_bytes = cursor.read_until(b'-', &mut buf)
-    ///     .expect("reading from cursor won't fail");
-    /// assert_eq!(num_bytes, 5);
-    /// assert_eq!(buf, b"ipsum");
-    /// buf.clear();
+    /// w.write_all(b"abc")?;
+    /// assert_eq!(w.write_padding(3, 4, b'.')?, 1);
+    /// w.write_all(b"d")?;
+    /// assert_eq!(w.write_padding(5, 4, b'.')?, 3);
     ///
-    /// // cursor is at EOF
-    /// let num_bytes = cursor.read_until(b'-', &mut buf)
-    ///     .expect("reading from cursor won't fail");
-    /// assert_eq!(num_bytes, 0);
-    /// assert_eq!(buf, b"");
+    /// assert_eq!(&buffer, b"abc.d...");
+    /// # Ok(())
+    /// # }
     /// ```
-    #[cfg(feature = "alloc")]
-    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
-        io_alloc::read_until(self, byte, buf)
+    fn write_padding(&mut self, current: u64, align: u64, fill: u8) -> Result<u64> {
+        if align <= 1 {
+            return Ok(0);
+        }
+
+        let padding = match current % align {
+            0 => return Ok(0),
+            rem => align - rem,
+        };
+
+        let chunk = [fill; 64];
+        let mut remaining = padding;
+        while remaining > 0 {
+            let n = cmp::min(remaining, chunk.len() as u64) as usize;
+            self.write_all(&chunk[..n])?;
+            remaining -= n as u64;
+        }
+        Ok(padding)
     }
 
-    /// Read all bytes until a newline (the `0xA` byte) is reached, and append
-    /// them to the provided buffer.
-    ///
-    /// This function will read bytes from the underlying stream until the
-    /// newline delimiter (the `0xA` byte) or EOF is found. Once found, all bytes
-    /// up to, and including, the delimiter (if found) will be appended to
-    /// `buf`.
+    /// Writes every byte produced by an iterator, returning the number of
+    /// bytes written.
     ///
-    /// If successful, this function will return the total number of bytes read.
-    ///
-    /// If this function returns [`Ok(0)`], the stream has reached EOF.
-    ///
-    /// This function is blocking and should be used carefully: it is possible for
-    /// an attacker to continuously send bytes without ever sending a newline
-    /// or EOF.
-    ///
-    /// [`Ok(0)`]: Ok
+    /// Bytes are collected into a small buffer on the stack and written with
+    /// [`write_all`] each time it fills, so no heap allocation is needed.
     ///
     /// # Errors
     ///
-    /// This function has the same error semantics as [`read_until`] and will
-    /// also return an error if the read bytes are not valid UTF-8. If an I/O
-    /// error is encountered then `buf` may contain some bytes already read in
-    /// the event that all data read so far was valid UTF-8.
+    /// This function will return the first error of non-[`ErrorKind::Interrupted`]
+    /// kind that [`write`] returns. The iterator may have been partially
+    /// consumed, and some of its bytes written, when this happens.
     ///
-    /// [`read_until`]: BufRead::read_until
+    /// [`write_all`]: Write::write_all
+    /// [`write`]: Write::write
     ///
     /// # Examples
     ///
-    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
-    /// this example, we use [`Cursor`] to read all the lines in a byte slice:
-    ///
     /// ```
-    /// use acid_io::{self, BufRead};
-    ///
-    /// let mut cursor = acid_io::Cursor::new(b"foo\nbar");
-    /// let mut buf = String::new();
-    ///
-    /// // cursor is at 'f'
-    /// let num_bytes = cursor.read_line(&mut buf)
-    ///     .expect("reading from cursor won't fail");
-    /// assert_eq!(num_bytes, 4);
-    /// assert_eq!(buf, "foo\n");
-    /// buf.clear();
+    /// use acid_io::Write;
     ///
-    /// // cursor is at 'b'
-    /// let num_bytes = cursor.read_line(&mut buf)
-    ///     .expect("reading from cursor won't fail");
-    /// assert_eq!(num_bytes, 3);
-    /// assert_eq!(buf, "bar");
-    /// buf.clear();
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buffer = [0u8; 8];
+    /// let mut w = buffer.as_mut_slice();
     ///
-    /// // cursor is at EOF
-    /// let num_bytes = cursor.read_line(&mut buf)
-    ///     .expect("reading from cursor won't fail");
-    /// assert_eq!(num_bytes, 0);
-    /// assert_eq!(buf, "");
+    /// let n = w.write_iter(b"abcd".iter().map(|b| b.to_ascii_uppercase()))?;
+    /// assert_eq!(n, 4);
+    /// assert_eq!(&buffer[..4], b"ABCD");
+    /// # Ok(())
+    /// # }
     /// ```
-    #[cfg(feature = "alloc")]
-    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
-        // Note that we are not calling the `.read_until` method here, but
-        // rather our hardcoded implementation. For more details as to why, see
-        // the comments in `read_to_end`.
-        unsafe { io_alloc::append_to_string(buf, |b| io_alloc::read_until(self, b'\n', b)) }
+    fn write_iter<I: IntoIterator<Item = u8>>(&mut self, iter: I) -> Result<usize>
+    where
+        Self: Sized,
+    {
+        let mut chunk = [0u8; 256];
+        let mut len = 0;
+        let mut total = 0;
+        for byte in iter {
+            chunk[len] = byte;
+            len += 1;
+            if len == chunk.len() {
+                self.write_all(&chunk)?;
+                total += len;
+                len = 0;
+            }
+        }
+        self.write_all(&chunk[..len])?;
+        Ok(total + len)
     }
 
-    /// Returns an iterator over the contents of this reader split on the byte
-    /// `byte`.
-    ///
-    /// The iterator returned from this function will return instances of
-    /// <code>[io::Result]<[Vec]\<u8>></code>. Each vector returned will *not* have
-    /// the delimiter byte at the end.
-    ///
-    /// This function will yield errors whenever [`read_until`] would have
-    /// also yielded an error.
+    /// Creates a "by reference" adapter for this instance of `Write`.
     ///
-    /// [io::Result]: self::Result "io::Result"
-    /// [`read_until`]: BufRead::read_until
+    /// The returned adapter also implements `Write` and will simply borrow this
+    /// current writer.
     ///
     /// # Examples
     ///
-    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
-    /// this example, we use [`Cursor`] to iterate over all hyphen delimited
-    /// segments in a byte slice
-    ///
     /// ```
-    /// use acid_io::BufRead;
+    /// use acid_io::Write;
     ///
-    /// let cursor = acid_io::Cursor::new(b"lorem-ipsum-dolor");
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buffer = [0u8; 16];
     ///
-    /// let mut split_iter = cursor.split(b'-').map(|l| l.unwrap());
-    /// assert_eq!(split_iter.next(), Some(b"lorem".to_vec()));
-    /// assert_eq!(split_iter.next(), Some(b"ipsum".to_vec()));
-    /// assert_eq!(split_iter.next(), Some(b"dolor".to_vec()));
-    /// assert_eq!(split_iter.next(), None);
+    /// let mut w = buffer.as_mut_slice();
+    /// let reference = w.by_ref();
+    ///
+    /// // we can use reference just like our original buffer
+    /// reference.write_all(b"some bytes")?;
+    /// # Ok(())
+    /// # }
     /// ```
-    #[cfg(feature = "alloc")]
-    fn split(self, byte: u8) -> Split<Self>
+    fn by_ref(&mut self) -> &mut Self
     where
         Self: Sized,
     {
-        Split {
-            buf: self,
-            delim: byte,
-        }
+        self
     }
 
-    /// Returns an iterator over the lines of this reader.
-    ///
-    /// The iterator returned from this function will yield instances of
-    /// <code>[io::Result]<[String]></code>. Each string returned will *not* have a newline
-    /// byte (the `0xA` byte) or `CRLF` (`0xD`, `0xA` bytes) at the end.
+    /// Creates an adapter which accepts at most `max` bytes from each write.
     ///
-    /// [io::Result]: self::Result "io::Result"
+    /// Each [`write`] on the adapter passes on at most `max` bytes and reports
+    /// a short count for the rest, so [`write_all`] and similar loops are
+    /// forced to call it repeatedly. [`write_vectored`] is limited to `max`
+    /// bytes across all the buffers. This is useful for testing that code
+    /// which drives a writer handles short writes correctly.
     ///
-    /// # Examples
+    /// [`write`]: Write::write
+    /// [`write_all`]: Write::write_all
+    /// [`write_vectored`]: Write::write_vectored
     ///
-    /// [`acid_io::Cursor`][`Cursor`] is a type that implements `BufRead`. In
-    /// this example, we use [`Cursor`] to iterate over all the lines in a byte
-    /// slice.
+    /// # Panics
     ///
-    /// ```
-    /// use acid_io::BufRead;
+    /// Panics if `max` is 0, since every write would then fail to make
+    /// progress.
     ///
-    /// let cursor = acid_io::Cursor::new(b"lorem\nipsum\r\ndolor");
+    /// # Examples
     ///
-    /// let mut lines_iter = cursor.lines().map(|l| l.unwrap());
-    /// assert_eq!(lines_iter.next(), Some(String::from("lorem")));
-    /// assert_eq!(lines_iter.next(), Some(String::from("ipsum")));
-    /// assert_eq!(lines_iter.next(), Some(String::from("dolor")));
-    /// assert_eq!(lines_iter.next(), None);
     /// ```
+    /// use acid_io::Write;
     ///
-    /// # Errors
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buffer = [0u8; 13];
+    /// let mut w = buffer.as_mut_slice().chunked(4);
     ///
-    /// Each line of the iterator has the same error semantics as [`BufRead::read_line`].
-    #[cfg(feature = "alloc")]
-    fn lines(self) -> Lines<Self>
+    /// assert_eq!(w.write(b"Hello, world!")?, 4);
+    ///
+    /// // `write_all` loops over the short writes.
+    /// w.write_all(b"o, world!")?;
+    /// assert_eq!(&buffer, b"Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn chunked(self, max: usize) -> ChunkedWriter<Self>
     where
         Self: Sized,
     {
-        Lines { buf: self }
+        assert!(max != 0, "chunk size must be non-zero");
+        ChunkedWriter { inner: self, max }
     }
 }
 
-impl BufRead for &[u8] {
+impl<W: Write + ?Sized> Write for &mut W {
     #[inline]
-    fn fill_buf(&mut self) -> Result<&[u8]> {
-        Ok(*self)
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
     }
 
     #[inline]
-    fn consume(&mut self, amt: usize) {
-        *self = &self[amt..]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
     }
 }
 
-impl<B: BufRead + ?Sized> BufRead for &mut B {
+impl Write for &mut [u8] {
     #[inline]
-    fn fill_buf(&mut self) -> Result<&[u8]> {
-        (**self).fill_buf()
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        let copy_len = cmp::min(src.len(), self.len());
+
+        // Move slice out of self before splitting to appease borrowck.
+        let (dst, rem) = mem::take(self).split_at_mut(copy_len);
+        dst.copy_from_slice(&src[..copy_len]);
+
+        *self = rem;
+
+        Ok(copy_len)
     }
 
     #[inline]
-    fn consume(&mut self, amt: usize) {
-        (**self).consume(amt)
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            nwritten += self.write(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+
+        Ok(nwritten)
     }
 
-    #[cfg(feature = "alloc")]
     #[inline]
-    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
-        (**self).read_until(byte, buf)
+    fn is_write_vectored(&self) -> bool {
+        true
     }
 
-    #[cfg(feature = "alloc")]
     #[inline]
-    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
-        (**self).read_line(buf)
+    fn write_all(&mut self, data: &[u8]) -> Result<()> {
+        if self.write(data)? == data.len() {
+            Ok(())
+        } else {
+            Err(Error::new_const(
+                ErrorKind::WriteZero,
+                &"failed to write whole buffer",
+            ))
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
     }
 }
 
-impl<T> BufRead for Cursor<T>
-where
-    T: AsRef<[u8]>,
-{
-    fn fill_buf(&mut self) -> Result<&[u8]> {
-        Ok(self.remaining_slice())
+/// An absent writer discards everything written to it, like [`sink()`].
+///
+/// `None` accepts every write in full and flushes successfully, while
+/// `Some(writer)` delegates to `writer`.
+///
+/// [`sink()`]: crate::sink
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::Write;
+///
+/// let mut absent: Option<&mut [u8]> = None;
+/// assert_eq!(absent.write(b"discarded").unwrap(), 9);
+/// ```
+impl<W: Write> Write for Option<W> {
+    #[inline]
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        match self {
+            Some(w) => w.write(src),
+            None => Ok(src.len()),
+        }
     }
 
-    fn consume(&mut self, amt: usize) {
-        self.pos += amt as u64;
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        match self {
+            Some(w) => w.write_vectored(bufs),
+            None => Ok(bufs.iter().map(|b| b.len()).sum()),
+        }
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        match self {
+            Some(w) => w.is_write_vectored(),
+            None => true,
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Some(w) => w.flush(),
+            None => Ok(()),
+        }
     }
 }
 
-// Write =========================================================================================
+/// A pair of writers receives every write in turn.
+///
+/// Each call to [`write`] writes the whole buffer to the first writer with
+/// [`write_all`], then to the second, and reports the full buffer as written.
+/// [`flush`] flushes both writers, even if flushing the first one fails.
+///
+/// If an error occurs the first error is returned. Note that the first writer
+/// may already have received data that the second did not, so retrying the
+/// write duplicates that data in the first writer.
+///
+/// [`write`]: Write::write
+/// [`write_all`]: Write::write_all
+/// [`flush`]: Write::flush
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::Write;
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let (mut a, mut b) = ([0u8; 5], [0u8; 5]);
+/// let mut both = (&mut a[..], &mut b[..]);
+/// both.write_all(b"hello")?;
+///
+/// assert_eq!(&a, b"hello");
+/// assert_eq!(&b, b"hello");
+/// # Ok(())
+/// # }
+/// ```
+impl<A: Write, B: Write> Write for (A, B) {
+    /// Writes all of `src` to the first writer, then to the second.
+    ///
+    /// Unlike most writers, this may return an error after data has been
+    /// written: an error from the second writer is returned once the first
+    /// has taken all of `src`, as a pair has nowhere to keep the error for
+    /// the next call. Retrying after an error therefore writes the same data
+    /// to the first writer again.
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        self.0.write_all(src)?;
+        self.1.write_all(src)?;
+        Ok(src.len())
+    }
 
-pub(crate) fn default_write_vectored<F>(write: F, bufs: &[IoSlice<'_>]) -> Result<usize>
-where
-    F: FnOnce(&[u8]) -> Result<usize>,
-{
-    let buf = bufs
-        .iter()
-        .find(|b| !b.is_empty())
-        .map_or(&[][..], |b| &**b);
-    write(buf)
+    fn flush(&mut self) -> Result<()> {
+        let first = self.0.flush();
+        let second = self.1.flush();
+        first.and(second)
+    }
+}
+
+/// Writer adapter which sends the bytes before a fixed offset to one writer
+/// and the rest to another.
+///
+/// The adapter tracks how many bytes have been written through it. Bytes at
+/// offsets below the boundary go to the first writer, and bytes at or past it
+/// go to the second, so a header region can be written separately from the
+/// body which follows it. A single write which straddles the boundary is
+/// split between the two writers.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{SplitWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut header = [0u8; 4];
+/// let mut body = [0u8; 8];
+/// let mut writer = SplitWriter::new(&mut header[..], 4, &mut body[..]);
+///
+/// writer.write_all(b"HEADbody")?;
+/// assert_eq!(writer.position(), 8);
+///
+/// assert_eq!(&header, b"HEAD");
+/// assert_eq!(&body[..4], b"body");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SplitWriter<A, B> {
+    first: A,
+    second: B,
+    boundary: u64,
+    pos: u64,
+}
+
+impl<A, B> SplitWriter<A, B> {
+    /// Creates a new `SplitWriter` which writes the first `boundary` bytes to
+    /// `first` and everything after them to `second`.
+    pub fn new(first: A, boundary: u64, second: B) -> SplitWriter<A, B> {
+        SplitWriter {
+            first,
+            second,
+            boundary,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes written through this adapter.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns the offset at which writes switch to the second writer.
+    pub fn boundary(&self) -> u64 {
+        self.boundary
+    }
+
+    /// Consumes the `SplitWriter`, returning the wrapped writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// Gets references to the underlying writers.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Gets mutable references to the underlying writers.
+    ///
+    /// Bytes written directly to the underlying writers are not counted
+    /// towards the position.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+}
+
+impl<A: Write, B: Write> Write for SplitWriter<A, B> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        if self.pos >= self.boundary {
+            let n = self.second.write(src)?;
+            self.pos = self.pos.saturating_add(n as u64);
+            return Ok(n);
+        }
+
+        let room = cmp::min(src.len() as u64, self.boundary - self.pos) as usize;
+        let n = self.first.write(&src[..room])?;
+        self.pos += n as u64;
+        if n < room || room == src.len() {
+            return Ok(n);
+        }
+
+        // The write straddles the boundary. The first part has been accepted,
+        // so an error from the second writer is left for the next write.
+        match self.second.write(&src[room..]) {
+            Ok(m) => {
+                self.pos = self.pos.saturating_add(m as u64);
+                Ok(n + m)
+            }
+            Err(_) => Ok(n),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let first = self.first.flush();
+        let second = self.second.flush();
+        first.and(second)
+    }
+}
+
+/// Writer adapter which checks that exactly a given number of bytes is
+/// written through it.
+///
+/// Bytes are passed to the underlying writer unchanged and counted. The count
+/// is only checked by [`finish`], so that an encoder which announced a length
+/// up front and then wrote a different amount fails with a clear error
+/// instead of producing corrupt output silently.
+///
+/// [`finish`]: ExpectWriteLen::finish
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{ErrorKind, ExpectWriteLen, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut out = [0u8; 8];
+/// let mut writer = ExpectWriteLen::new(&mut out[..], 5);
+/// writer.write_all(b"hello")?;
+/// writer.finish()?;
+///
+/// let mut writer = ExpectWriteLen::new(&mut out[..], 5);
+/// writer.write_all(b"hey")?;
+/// assert_eq!(writer.finish().unwrap_err().kind(), ErrorKind::InvalidData);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ExpectWriteLen<W> {
+    inner: W,
+    expected: u64,
+    written: u64,
+}
+
+impl<W: Write> ExpectWriteLen<W> {
+    /// Creates a new `ExpectWriteLen` which expects exactly `expected` bytes
+    /// to be written to `inner`.
+    pub fn new(inner: W, expected: u64) -> ExpectWriteLen<W> {
+        ExpectWriteLen {
+            inner,
+            expected,
+            written: 0,
+        }
+    }
+
+    /// Returns the number of bytes expected in total.
+    pub fn expected(&self) -> u64 {
+        self.expected
+    }
+
+    /// Returns the number of bytes written through this adapter.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Checks that exactly the expected number of bytes was written, then
+    /// flushes and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidData`] if fewer or more
+    /// bytes than expected were written; the writer is not flushed in that
+    /// case. Otherwise, returns any error from flushing.
+    pub fn finish(mut self) -> Result<W> {
+        if self.written < self.expected {
+            return Err(Error::new_const(
+                ErrorKind::InvalidData,
+                &"fewer bytes written than expected",
+            ));
+        }
+        if self.written > self.expected {
+            return Err(Error::new_const(
+                ErrorKind::InvalidData,
+                &"more bytes written than expected",
+            ));
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for ExpectWriteLen<W> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        let n = self.inner.write(src)?;
+        self.written = self.written.saturating_add(n as u64);
+        Ok(n)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let n = self.inner.write_vectored(bufs)?;
+        self.written = self.written.saturating_add(n as u64);
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Seek ==========================================================================================
+
+/// Enumeration of possible methods to seek within an I/O object.
+///
+/// It is used by the [`Seek`] trait.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeekFrom {
+    /// Sets the offset to the provided number of bytes.
+    Start(u64),
+    /// Sets the offset to the size of this object plus the specified number of
+    /// bytes.
+    ///
+    /// It is possible to seek beyond the end of an object, but it's an error to
+    /// seek before byte 0.
+    End(i64),
+    /// Sets the offset to the current position plus the specified number of
+    /// bytes.
+    ///
+    /// It is possible to seek beyond the end of an object, but it's an error to
+    /// seek before byte 0.
+    Current(i64),
 }
 
-/// A trait for objects which are byte-oriented sinks.
-///
-/// Implementors of the `Write` trait are sometimes called 'writers'.
-///
-/// Writers are defined by two required methods, [`write`] and [`flush`]:
-///
-/// * The [`write`] method will attempt to write some data into the object,
-///   returning how many bytes were successfully written.
-///
-/// * The [`flush`] method is useful for adapters and explicit buffers
-///   themselves for ensuring that all buffered data has been pushed out to the
-///   'true sink'.
-///
-/// Writers are intended to be composable with one another. Many implementors
-/// throughout take and provide types which implement the `Write`
-/// trait.
-///
-/// [`write`]: Write::write
-/// [`flush`]: Write::flush
-///
-/// # Examples
-///
-/// ```
-/// use acid_io::{Cursor, Write as _};
-/// # fn main() -> acid_io::Result<()> {
-/// let data = b"some bytes";
-///
-/// let mut arr = [0u8; 10];
-/// let mut buffer = Cursor::new(&mut arr[..]);
-/// buffer.write(data)?;
-///
-/// assert_eq!(buffer.get_ref(), data);
-/// # Ok(())
-/// # }
-/// ```
-///
-/// The trait also provides convenience methods like [`write_all`], which calls
-/// `write` in a loop until its entire input has been written.
+/// The `Seek` trait provides a cursor which can be moved within a stream of
+/// bytes.
 ///
-/// [`write_all`]: Write::write_all
-pub trait Write {
-    /// Write a buffer into this writer, returning how many bytes were written.
+/// The stream typically has a fixed size, allowing seeking relative to either
+/// end or the current offset.
+pub trait Seek {
+    /// Seek to an offset, in bytes, in a stream.
     ///
-    /// This function will attempt to write the entire contents of `buf`, but
-    /// the entire write might not succeed, or the write may also generate an
-    /// error. A call to `write` represents *at most one* attempt to write to
-    /// any wrapped object.
+    /// A seek beyond the end of a stream is allowed, but behavior is defined
+    /// by the implementation.
     ///
-    /// Calls to `write` are not guaranteed to block waiting for data to be
-    /// written, and a write which would otherwise block can be indicated through
-    /// an [`Err`] variant.
+    /// If the seek operation completed successfully,
+    /// this method returns the new position from the start of the stream.
+    /// That position can be used later with [`SeekFrom::Start`].
     ///
-    /// If the return value is [`Ok(n)`] then it must be guaranteed that
-    /// `n <= buf.len()`. A return value of `0` typically means that the
-    /// underlying object is no longer able to accept bytes and will likely not
-    /// be able to in the future as well, or that the buffer provided is empty.
+    /// # Errors
+    ///
+    /// Seeking can fail, for example because it might involve flushing a buffer.
+    ///
+    /// Seeking to a negative offset is considered an error.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64>;
+
+    /// Rewind to the beginning of a stream.
+    ///
+    /// This is a convenience method, equivalent to `seek(SeekFrom::Start(0))`.
     ///
     /// # Errors
     ///
-    /// Each call to `write` may generate an I/O error indicating that the
-    /// operation could not be completed. If an error is returned then no bytes
-    /// in the buffer were written to this writer.
+    /// Rewinding can fail, for example because it might involve flushing a buffer.
+    fn rewind(&mut self) -> Result<()> {
+        self.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
+
+    /// Returns the length of this stream (in bytes).
     ///
-    /// It is **not** considered an error if the entire buffer could not be
-    /// written to this writer.
+    /// This method is implemented using up to three seek operations. If this
+    /// method returns successfully, the seek position is unchanged (i.e. the
+    /// position before calling this method is the same as afterwards).
+    /// However, if this method returns an error, the seek position is
+    /// unspecified.
     ///
-    /// An error of the [`ErrorKind::Interrupted`] kind is non-fatal and the
-    /// write operation should be retried if there is nothing else to do.
+    /// If you need to obtain the length of *many* streams and you don't care
+    /// about the seek position afterwards, you can reduce the number of seek
+    /// operations by simply calling `seek(SeekFrom::End(0))` and using its
+    /// return value (it is also the stream length).
+    ///
+    /// Note that length of a stream can change over time (for example, when
+    /// data is appended to a file). So calling this method multiple times does
+    /// not necessarily return the same length each time.
+    fn stream_len(&mut self) -> Result<u64> {
+        let old_pos = self.stream_position()?;
+        let len = self.seek(SeekFrom::End(0))?;
+
+        // Avoid seeking a third time when we were already at the end of the
+        // stream. The branch is usually way cheaper than a seek operation.
+        if old_pos != len {
+            self.seek(SeekFrom::Start(old_pos))?;
+        }
+
+        Ok(len)
+    }
+
+    /// Returns the length of this stream (in bytes), reporting whether the
+    /// seek position could be restored if measuring fails.
+    ///
+    /// This behaves like [`stream_len`], but never leaves the seek position
+    /// silently moved. If this method returns successfully, the seek position
+    /// is unchanged. If it returns an error, [`StreamLenError::measured_len`]
+    /// tells the two failure cases apart:
+    ///
+    /// * `None`: the length could not be measured. The seek position is
+    ///   unspecified.
+    /// * `Some(len)`: the length was measured as `len`, but restoring the
+    ///   original position failed. The seek position is unspecified (and is
+    ///   most likely at the end of the stream).
+    ///
+    /// [`stream_len`]: Seek::stream_len
     ///
     /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
-    ///
-    /// # fn main() -> acid_io::Result<()> {
-    /// let mut dst = [0u8; 16];
+    /// use acid_io::{Cursor, Seek, SeekFrom};
     ///
-    /// dst.as_mut_slice().write(b"some bytes")?;
+    /// let mut c = Cursor::new(b"some bytes");
+    /// c.seek(SeekFrom::Start(3)).unwrap();
     ///
-    /// assert_eq!(&dst[..10], b"some bytes");
-    /// # Ok(())
-    /// # }
+    /// assert_eq!(c.stream_len_checked().unwrap(), 10);
+    /// assert_eq!(c.position(), 3);
     /// ```
+    fn stream_len_checked(&mut self) -> core::result::Result<u64, StreamLenError> {
+        let old_pos = self
+            .stream_position()
+            .map_err(|e| StreamLenError::new(e, None))?;
+        let len = self
+            .seek(SeekFrom::End(0))
+            .map_err(|e| StreamLenError::new(e, None))?;
+
+        if old_pos != len {
+            self.seek(SeekFrom::Start(old_pos))
+                .map_err(|e| StreamLenError::new(e, Some(len)))?;
+        }
+
+        Ok(len)
+    }
+
+    /// Returns the current seek position from the start of the stream.
     ///
-    /// [`Ok(n)`]: Ok
-    fn write(&mut self, src: &[u8]) -> Result<usize>;
+    /// This is equivalent to `self.seek(SeekFrom::Current(0))`.
+    fn stream_position(&mut self) -> Result<u64> {
+        self.seek(SeekFrom::Current(0))
+    }
 
-    /// Like [`write`], except that it writes from a slice of buffers.
+    /// Returns `true` if this stream supports seeking relative to its end with
+    /// [`SeekFrom::End`].
     ///
-    /// Data is copied from each buffer in order, with the final buffer
-    /// read from possibly being only partially consumed. This method must
-    /// behave as a call to [`write`] with the buffers concatenated would.
+    /// Streams whose length is not known, such as a pipe which can only seek
+    /// forwards from its current position, should override this to return
+    /// `false`. Generic code can then pick a strategy up front, for example
+    /// falling back to forward-only parsing, rather than attempting the seek
+    /// and handling the error.
     ///
-    /// The default implementation calls [`write`] with either the first nonempty
-    /// buffer provided, or an empty one if none exists.
+    /// This is only a hint: a stream which returns `true` may still fail a
+    /// particular end-relative seek. The default implementation returns
+    /// `true`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
-    /// use acid_io::{Cursor, IoSlice};
+    /// use acid_io::{Cursor, Seek, SeekFrom};
     ///
     /// # fn main() -> acid_io::Result<()> {
-    /// let mut data1 = [1; 8];
-    /// let mut data2 = [15; 8];
-    /// let io_slice1 = IoSlice::new(&mut data1);
-    /// let io_slice2 = IoSlice::new(&mut data2);
+    /// let mut stream = Cursor::new(b"header...footer");
     ///
-    /// let mut dst = [0u8; 16];
-    ///
-    /// dst.as_mut_slice().write_vectored(&[io_slice1, io_slice2])?;
-    /// assert_eq!(&dst[..8], &data1);
-    /// assert_eq!(&dst[8..], &data2);
+    /// if stream.supports_end_seek() {
+    ///     stream.seek(SeekFrom::End(-6))?;
+    /// }
+    /// assert_eq!(stream.position(), 9);
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// [`write`]: Write::write
-    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
-        default_write_vectored(|b| self.write(b), bufs)
+    fn supports_end_seek(&self) -> bool {
+        true
     }
 
-    /// Determines if this `Write`r has an efficient [`write_vectored`]
-    /// implementation.
+    /// Returns the current position from the start of the stream if it is
+    /// known without seeking, or `None` otherwise.
     ///
-    /// If a `Write`r does not override the default [`write_vectored`]
-    /// implementation, code using it may want to avoid the method all together
-    /// and coalesce writes into a single buffer for higher performance.
+    /// Streams which keep track of their own position, such as [`Cursor`] and
+    /// [`CachedSeek`], override this. Adapters can then skip passing on a
+    /// seek which would not move the stream, avoiding a potentially expensive
+    /// call. The default implementation returns `None`.
     ///
-    /// The default implementation returns `false`.
+    /// # Examples
     ///
-    /// [`write_vectored`]: Write::write_vectored
-    // TODO(dataphract):
-    // Since this is unstable in std::io, it can't be exposed in our stable
-    // public API. However, the efficiency of std::io's (and thus our) Write
-    // implementations depends on it, so it's important that it be available.
-    #[doc(hidden)]
-    fn is_write_vectored(&self) -> bool {
-        false
+    /// ```
+    /// use acid_io::{Cursor, Seek};
+    ///
+    /// let mut stream = Cursor::new(b"abc");
+    /// stream.set_position(2);
+    /// assert_eq!(stream.known_position(), Some(2));
+    /// ```
+    fn known_position(&self) -> Option<u64> {
+        None
     }
 
-    /// Flush this output stream, ensuring that all intermediately buffered
-    /// contents reach their destination.
+    /// Seeks forward to the next multiple of `align`, returning the new
+    /// position from the start of the stream.
+    ///
+    /// If the current position is already a multiple of `align`, the
+    /// position is not changed. An `align` of 0 or 1 never moves the
+    /// position.
     ///
     /// # Errors
     ///
-    /// It is considered an error if not all bytes could be written due to
-    /// I/O errors or EOF being reached.
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if the next
+    /// multiple of `align` does not fit in a `u64`.
     ///
     /// # Examples
     ///
     /// ```
-    /// #[cfg(not(feature = "alloc"))]
-    /// # fn main() -> acid_io::Result<()> { Ok(()) }
-    /// #[cfg(feature = "alloc")]
-    /// # fn main() -> acid_io::Result<()> {
-    /// use acid_io::prelude::*;
-    /// use acid_io::BufWriter;
-    ///
-    /// let mut dst = [0u8; 16];
-    /// let mut buffer = BufWriter::new(dst.as_mut_slice());
+    /// use acid_io::{Cursor, Seek, SeekFrom};
     ///
-    /// buffer.write_all(b"some bytes")?;
-    /// buffer.flush()?;
-    /// drop(buffer);
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut c = Cursor::new([0u8; 32]);
+    /// c.seek(SeekFrom::Start(5))?;
     ///
-    /// assert_eq!(&dst[..10], b"some bytes");
+    /// assert_eq!(c.align_to(8)?, 8);
+    /// assert_eq!(c.align_to(8)?, 8);
+    /// assert_eq!(c.align_to(0)?, 8);
     /// # Ok(())
     /// # }
     /// ```
-    fn flush(&mut self) -> Result<()>;
+    fn align_to(&mut self, align: u64) -> Result<u64> {
+        let pos = self.stream_position()?;
+        if align <= 1 {
+            return Ok(pos);
+        }
 
-    /// Attempts to write an entire buffer into this writer.
-    ///
-    /// This method will continuously call [`write`] until there is no more data
-    /// to be written or an error of non-[`ErrorKind::Interrupted`] kind is
-    /// returned. This method will not return until the entire buffer has been
-    /// successfully written or such an error occurs. The first error that is
-    /// not of [`ErrorKind::Interrupted`] kind generated from this method will be
-    /// returned.
+        match pos % align {
+            0 => Ok(pos),
+            rem => {
+                let target = pos.checked_add(align - rem).ok_or_else(|| {
+                    Error::new_const(
+                        ErrorKind::InvalidInput,
+                        &"alignment overflows the stream position",
+                    )
+                })?;
+                self.seek(SeekFrom::Start(target))
+            }
+        }
+    }
+}
+
+/// Extension methods for streams which are both [`Read`] and [`Seek`].
+///
+/// This trait is implemented for every such type.
+pub trait ReadSeekExt: Read + Seek {
+    /// Reads the last `buf.len()` bytes of the stream into `buf`, then
+    /// restores the original seek position.
     ///
-    /// If the buffer contains no data, this will never call [`write`].
+    /// This is intended for formats which store a fixed-size footer or
+    /// trailer at the end of the stream. The stream length is measured only
+    /// once.
     ///
     /// # Errors
     ///
-    /// This function will return the first error of
-    /// non-[`ErrorKind::Interrupted`] kind that [`write`] returns.
+    /// If the stream is shorter than `buf`, an error of kind
+    /// [`ErrorKind::UnexpectedEof`] is returned and nothing is read. Any error
+    /// from seeking or reading is returned as well. In every case, an attempt
+    /// is made to restore the original position; if only that fails, its
+    /// error is returned and the position is unspecified.
     ///
-    /// [`write`]: Write::write
+    /// # Examples
     ///
     /// ```
-    /// use acid_io::prelude::*;
+    /// use acid_io::{Cursor, ReadSeekExt};
     ///
     /// # fn main() -> acid_io::Result<()> {
-    /// let mut src = b"some bytes";
-    ///
-    /// let mut large = [0u8; 16];
-    /// large.as_mut_slice().write_all(src)?;
-    ///
-    /// // write_all to a buffer that's not large enough
-    /// let mut small = [0u8; 4];
-    /// let res = small.as_mut_slice().write_all(src);
-    /// assert!(res.is_err());
+    /// let mut c = Cursor::new(b"payload|END!");
+    /// c.set_position(2);
     ///
+    /// let mut footer = [0; 4];
+    /// c.read_footer(&mut footer)?;
+    /// assert_eq!(&footer, b"END!");
+    /// assert_eq!(c.position(), 2);
     /// # Ok(())
     /// # }
     /// ```
-    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
-        while !buf.is_empty() {
-            match self.write(buf) {
-                Ok(0) => {
-                    return Err(Error::new_const(
-                        ErrorKind::WriteZero,
-                        &"failed to write whole buffer",
-                    ));
-                }
-                Ok(n) => buf = &buf[n..],
-                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
-                Err(e) => return Err(e),
-            }
-        }
-        Ok(())
+    fn read_footer(&mut self, buf: &mut [u8]) -> Result<()> {
+        let old_pos = self.stream_position()?;
+        let len = self.seek(SeekFrom::End(0))?;
+
+        let result = match len.checked_sub(buf.len() as u64) {
+            Some(start) => self
+                .seek(SeekFrom::Start(start))
+                .and_then(|_| self.read_exact(buf)),
+            None => Err(Error::new_const(
+                ErrorKind::UnexpectedEof,
+                &"stream is shorter than the footer",
+            )),
+        };
+
+        let restored = self.seek(SeekFrom::Start(old_pos));
+        result?;
+        restored.map(|_| ())
     }
 
-    /// Attempts to write multiple buffers into this writer.
-    ///
-    /// This method will continuously call [`write_vectored`] until there is no
-    /// more data to be written or an error of non-[`ErrorKind::Interrupted`]
-    /// kind is returned. This method will not return until all buffers have
-    /// been successfully written or such an error occurs. The first error that
-    /// is not of [`ErrorKind::Interrupted`] kind generated from this method
-    /// will be returned.
-    ///
-    /// If the buffer contains no data, this will never call [`write_vectored`].
+    /// Rewinds to the beginning of the stream and reads all of it, appending
+    /// it to `buf`.
     ///
-    /// # Notes
+    /// This is a convenience method, equivalent to [`rewind`] followed by
+    /// [`Read::read_to_end`]. On success, the seek position is left at the
+    /// end of the stream. Returns the number of bytes read.
     ///
-    /// Unlike [`write_vectored`], this takes a *mutable* reference to
-    /// a slice of [`IoSlice`]s, not an immutable one. That's because we need to
-    /// modify the slice to keep track of the bytes already written.
+    /// [`rewind`]: Seek::rewind
     ///
-    /// Once this function returns, the contents of `bufs` are unspecified, as
-    /// this depends on how many calls to [`write_vectored`] were necessary. It is
-    /// best to understand this function as taking ownership of `bufs` and to
-    /// not use `bufs` afterwards. The underlying buffers, to which the
-    /// [`IoSlice`]s point (but not the [`IoSlice`]s themselves), are unchanged and
-    /// can be reused.
+    /// # Errors
     ///
-    /// [`write_vectored`]: Write::write_vectored
+    /// Returns any error from [`rewind`] or [`Read::read_to_end`]. Bytes read
+    /// before an error are still appended to `buf`.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(not(feature = "alloc"))]
-    /// # fn main() -> acid_io::Result<()> { Ok(()) }
-    /// # #[cfg(feature = "alloc")]
+    /// use acid_io::{Cursor, ReadSeekExt, Write};
+    ///
     /// # fn main() -> acid_io::Result<()> {
+    /// let mut c = Cursor::new(Vec::new());
+    /// c.write_all(b"snapshot")?;
     ///
-    /// use acid_io::{Write, IoSlice};
+    /// let mut buf = Vec::new();
+    /// assert_eq!(c.read_all_from_start(&mut buf)?, 8);
+    /// assert_eq!(buf, b"snapshot");
+    /// assert_eq!(c.position(), 8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_all_from_start(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        self.rewind()?;
+        self.read_to_end(buf)
+    }
+}
+
+impl<R: Read + Seek + ?Sized> ReadSeekExt for R {}
+
+/// Extension methods for streams which are both [`Write`] and [`Seek`].
+///
+/// This trait is implemented for every such type.
+pub trait WriteSeekExt: Write + Seek {
+    /// Writes all of `buf` at the absolute offset `pos`, then returns to the
+    /// original position.
     ///
-    /// let mut writer = Vec::new();
-    /// let bufs = &mut [
-    ///     IoSlice::new(&[1]),
-    ///     IoSlice::new(&[2, 3]),
-    ///     IoSlice::new(&[4, 5, 6]),
-    /// ];
+    /// This is useful for filling in fields, such as lengths or offsets, whose
+    /// location is known in advance but whose value is only known later.
     ///
-    /// writer.write_all_vectored(bufs)?;
-    /// // Note: the contents of `bufs` is now undefined, see the Notes section.
+    /// # Errors
+    ///
+    /// Returns the first error from seeking to `pos` or writing `buf`. Even if
+    /// those fail, this tries to seek back to the original position; an error
+    /// from doing so is only returned if everything else succeeded.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(writer, &[1, 2, 3, 4, 5, 6]);
-    /// # Ok(()) }
     /// ```
-    // TODO(dataphract):
-    // LineWriter depends on this, but it's not stable, so we hide it from the
-    // public API. If/when it's stabilized, remove #[doc(hidden)].
-    #[doc(hidden)]
-    fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> Result<()> {
-        // Guarantee that bufs is empty if it contains no data,
-        // to avoid calling write_vectored if there is no data to be written.
-        IoSlice::advance_slices(&mut bufs, 0);
-        while !bufs.is_empty() {
-            match self.write_vectored(bufs) {
-                Ok(0) => {
-                    return Err(Error::new_const(
-                        ErrorKind::WriteZero,
-                        &"failed to write whole buffer",
-                    ));
-                }
-                Ok(n) => IoSlice::advance_slices(&mut bufs, n),
-                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
-                Err(e) => return Err(e),
+    /// use acid_io::{Cursor, Write, WriteSeekExt};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut buf = [0u8; 8];
+    /// let mut c = Cursor::new(&mut buf[..]);
+    /// c.write_all(b"\0\0body")?;
+    ///
+    /// // Fill in the length placeholder once the body is written.
+    /// c.write_all_at(0, &4u16.to_be_bytes())?;
+    /// assert_eq!(c.position(), 6);
+    /// assert_eq!(&buf, b"\0\x04body\0\0");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn write_all_at(&mut self, pos: u64, buf: &[u8]) -> Result<()> {
+        let old_pos = self.stream_position()?;
+
+        let result = self
+            .seek(SeekFrom::Start(pos))
+            .and_then(|_| self.write_all(buf));
+
+        let restored = self.seek(SeekFrom::Start(old_pos));
+        result?;
+        restored.map(|_| ())
+    }
+}
+
+impl<W: Write + Seek + ?Sized> WriteSeekExt for W {}
+
+/// A stream whose length is known without seeking.
+///
+/// [`fixed_len`] is the total length of the stream in bytes, the value
+/// [`Seek::stream_len`] would return. It stands in for `stream_len` on
+/// streams whose length never changes, such as in-memory buffers, so generic
+/// code can query it repeatedly without any seeking machinery. Reading and
+/// seeking do not change it. A `&[u8]` is a stream of its own bytes: reading
+/// from it replaces it with a shorter slice, which is a different stream. The
+/// default implementation returns [`None`], meaning the length is unknown.
+///
+/// [`fixed_len`]: FixedLen::fixed_len
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Cursor, FixedLen};
+///
+/// fn describe<S: FixedLen>(stream: &S) -> &'static str {
+///     match stream.fixed_len() {
+///         Some(0) => "empty",
+///         Some(_) => "sized",
+///         None => "unknown",
+///     }
+/// }
+///
+/// assert_eq!(describe(&Cursor::new(b"abc")), "sized");
+/// assert_eq!(describe(&&b""[..]), "empty");
+/// ```
+pub trait FixedLen {
+    /// Returns the total length of the stream in bytes, if it is known.
+    fn fixed_len(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The length of the underlying buffer, whatever the position.
+impl<T: AsRef<[u8]>> FixedLen for Cursor<T> {
+    #[inline]
+    fn fixed_len(&self) -> Option<u64> {
+        Some(self.get_ref().as_ref().len() as u64)
+    }
+}
+
+/// The length of the slice.
+impl FixedLen for &[u8] {
+    #[inline]
+    fn fixed_len(&self) -> Option<u64> {
+        Some(self.len() as u64)
+    }
+}
+
+/// An error returned by [`Seek::stream_len_checked`].
+///
+/// This combines the underlying I/O error with the length of the stream, if
+/// it was measured before the error occurred. A measured length means that
+/// only restoring the original seek position failed.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Error, ErrorKind, Seek, SeekFrom};
+///
+/// // A stream which cannot seek back to the start once it reaches the end.
+/// struct OneWay(u64);
+///
+/// impl Seek for OneWay {
+///     fn seek(&mut self, pos: SeekFrom) -> acid_io::Result<u64> {
+///         match pos {
+///             SeekFrom::End(0) => self.0 = 16,
+///             SeekFrom::Current(0) => (),
+///             _ => return Err(ErrorKind::Other.into()),
+///         }
+///         Ok(self.0)
+///     }
+/// }
+///
+/// let err = OneWay(4).stream_len_checked().unwrap_err();
+/// assert_eq!(err.measured_len(), Some(16));
+/// assert_eq!(err.error().kind(), ErrorKind::Other);
+/// ```
+#[derive(Debug)]
+pub struct StreamLenError {
+    error: Error,
+    len: Option<u64>,
+}
+
+impl StreamLenError {
+    fn new(error: Error, len: Option<u64>) -> StreamLenError {
+        StreamLenError { error, len }
+    }
+
+    /// Returns the error which caused [`Seek::stream_len_checked`] to fail.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the measured length of the stream, if the failure occurred
+    /// while restoring the original seek position.
+    ///
+    /// In either case, the seek position of the stream is unspecified.
+    pub fn measured_len(&self) -> Option<u64> {
+        self.len
+    }
+
+    /// Consumes the `StreamLenError`, returning the underlying error.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+
+    /// Consumes the `StreamLenError`, returning the underlying error and the
+    /// measured length of the stream, if any.
+    pub fn into_parts(self) -> (Error, Option<u64>) {
+        (self.error, self.len)
+    }
+}
+
+impl From<StreamLenError> for Error {
+    fn from(sle: StreamLenError) -> Error {
+        sle.error
+    }
+}
+
+impl fmt::Display for StreamLenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.len {
+            Some(len) => write!(
+                f,
+                "measured stream length {} but failed to restore position: {}",
+                len, self.error
+            ),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
+/// Seek adapter which remembers the stream position and skips seeks that
+/// would not move it.
+///
+/// Each seek whose target can be worked out from the cached position is
+/// compared against it, and the underlying [`seek`] is only called if the
+/// position would change. Seeks relative to the end of the stream are always
+/// passed on, since their target depends on the stream length. Reads and
+/// writes through the adapter keep the cached position up to date.
+///
+/// This is useful for backends where seeking is expensive even when the
+/// position does not change. The cached position is also reported by
+/// [`Seek::known_position`], so adapters such as [`BufReader`] put in front
+/// of a `CachedSeek` skip such seeks as well.
+///
+/// [`seek`]: Seek::seek
+/// [`BufReader`]: crate::BufReader
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{CachedSeek, Cursor, Read, Seek, SeekFrom};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut stream = CachedSeek::new(Cursor::new(b"Hello, world!"))?;
+///
+/// let mut buf = [0u8; 5];
+/// stream.read_exact(&mut buf)?;
+///
+/// // The stream is already at offset 5, so this does not seek.
+/// assert_eq!(stream.seek(SeekFrom::Start(5))?, 5);
+/// assert_eq!(stream.seek(SeekFrom::Current(2))?, 7);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CachedSeek<S> {
+    inner: S,
+    // `None` when the position of `inner` is not known.
+    pos: Option<u64>,
+}
+
+impl<S: Seek> CachedSeek<S> {
+    /// Creates a new `CachedSeek`, querying the current position of `inner`.
+    pub fn new(mut inner: S) -> Result<CachedSeek<S>> {
+        let pos = inner.stream_position()?;
+        Ok(CachedSeek {
+            inner,
+            pos: Some(pos),
+        })
+    }
+}
+
+impl<S> CachedSeek<S> {
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// The stream may be moved through the returned reference, so the cached
+    /// position is discarded and queried again on the next seek.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes the `CachedSeek`, returning the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.pos = self.pos.and_then(|pos| pos.checked_add(n as u64));
+    }
+}
+
+impl<S: Seek> Seek for CachedSeek<S> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        let target = match (&style, self.pos) {
+            (&SeekFrom::Start(n), _) => Some(n),
+            (&SeekFrom::Current(n), Some(pos)) => offset_position(pos, n),
+            _ => None,
+        };
+
+        if let (Some(target), Some(pos)) = (target, self.pos) {
+            if target == pos {
+                return Ok(pos);
             }
         }
-        Ok(())
+
+        match self.inner.seek(style) {
+            Ok(pos) => {
+                self.pos = Some(pos);
+                Ok(pos)
+            }
+            Err(e) => {
+                self.pos = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        match self.pos {
+            Some(pos) => Ok(pos),
+            None => {
+                let pos = self.inner.stream_position()?;
+                self.pos = Some(pos);
+                Ok(pos)
+            }
+        }
+    }
+
+    #[inline]
+    fn supports_end_seek(&self) -> bool {
+        self.inner.supports_end_seek()
+    }
+
+    #[inline]
+    fn known_position(&self) -> Option<u64> {
+        self.pos
+    }
+}
+
+impl<S: Read> Read for CachedSeek<S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.advance(n);
+        Ok(n)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let n = self.inner.read_vectored(bufs)?;
+        self.advance(n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S: BufRead> BufRead for CachedSeek<S> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.advance(amt);
+    }
+}
+
+impl<S: Write> Write for CachedSeek<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.advance(n);
+        Ok(n)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let n = self.inner.write_vectored(bufs)?;
+        self.advance(n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Write adapter which forbids seeking back over data already written.
+///
+/// The highest position written to, or the position of the stream when the
+/// adapter was created if that is higher, is the high-water mark. Seeks to
+/// any position at or after it are passed on, so gaps can still be left by
+/// seeking forward, but seeks before it fail with
+/// [`ErrorKind::Unsupported`] and leave the position unchanged. Data before
+/// the high-water mark therefore cannot be overwritten through the adapter.
+///
+/// A [`SeekFrom::End`] target is only known once the underlying writer has
+/// moved there, so a seek before the high-water mark is undone by seeking
+/// back. If that fails too, the position is lost and every write fails until
+/// a later seek lands at or after the high-water mark.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{AppendOnly, Cursor, Seek, SeekFrom, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut buf = [0u8; 8];
+/// let mut log = AppendOnly::new(Cursor::new(&mut buf[..]))?;
+///
+/// log.write_all(b"abc")?;
+/// assert!(log.seek(SeekFrom::Start(1)).is_err());
+///
+/// // Skipping ahead is allowed.
+/// assert_eq!(log.seek(SeekFrom::Current(2))?, 5);
+/// log.write_all(b"d")?;
+/// assert_eq!(log.high_water_mark(), 6);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AppendOnly<W> {
+    inner: HighWaterWriter<W>,
+    // The position of the stream when the adapter was created.
+    start: u64,
+    // Set when the underlying writer may be before the high-water mark.
+    lost: bool,
+}
+
+impl<W: Write + Seek> AppendOnly<W> {
+    /// Creates a new `AppendOnly`, querying the current position of `inner`,
+    /// which becomes the initial high-water mark.
+    pub fn new(inner: W) -> Result<AppendOnly<W>> {
+        let inner = HighWaterWriter::new(inner)?;
+        Ok(AppendOnly {
+            start: inner.pos,
+            inner,
+            lost: false,
+        })
+    }
+
+    /// Returns the position before which seeking is not allowed.
+    pub fn high_water_mark(&self) -> u64 {
+        cmp::max(self.start, self.inner.high_water())
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to or seeking the underlying writer directly bypasses the
+    /// checks of this adapter and may leave its position out of date.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Consumes the `AppendOnly`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
     }
+}
 
-    /// Writes a formatted string into this writer, returning any error
-    /// encountered.
-    ///
-    /// This method is primarily used to interface with the
-    /// [`format_args!()`] macro, and it is rare that this should
-    /// explicitly be called. The [`write!()`] macro should be favored to
-    /// invoke this method instead.
-    ///
-    /// This function internally uses the [`write_all`] method on
-    /// this trait and hence will continuously write data so long as no errors
-    /// are received. This also means that partial writes are not indicated in
-    /// this signature.
-    ///
-    /// [`write_all`]: Write::write_all
-    ///
-    /// # Errors
-    ///
-    /// This function will return any I/O error reported while formatting.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use acid_io::prelude::*;
-    ///
-    /// # fn main() -> acid_io::Result<()> {
-    /// let mut buffer = [0u8; 32];
-    ///
-    /// // this call
-    /// write!(buffer.as_mut_slice(), "{:.*}", 2, 1.234567)?;
-    /// // turns into this:
-    /// buffer.as_mut_slice().write_fmt(format_args!("{:.*}", 2, 1.234567))?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
-        // Create a shim which translates a Write to a fmt::Write and saves
-        // off I/O errors. instead of discarding them
-        struct Adapter<'a, T: ?Sized + 'a> {
-            inner: &'a mut T,
-            error: Result<()>,
-        }
+fn seek_before_high_water_mark() -> Error {
+    Error::new_const(
+        ErrorKind::Unsupported,
+        &"cannot seek before data already written",
+    )
+}
 
-        impl<T: Write + ?Sized> fmt::Write for Adapter<'_, T> {
-            fn write_str(&mut self, s: &str) -> fmt::Result {
-                match self.inner.write_all(s.as_bytes()) {
-                    Ok(()) => Ok(()),
-                    Err(e) => {
-                        self.error = Err(e);
-                        Err(fmt::Error)
+fn position_lost() -> Error {
+    Error::new_const(
+        ErrorKind::Other,
+        &"position may be before data already written after a failed seek",
+    )
+}
+
+impl<W: Write + Seek> Seek for AppendOnly<W> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        let high = self.high_water_mark();
+        let pos = self.inner.pos;
+        let target = match style {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => offset_position(pos, n),
+            // The target depends on the stream length, so seek and check.
+            SeekFrom::End(_) => {
+                let target = self.inner.seek(style)?;
+                if target < high {
+                    if let Err(e) = self.inner.seek(SeekFrom::Start(pos)) {
+                        self.lost = true;
+                        return Err(e);
                     }
+                    return Err(seek_before_high_water_mark());
                 }
+                self.lost = false;
+                return Ok(target);
             }
-        }
-
-        let mut output = Adapter {
-            inner: self,
-            error: Ok(()),
         };
-        match fmt::write(&mut output, fmt) {
-            Ok(()) => Ok(()),
-            Err(..) => {
-                // check if the error came from the underlying `Write` or not
-                if output.error.is_err() {
-                    output.error
-                } else {
-                    Err(Error::new_const(
-                        ErrorKind::Uncategorized,
-                        &"formatter error",
-                    ))
-                }
+
+        match target {
+            Some(target) if target < high => Err(seek_before_high_water_mark()),
+            _ => {
+                let target = self.inner.seek(style)?;
+                self.lost = target < high;
+                Ok(target)
             }
         }
     }
 
-    /// Creates a "by reference" adapter for this instance of `Write`.
-    ///
-    /// The returned adapter also implements `Write` and will simply borrow this
-    /// current writer.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use acid_io::Write;
-    ///
-    /// # fn main() -> acid_io::Result<()> {
-    /// let mut buffer = [0u8; 16];
-    ///
-    /// let mut w = buffer.as_mut_slice();
-    /// let reference = w.by_ref();
-    ///
-    /// // we can use reference just like our original buffer
-    /// reference.write_all(b"some bytes")?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    fn by_ref(&mut self) -> &mut Self
-    where
-        Self: Sized,
-    {
-        self
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+
+    #[inline]
+    fn supports_end_seek(&self) -> bool {
+        self.inner.supports_end_seek()
+    }
+
+    #[inline]
+    fn known_position(&self) -> Option<u64> {
+        self.inner.known_position()
     }
 }
 
-impl Write for &mut [u8] {
+impl<W: Write + Seek> Write for AppendOnly<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.lost {
+            return Err(position_lost());
+        }
+        self.inner.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        if self.lost {
+            return Err(position_lost());
+        }
+        self.inner.write_vectored(bufs)
+    }
+
     #[inline]
-    fn write(&mut self, src: &[u8]) -> Result<usize> {
-        let copy_len = cmp::min(src.len(), self.len());
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
 
-        // Move slice out of self before splitting to appease borrowck.
-        let (dst, rem) = mem::take(self).split_at_mut(copy_len);
-        dst.copy_from_slice(&src[..copy_len]);
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
 
-        *self = rem;
+/// Write adapter which tracks the highest position written to.
+///
+/// Each write moves the high-water mark to the position after the last byte
+/// written, if that is further than any written before. Seeks are passed on
+/// and do not move it, so after seeking back to patch earlier data,
+/// [`high_water`] still tells how long the output needs to be, for example
+/// to truncate or allocate a file to exactly the written size.
+///
+/// [`high_water`]: HighWaterWriter::high_water
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Cursor, HighWaterWriter, Seek, SeekFrom, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut buf = [0u8; 16];
+/// let mut writer = HighWaterWriter::new(Cursor::new(&mut buf[..]))?;
+///
+/// writer.seek(SeekFrom::Start(4))?;
+/// writer.write_all(b"body")?;
+/// writer.rewind()?;
+/// writer.write_all(&[4])?;
+///
+/// assert_eq!(writer.stream_position()?, 1);
+/// assert_eq!(writer.high_water(), 8);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HighWaterWriter<W> {
+    inner: W,
+    pos: u64,
+    high: u64,
+}
 
-        Ok(copy_len)
+impl<W: Write + Seek> HighWaterWriter<W> {
+    /// Creates a new `HighWaterWriter`, querying the current position of
+    /// `inner`.
+    ///
+    /// The high-water mark starts at 0, whatever the position.
+    pub fn new(mut inner: W) -> Result<HighWaterWriter<W>> {
+        let pos = inner.stream_position()?;
+        Ok(HighWaterWriter {
+            inner,
+            pos,
+            high: 0,
+        })
+    }
+
+    /// Returns the position after the furthest byte written, or 0 if nothing
+    /// has been written.
+    pub fn high_water(&self) -> u64 {
+        self.high
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to or seeking the underlying writer directly is not tracked
+    /// and may leave the position of this adapter out of date.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the `HighWaterWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn advance(&mut self, n: usize) {
+        if n > 0 {
+            self.pos = self.pos.saturating_add(n as u64);
+            self.high = cmp::max(self.high, self.pos);
+        }
+    }
+}
+
+impl<W: Write + Seek> Seek for HighWaterWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
     }
 
     #[inline]
+    fn supports_end_seek(&self) -> bool {
+        self.inner.supports_end_seek()
+    }
+
+    #[inline]
+    fn known_position(&self) -> Option<u64> {
+        Some(self.pos)
+    }
+}
+
+impl<W: Write + Seek> Write for HighWaterWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.advance(n);
+        Ok(n)
+    }
+
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
-        let mut nwritten = 0;
-        for buf in bufs {
-            nwritten += self.write(buf)?;
-            if self.is_empty() {
-                break;
-            }
-        }
+        let n = self.inner.write_vectored(bufs)?;
+        self.advance(n);
+        Ok(n)
+    }
 
-        Ok(nwritten)
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Observed ======================================================================================
+
+/// Callbacks invoked by an [`Observed`] stream after each successful
+/// operation.
+///
+/// All methods have empty default implementations, so implementors only need
+/// to provide the callbacks they are interested in. The callbacks take `&self`
+/// so that a single observer can be shared between several streams; use
+/// [`Cell`] or atomics to record state.
+///
+/// [`Cell`]: core::cell::Cell
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::IoObserver;
+/// use core::cell::Cell;
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     read: Cell<usize>,
+/// }
+///
+/// impl IoObserver for Counter {
+///     fn on_read(&self, n: usize) {
+///         self.read.set(self.read.get() + n);
+///     }
+/// }
+/// ```
+pub trait IoObserver {
+    /// Called after a read (or [`consume`]) of `n` bytes.
+    ///
+    /// [`consume`]: BufRead::consume
+    fn on_read(&self, n: usize) {
+        let _ = n;
+    }
+
+    /// Called after a write of `n` bytes.
+    fn on_write(&self, n: usize) {
+        let _ = n;
+    }
+
+    /// Called after a flush.
+    fn on_flush(&self) {}
+}
+
+impl<O: IoObserver + ?Sized> IoObserver for &O {
+    fn on_read(&self, n: usize) {
+        (**self).on_read(n)
+    }
+
+    fn on_write(&self, n: usize) {
+        (**self).on_write(n)
+    }
+
+    fn on_flush(&self) {
+        (**self).on_flush()
+    }
+}
+
+/// Stream adapter which reports each successful operation to an
+/// [`IoObserver`].
+///
+/// `Observed` forwards [`Read`], [`BufRead`], [`Write`] and [`Seek`] to the
+/// wrapped stream. Failed operations are not reported. Since the observer
+/// decides what to record, this can be used for counting, timing or tracing
+/// without `acid_io` depending on a clock.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{IoObserver, Observed, Read};
+/// use core::cell::Cell;
+///
+/// # fn main() -> acid_io::Result<()> {
+/// #[derive(Default)]
+/// struct Counter {
+///     read: Cell<usize>,
+/// }
+///
+/// impl IoObserver for Counter {
+///     fn on_read(&self, n: usize) {
+///         self.read.set(self.read.get() + n);
+///     }
+/// }
+///
+/// let counter = Counter::default();
+/// let mut reader = Observed::new(&b"Hello, world!"[..], &counter);
+///
+/// let mut buf = [0u8; 5];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(counter.read.get(), 5);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Observed<T, O> {
+    inner: T,
+    observer: O,
+}
+
+impl<T, O: IoObserver> Observed<T, O> {
+    /// Creates a new `Observed` which reports the operations on `inner` to
+    /// `observer`.
+    pub fn new(inner: T, observer: O) -> Observed<T, O> {
+        Observed { inner, observer }
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Operations performed directly on the underlying stream are not
+    /// reported to the observer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Consumes the `Observed`, returning the underlying stream and the
+    /// observer.
+    pub fn into_parts(self) -> (T, O) {
+        (self.inner, self.observer)
+    }
+}
+
+impl<T: Read, O: IoObserver> Read for Observed<T, O> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.observer.on_read(n);
+        Ok(n)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let n = self.inner.read_vectored(bufs)?;
+        self.observer.on_read(n);
+        Ok(n)
     }
 
     #[inline]
-    fn is_write_vectored(&self) -> bool {
-        true
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
     }
 
     #[inline]
-    fn write_all(&mut self, data: &[u8]) -> Result<()> {
-        if self.write(data)? == data.len() {
-            Ok(())
-        } else {
-            Err(Error::new_const(
-                ErrorKind::WriteZero,
-                &"failed to write whole buffer",
-            ))
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
+}
 
+impl<T: ReadReady, O: IoObserver> ReadReady for Observed<T, O> {
     #[inline]
-    fn flush(&mut self) -> Result<()> {
-        Ok(())
+    fn read_ready(&mut self) -> Result<bool> {
+        self.inner.read_ready()
     }
 }
 
-// Seek ==========================================================================================
+impl<T: BufRead, O: IoObserver> BufRead for Observed<T, O> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf()
+    }
 
-/// Enumeration of possible methods to seek within an I/O object.
-///
-/// It is used by the [`Seek`] trait.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum SeekFrom {
-    /// Sets the offset to the provided number of bytes.
-    Start(u64),
-    /// Sets the offset to the size of this object plus the specified number of
-    /// bytes.
-    ///
-    /// It is possible to seek beyond the end of an object, but it's an error to
-    /// seek before byte 0.
-    End(i64),
-    /// Sets the offset to the current position plus the specified number of
-    /// bytes.
-    ///
-    /// It is possible to seek beyond the end of an object, but it's an error to
-    /// seek before byte 0.
-    Current(i64),
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.observer.on_read(amt);
+    }
 }
 
-/// The `Seek` trait provides a cursor which can be moved within a stream of
-/// bytes.
-///
-/// The stream typically has a fixed size, allowing seeking relative to either
-/// end or the current offset.
-pub trait Seek {
-    /// Seek to an offset, in bytes, in a stream.
-    ///
-    /// A seek beyond the end of a stream is allowed, but behavior is defined
-    /// by the implementation.
-    ///
-    /// If the seek operation completed successfully,
-    /// this method returns the new position from the start of the stream.
-    /// That position can be used later with [`SeekFrom::Start`].
-    ///
-    /// # Errors
-    ///
-    /// Seeking can fail, for example because it might involve flushing a buffer.
-    ///
-    /// Seeking to a negative offset is considered an error.
-    fn seek(&mut self, pos: SeekFrom) -> Result<u64>;
+impl<T: Write, O: IoObserver> Write for Observed<T, O> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.observer.on_write(n);
+        Ok(n)
+    }
 
-    /// Rewind to the beginning of a stream.
-    ///
-    /// This is a convenience method, equivalent to `seek(SeekFrom::Start(0))`.
-    ///
-    /// # Errors
-    ///
-    /// Rewinding can fail, for example because it might involve flushing a buffer.
-    fn rewind(&mut self) -> Result<()> {
-        self.seek(SeekFrom::Start(0))?;
-        Ok(())
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let n = self.inner.write_vectored(bufs)?;
+        self.observer.on_write(n);
+        Ok(n)
     }
 
-    /// Returns the length of this stream (in bytes).
-    ///
-    /// This method is implemented using up to three seek operations. If this
-    /// method returns successfully, the seek position is unchanged (i.e. the
-    /// position before calling this method is the same as afterwards).
-    /// However, if this method returns an error, the seek position is
-    /// unspecified.
-    ///
-    /// If you need to obtain the length of *many* streams and you don't care
-    /// about the seek position afterwards, you can reduce the number of seek
-    /// operations by simply calling `seek(SeekFrom::End(0))` and using its
-    /// return value (it is also the stream length).
-    ///
-    /// Note that length of a stream can change over time (for example, when
-    /// data is appended to a file). So calling this method multiple times does
-    /// not necessarily return the same length each time.
-    fn stream_len(&mut self) -> Result<u64> {
-        let old_pos = self.stream_position()?;
-        let len = self.seek(SeekFrom::End(0))?;
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
 
-        // Avoid seeking a third time when we were already at the end of the
-        // stream. The branch is usually way cheaper than a seek operation.
-        if old_pos != len {
-            self.seek(SeekFrom::Start(old_pos))?;
-        }
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()?;
+        self.observer.on_flush();
+        Ok(())
+    }
+}
 
-        Ok(len)
+impl<T: Seek, O: IoObserver> Seek for Observed<T, O> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
     }
 
-    /// Returns the current seek position from the start of the stream.
-    ///
-    /// This is equivalent to `self.seek(SeekFrom::Current(0))`.
     fn stream_position(&mut self) -> Result<u64> {
-        self.seek(SeekFrom::Current(0))
+        self.inner.stream_position()
+    }
+
+    #[inline]
+    fn supports_end_seek(&self) -> bool {
+        self.inner.supports_end_seek()
+    }
+
+    #[inline]
+    fn known_position(&self) -> Option<u64> {
+        self.inner.known_position()
     }
 }
 
@@ -1781,16 +5573,14 @@ pub trait Seek {
 ///     Ok(())
 /// }
 ///
-/// // now let's write a test
-/// #[test]
-/// fn test_writes_bytes() {
-///     use acid_io::Cursor;
-///     let mut buf = Cursor::new(vec![0; 15]);
+/// // now let's try it out
+/// use acid_io::Cursor;
+/// let mut arr = [0u8; 15];
+/// let mut buf = Cursor::new(&mut arr[..]);
 ///
-///     write_ten_bytes_at_end(&mut buf).unwrap();
+/// write_ten_bytes_at_end(&mut buf).unwrap();
 ///
-///     assert_eq!(&buf.get_ref()[5..15], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
-/// }
+/// assert_eq!(&buf.get_ref()[5..15], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 /// ```
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct Cursor<T> {
@@ -1944,6 +5734,33 @@ where
         &self.inner.as_ref()[(len as usize)..]
     }
 
+    /// Returns the slice before the current position.
+    ///
+    /// Together with [`remaining_slice`] this splits the underlying buffer at
+    /// the position, which is clamped to the length of the buffer.
+    ///
+    /// [`remaining_slice`]: Cursor::remaining_slice
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Cursor;
+    ///
+    /// let mut buf = Cursor::new(vec![1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(buf.consumed_slice(), &[]);
+    ///
+    /// buf.set_position(2);
+    /// assert_eq!(buf.consumed_slice(), &[1, 2]);
+    ///
+    /// buf.set_position(6);
+    /// assert_eq!(buf.consumed_slice(), &[1, 2, 3, 4, 5]);
+    /// ```
+    pub fn consumed_slice(&self) -> &[u8] {
+        let len = self.pos.min(self.inner.as_ref().len() as u64);
+        &self.inner.as_ref()[..(len as usize)]
+    }
+
     /// Returns `true` if the remaining slice is empty.
     ///
     /// # Examples
@@ -1965,6 +5782,159 @@ where
     pub fn is_empty(&self) -> bool {
         self.pos >= self.inner.as_ref().len() as u64
     }
+
+    /// Returns an iterator over the remaining slice in non-overlapping chunks
+    /// of `size` bytes, like [`slice::chunks`].
+    ///
+    /// The last chunk is shorter if the remaining length is not a multiple of
+    /// `size`. The position of the cursor is not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Cursor;
+    ///
+    /// let mut buf = Cursor::new(b"hdr:abcdefg");
+    /// buf.set_position(4);
+    ///
+    /// let mut chunks = buf.chunks(3);
+    /// assert_eq!(chunks.next(), Some(&b"abc"[..]));
+    /// assert_eq!(chunks.next(), Some(&b"def"[..]));
+    /// assert_eq!(chunks.next(), Some(&b"g"[..]));
+    /// assert_eq!(chunks.next(), None);
+    ///
+    /// assert_eq!(buf.position(), 4);
+    /// ```
+    pub fn chunks(&self, size: usize) -> slice::Chunks<'_, u8> {
+        self.remaining_slice().chunks(size)
+    }
+
+    /// Consumes this cursor, returning the whole underlying buffer as an
+    /// array if it holds exactly `N` bytes.
+    ///
+    /// The position is ignored. If the length of the buffer is not `N`, the
+    /// cursor is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{Cursor, Read};
+    ///
+    /// let mut buf = Cursor::new(&b"abc"[..]);
+    /// buf.read_exact(&mut [0u8; 2]).unwrap();
+    ///
+    /// let buf = buf.try_into_array::<4>().unwrap_err();
+    /// assert_eq!(buf.try_into_array::<3>().unwrap(), *b"abc");
+    /// ```
+    pub fn try_into_array<const N: usize>(self) -> core::result::Result<[u8; N], Cursor<T>> {
+        match self.inner.as_ref().try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Moves the position forward by `n` bytes.
+    ///
+    /// This marks `n` bytes after the current position as consumed or
+    /// written without touching them, for example after filling
+    /// [`remaining_slice_mut`] through some other means.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if the new
+    /// position would lie past the end of the underlying buffer. The
+    /// position is left unchanged in that case.
+    ///
+    /// [`remaining_slice_mut`]: Cursor::remaining_slice_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Cursor;
+    ///
+    /// let mut buf = Cursor::new([0u8; 4]);
+    ///
+    /// buf.advance(3).unwrap();
+    /// assert_eq!(buf.position(), 3);
+    ///
+    /// assert!(buf.advance(2).is_err());
+    /// assert_eq!(buf.position(), 3);
+    /// ```
+    pub fn advance(&mut self, n: usize) -> Result<()> {
+        let len = self.inner.as_ref().len() as u64;
+        match self.pos.checked_add(n as u64) {
+            Some(pos) if pos <= len => {
+                self.pos = pos;
+                Ok(())
+            }
+            _ => Err(Error::new_const(
+                ErrorKind::InvalidInput,
+                &"advancing past the end of the buffer",
+            )),
+        }
+    }
+}
+
+/// Borrows the whole underlying buffer, regardless of the position.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Cursor, Write};
+///
+/// fn checksum(data: impl AsRef<[u8]>) -> u32 {
+///     data.as_ref().iter().map(|&b| b as u32).sum()
+/// }
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut buf = Cursor::new([0u8; 4]);
+/// buf.write_all(&[1, 2, 3])?;
+///
+/// assert_eq!(buf.as_ref(), &[1, 2, 3, 0]);
+/// assert_eq!(checksum(&buf), 6);
+/// # Ok(())
+/// # }
+/// ```
+impl<T: AsRef<[u8]>> AsRef<[u8]> for Cursor<T> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.inner.as_ref()
+    }
+}
+
+impl<T> Cursor<T>
+where
+    T: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Returns the remaining slice mutably.
+    ///
+    /// Together with [`advance`], this allows filling the buffer in place and
+    /// then committing the filled bytes.
+    ///
+    /// [`advance`]: Cursor::advance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::Cursor;
+    ///
+    /// let mut buf = Cursor::new([0u8; 4]);
+    /// buf.set_position(1);
+    ///
+    /// buf.remaining_slice_mut()[..2].copy_from_slice(b"ab");
+    /// buf.advance(2).unwrap();
+    ///
+    /// assert_eq!(buf.get_ref(), b"\0ab\0");
+    /// assert_eq!(buf.position(), 3);
+    /// ```
+    pub fn remaining_slice_mut(&mut self) -> &mut [u8] {
+        let len = self.pos.min(self.inner.as_ref().len() as u64);
+        &mut self.inner.as_mut()[(len as usize)..]
+    }
 }
 
 impl<T> Read for Cursor<T>
@@ -1999,6 +5969,12 @@ where
         self.pos += n as u64;
         Ok(())
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining_slice().len();
+        (len, Some(len))
+    }
 }
 
 // Non-resizing write implementation
@@ -2049,6 +6025,35 @@ impl Write for Cursor<&mut [u8]> {
     }
 }
 
+impl<const N: usize> Write for Cursor<[u8; N]> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        slice_write_vectored(&mut self.pos, &mut self.inner, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Applies a signed seek offset to `base`, returning `None` if the result is
+/// negative or overflows.
+#[inline]
+pub(crate) fn offset_position(base: u64, offset: i64) -> Option<u64> {
+    base.checked_add_signed(offset)
+}
+
 impl<A> Seek for Cursor<A>
 where
     A: AsRef<[u8]>,
@@ -2063,23 +6068,7 @@ where
             SeekFrom::Current(n) => (self.pos, n),
         };
 
-        // TODO: The standard library does this whole op with a call to
-        // `checked_add_signed`, which is unstable behind `mixed_integer_ops`
-        // (https://github.com/rust-lang/rust/issues/87840). Once (if) that
-        // stabilizes, follow suit here.
-        let end_pos = {
-            let (p, overflowed) = base_pos.overflowing_add(offset as u64);
-
-            // If offset < 0 and the unsigned addition didn't overflow, then
-            // the signed addition would have a negative sum.
-            if overflowed ^ (offset < 0) {
-                None
-            } else {
-                Some(p)
-            }
-        };
-
-        match end_pos {
+        match offset_position(base_pos, offset) {
             Some(n) => {
                 self.pos = n;
                 Ok(self.pos)
@@ -2095,7 +6084,16 @@ where
         Ok(self.inner.as_ref().len() as u64)
     }
 
+    fn stream_len_checked(&mut self) -> core::result::Result<u64, StreamLenError> {
+        Ok(self.inner.as_ref().len() as u64)
+    }
+
     fn stream_position(&mut self) -> Result<u64> {
         Ok(self.pos)
     }
+
+    #[inline]
+    fn known_position(&self) -> Option<u64> {
+        Some(self.pos)
+    }
 }
@@ -37,6 +37,11 @@ impl<'a> RawIoSliceMut<'a> {
         }
     }
 
+    #[inline]
+    const fn len(&self) -> usize {
+        self.0.iov_len
+    }
+
     #[inline]
     fn as_slice(&self) -> &'a [u8] {
         // SAFETY: this type was initialized using a &mut [u8], so all
@@ -83,7 +88,12 @@ impl<'a> RawIoSlice<'a> {
     }
 
     #[inline]
-    fn as_slice(&self) -> &'a [u8] {
+    const fn len(&self) -> usize {
+        self.0.iov_len
+    }
+
+    #[inline]
+    const fn as_slice(&self) -> &'a [u8] {
         // SAFETY: this type was initialized using a &[u8], so all
         // invariants of reconstructing that slice are upheld.
         unsafe { slice::from_raw_parts(self.0.iov_base as *const u8, self.0.iov_len) }
@@ -111,6 +121,70 @@ impl<'a> IoSliceMut<'a> {
         IoSliceMut(RawIoSliceMut::new(buf))
     }
 
+    /// Returns the number of bytes in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSliceMut;
+    ///
+    /// let mut data = [0; 8];
+    /// let buf = IoSliceMut::new(&mut data);
+    /// assert_eq!(buf.len(), 8);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the buffer has a length of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSliceMut;
+    ///
+    /// let buf = IoSliceMut::new(&mut []);
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the underlying bytes as a shared slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSliceMut;
+    ///
+    /// let mut data = [1, 2, 3];
+    /// let buf = IoSliceMut::new(&mut data);
+    /// assert_eq!(buf.as_slice(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Returns the underlying bytes as a mutable slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSliceMut;
+    ///
+    /// let mut data = [1, 2, 3];
+    /// let mut buf = IoSliceMut::new(&mut data);
+    /// buf.as_mut_slice()[0] = 7;
+    /// assert_eq!(buf.as_slice(), &[7, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+
     /// Advance the internal cursor of the slice.
     ///
     /// Also see [`IoSliceMut::advance_slices`] to advance the cursors of
@@ -186,6 +260,70 @@ impl<'a> IoSliceMut<'a> {
             bufs[0].advance(n - accumulated_len)
         }
     }
+
+    /// Wraps each buffer of an array in an `IoSliceMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{IoSliceMut, Read};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let mut a = [0u8; 2];
+    /// let mut b = [0u8; 3];
+    /// let mut bufs = IoSliceMut::from_arrays([&mut a, &mut b]);
+    /// assert_eq!(IoSliceMut::total_len(&bufs), 5);
+    ///
+    /// (&b"hello"[..]).read_vectored(&mut bufs)?;
+    /// assert_eq!((a, b), (*b"he", *b"llo"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_arrays<const K: usize>(bufs: [&'a mut [u8]; K]) -> [IoSliceMut<'a>; K] {
+        bufs.map(IoSliceMut::new)
+    }
+
+    /// Returns the total number of bytes in `bufs`.
+    #[inline]
+    pub fn total_len(bufs: &[IoSliceMut<'_>]) -> usize {
+        bufs.iter().map(|buf| buf.len()).sum()
+    }
+
+    /// Returns an iterator over the bytes of `bufs`, as if they were
+    /// concatenated.
+    ///
+    /// This allows inspecting the contents of a scatter list without copying
+    /// them into a single buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSliceMut;
+    ///
+    /// let mut a = *b"he";
+    /// let mut b = *b"llo";
+    /// let bufs = IoSliceMut::from_arrays([&mut a, &mut b]);
+    ///
+    /// assert!(IoSliceMut::iter_bytes(&bufs).eq(b"hello".iter().copied()));
+    /// ```
+    pub fn iter_bytes<'b>(bufs: &'b [IoSliceMut<'a>]) -> impl Iterator<Item = u8> + 'b {
+        bufs.iter().flat_map(|buf| buf.iter().copied())
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for IoSliceMut<'a> {
+    #[inline]
+    fn from(buf: &'a mut [u8]) -> IoSliceMut<'a> {
+        IoSliceMut::new(buf)
+    }
+}
+
+impl<'a, const N: usize> From<&'a mut [u8; N]> for IoSliceMut<'a> {
+    #[inline]
+    fn from(buf: &'a mut [u8; N]) -> IoSliceMut<'a> {
+        IoSliceMut::new(buf)
+    }
 }
 
 impl<'a> Deref for IoSliceMut<'a> {
@@ -227,6 +365,55 @@ impl<'a> IoSlice<'a> {
         IoSlice(RawIoSlice::new(buf))
     }
 
+    /// Returns the number of bytes in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSlice;
+    ///
+    /// let buf = IoSlice::new(&[1, 2, 3]);
+    /// assert_eq!(buf.len(), 3);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the buffer has a length of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSlice;
+    ///
+    /// let buf = IoSlice::new(&[]);
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the underlying bytes.
+    ///
+    /// Unlike the [`Deref`] implementation, the returned slice has the
+    /// lifetime of the original buffer rather than that of the `IoSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSlice;
+    ///
+    /// let data = [1, 2, 3];
+    /// let buf = IoSlice::new(&data);
+    /// assert_eq!(buf.as_slice(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub const fn as_slice(&self) -> &'a [u8] {
+        self.0.as_slice()
+    }
+
     /// Advance the internal cursor of the slice.
     ///
     /// Also see [`IoSlice::advance_slices`] to advance the cursors of multiple
@@ -301,6 +488,70 @@ impl<'a> IoSlice<'a> {
             bufs[0].advance(n - accumulated_len)
         }
     }
+
+    /// Wraps each buffer of an array in an `IoSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{IoSlice, Write};
+    ///
+    /// # fn main() -> acid_io::Result<()> {
+    /// let bufs = IoSlice::from_arrays([b"he", b"llo"]);
+    /// assert_eq!(IoSlice::total_len(&bufs), 5);
+    ///
+    /// let mut out = [0u8; 5];
+    /// (&mut out[..]).write_vectored(&bufs)?;
+    /// assert_eq!(&out, b"hello");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_arrays<const K: usize>(bufs: [&'a [u8]; K]) -> [IoSlice<'a>; K] {
+        bufs.map(IoSlice::new)
+    }
+
+    /// Returns the total number of bytes in `bufs`.
+    #[inline]
+    pub fn total_len(bufs: &[IoSlice<'_>]) -> usize {
+        bufs.iter().map(|buf| buf.len()).sum()
+    }
+
+    /// Returns an iterator over the bytes of `bufs`, as if they were
+    /// concatenated.
+    ///
+    /// This allows inspecting the contents of a gather list, such as the part
+    /// left over after a partial vectored write, without copying them into a
+    /// single buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::IoSlice;
+    ///
+    /// let mut bufs = IoSlice::from_arrays([b"he", b"llo"]);
+    /// let mut bufs = &mut bufs[..];
+    /// IoSlice::advance_slices(&mut bufs, 1);
+    ///
+    /// assert!(IoSlice::iter_bytes(bufs).eq(b"ello".iter().copied()));
+    /// ```
+    pub fn iter_bytes<'b>(bufs: &'b [IoSlice<'a>]) -> impl Iterator<Item = u8> + 'b {
+        bufs.iter().flat_map(|buf| buf.iter().copied())
+    }
+}
+
+impl<'a> From<&'a [u8]> for IoSlice<'a> {
+    #[inline]
+    fn from(buf: &'a [u8]) -> IoSlice<'a> {
+        IoSlice::new(buf)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for IoSlice<'a> {
+    #[inline]
+    fn from(buf: &'a [u8; N]) -> IoSlice<'a> {
+        IoSlice::new(buf)
+    }
 }
 
 impl<'a> Deref for IoSlice<'a> {
@@ -4,12 +4,37 @@
 
 //! A pared-down version of [`std::io`] usable in `no_std` contexts.
 //!
+//! # The `std` feature
+//!
+//! With the `std` feature enabled, this crate only re-exports the items of
+//! [`std::io`]. Everything it provides beyond `std::io`, such as extra
+//! provided methods on `Read`, `Write`, `Seek` and `BufRead` and the
+//! adapters built on them, then disappears. The feature is therefore not
+//! additive: code which uses these items stops compiling as soon as any crate
+//! in the dependency graph enables `std`.
+//!
 //! [`std::io`]: https://doc.rust-lang.org/std/io/index.html
 
+#[cfg(all(not(feature = "std"), feature = "base64"))]
+pub(crate) mod base64;
+#[cfg(not(feature = "std"))]
+pub(crate) mod bits;
 #[cfg(all(not(feature = "std"), feature = "byteorder"))]
 pub mod byteorder;
 #[cfg(not(feature = "std"))]
+pub(crate) mod checksum;
+#[cfg(not(feature = "std"))]
+pub(crate) mod coalesce;
+#[cfg(not(feature = "std"))]
+pub(crate) mod codec;
+#[cfg(not(feature = "std"))]
+pub(crate) mod dynamic;
+#[cfg(not(feature = "std"))]
 pub(crate) mod error;
+#[cfg(not(feature = "std"))]
+pub(crate) mod fault;
+#[cfg(not(feature = "std"))]
+pub(crate) mod frame;
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 pub(crate) mod io_alloc;
 #[cfg(not(feature = "std"))]
@@ -19,6 +44,8 @@ mod io_slice;
 #[cfg(not(feature = "std"))]
 pub mod prelude;
 #[cfg(not(feature = "std"))]
+pub(crate) mod slices;
+#[cfg(not(feature = "std"))]
 pub(crate) mod util;
 
 #[cfg(all(not(feature = "std"), test))]
@@ -27,15 +54,50 @@ mod tests;
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 extern crate alloc;
 
+#[cfg(all(not(feature = "std"), feature = "base64"))]
+pub use base64::{Base64Reader, Base64Writer};
+#[cfg(not(feature = "std"))]
+pub use bits::{BitCursor, BitOrder};
+#[cfg(all(not(feature = "std"), feature = "checksum"))]
+pub use checksum::{Adler32, Crc32};
 #[cfg(not(feature = "std"))]
-pub use error::{Error, ErrorKind, ErrorTrait, Result};
+pub use checksum::{
+    Checksum, ChecksumAppendWriter, ChecksumLayout, ChecksumReader, ChecksumVerifyReader,
+    ChecksumWriter,
+};
+#[cfg(not(feature = "std"))]
+pub use coalesce::CoalesceWriter;
+#[cfg(not(feature = "std"))]
+pub use codec::{
+    EscapeWriter, HexReader, HexWriter, LineEnding, NewlineReader, NewlineWriter,
+    RunLengthEncoding, RunLengthReader, RunLengthWriter, SlipReader, SlipWriter, Transform,
+    TransformReader, TransformWriter, UnescapeReader, Utf8Writer, XorReader, XorWriter,
+};
+#[cfg(not(feature = "std"))]
+pub use dynamic::DynReader;
+#[cfg(not(feature = "std"))]
+pub use error::{Error, ErrorKind, ErrorTrait, IoResultExt, Result};
+#[cfg(not(feature = "std"))]
+pub use fault::{FaultyReader, FaultyWriter, WriteFault};
+#[cfg(not(feature = "std"))]
+pub use frame::{FramedReader, FramedWriter, TrimReader};
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
-pub use io_alloc::{BufReader, BufWriter, IntoInnerError, LineWriter, Lines, Split};
+pub use io_alloc::{
+    chain_all, BufReader, BufWriter, ChainMany, Doubling, GrowthStrategy, IntoInnerError,
+    LineWriter, Linear, Lines, LinesWithOffsets, Records, Split, Utf8Chunks,
+};
 #[cfg(not(feature = "std"))]
-pub use io_core::{BufRead, Cursor, Read, Seek, SeekFrom, Write, Take, Chain, Bytes};
+pub use io_core::{
+    AppendOnly, BufRead, Bytes, CachedSeek, Chain, ChunkedReader, ChunkedWriter, Cursor,
+    DeadlineReader, ExpectLen, ExpectWriteLen, FixedLen, HighWaterWriter, Inspect, IoObserver,
+    MergeReader, Observed, Prepend, Read, ReadFull, ReadReady, ReadSeekExt, ResumableReader, Seek,
+    SeekFrom, SplitWriter, StreamLenError, Take, Write, WriteSeekExt,
+};
 #[cfg(not(feature = "std"))]
 pub use io_slice::{IoSlice, IoSliceMut};
 #[cfg(not(feature = "std"))]
+pub use slices::{DualSliceReader, DualSliceWriter, SlicesReader};
+#[cfg(not(feature = "std"))]
 pub use util::{copy, empty, repeat, sink, Empty, Repeat, Sink};
 
 #[cfg(all(feature = "std", feature = "byteorder"))]
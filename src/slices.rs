@@ -0,0 +1,412 @@
+//! Readers and writers over byte data split across several in-memory slices.
+
+#![cfg(not(feature = "std"))]
+
+use core::cmp;
+
+use crate::{io_core, BufRead, Error, ErrorKind, Read, ReadReady, Result, Seek, SeekFrom, Write};
+
+/// A reader over a list of byte slices, read one after another as a single
+/// stream.
+///
+/// No data is copied until it is read, and [`fill_buf`] hands out each
+/// fragment directly. This is more efficient than chaining many `&[u8]`
+/// readers together, and the resulting type does not depend on the number of
+/// fragments.
+///
+/// The fragments form a single address space for [`Seek`]. By default,
+/// seeking past the end positions the reader at the end; see
+/// [`set_clamp_seeks`] to report an error instead.
+///
+/// [`fill_buf`]: BufRead::fill_buf
+/// [`set_clamp_seeks`]: SlicesReader::set_clamp_seeks
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{Read, SlicesReader};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let fragments: [&[u8]; 3] = [b"Hel", b"lo, wor", b"ld!"];
+/// let mut reader = SlicesReader::new(&fragments);
+///
+/// let mut buf = [0u8; 13];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"Hello, world!");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SlicesReader<'a> {
+    slices: &'a [&'a [u8]],
+    // Index of the fragment being read and the offset within it.
+    index: usize,
+    offset: usize,
+    clamp_seeks: bool,
+}
+
+impl<'a> SlicesReader<'a> {
+    /// Creates a new reader over `slices`, positioned at the start of the
+    /// first one.
+    pub const fn new(slices: &'a [&'a [u8]]) -> SlicesReader<'a> {
+        SlicesReader {
+            slices,
+            index: 0,
+            offset: 0,
+            clamp_seeks: true,
+        }
+    }
+
+    /// Sets whether seeking past the end of the data clamps to the end.
+    ///
+    /// If `clamp` is `true`, which is the default, a seek past the end moves
+    /// the reader to the end and returns the total length. If it is `false`,
+    /// such a seek fails with [`ErrorKind::InvalidInput`] and the position is
+    /// unchanged.
+    pub fn set_clamp_seeks(&mut self, clamp: bool) {
+        self.clamp_seeks = clamp;
+    }
+
+    /// Returns the current position within the concatenated data.
+    pub fn position(&self) -> u64 {
+        let before: usize = self.slices[..self.index].iter().map(|s| s.len()).sum();
+        (before + self.offset) as u64
+    }
+
+    /// Returns the total length of the concatenated data.
+    pub fn len(&self) -> u64 {
+        self.slices.iter().map(|s| s.len() as u64).sum()
+    }
+
+    /// Returns `true` if there is no data at all.
+    pub fn is_empty(&self) -> bool {
+        self.slices.iter().all(|s| s.is_empty())
+    }
+
+    /// Gets the underlying list of slices.
+    pub const fn get_ref(&self) -> &'a [&'a [u8]] {
+        self.slices
+    }
+
+    /// Returns the number of bytes left to read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acid_io::{BufRead, SlicesReader};
+    ///
+    /// let fragments: [&[u8]; 2] = [b"ab", b"cde"];
+    /// let mut reader = SlicesReader::new(&fragments);
+    /// assert_eq!(reader.remaining(), 5);
+    ///
+    /// reader.consume(1);
+    /// assert_eq!(reader.remaining(), 4);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.slices[self.index..]
+            .iter()
+            .map(|s| s.len())
+            .sum::<usize>()
+            - self.offset
+    }
+
+    /// Returns the unread part of the current fragment, moving on to the
+    /// next non-empty fragment if the current one is exhausted.
+    fn current(&mut self) -> &'a [u8] {
+        while let Some(slice) = self.slices.get(self.index) {
+            if self.offset < slice.len() {
+                return &slice[self.offset..];
+            }
+            self.index += 1;
+            self.offset = 0;
+        }
+        &[]
+    }
+}
+
+impl Read for SlicesReader<'_> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let mut nread = 0;
+        while nread < dst.len() {
+            let src = self.current();
+            if src.is_empty() {
+                break;
+            }
+
+            let amt = cmp::min(src.len(), dst.len() - nread);
+            dst[nread..nread + amt].copy_from_slice(&src[..amt]);
+            self.offset += amt;
+            nread += amt;
+        }
+        Ok(nread)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl BufRead for SlicesReader<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(self.current())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        let available = self.current().len();
+        self.offset += cmp::min(amt, available);
+    }
+}
+
+impl Seek for SlicesReader<'_> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        let len = self.len();
+        let target = match style {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => io_core::offset_position(len, n),
+            SeekFrom::Current(n) => io_core::offset_position(self.position(), n),
+        };
+
+        let target = match target {
+            Some(n) if n <= len => n,
+            Some(_) if self.clamp_seeks => len,
+            Some(_) => {
+                return Err(Error::new_const(
+                    ErrorKind::InvalidInput,
+                    &"invalid seek past the end of the slices",
+                ))
+            }
+            None => {
+                return Err(Error::new_const(
+                    ErrorKind::InvalidInput,
+                    &"invalid seek to a negative or overflowing position",
+                ))
+            }
+        };
+
+        // Locate the fragment containing `target`.
+        self.index = 0;
+        let mut offset = target as usize;
+        while let Some(slice) = self.slices.get(self.index) {
+            if offset < slice.len() {
+                break;
+            }
+            offset -= slice.len();
+            self.index += 1;
+        }
+        self.offset = offset;
+        Ok(target)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.len())
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.position())
+    }
+}
+
+impl ReadReady for SlicesReader<'_> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(!self.current().is_empty())
+    }
+}
+
+/// A reader over two byte slices, read one after the other as a single
+/// stream.
+///
+/// This is the read side of a ring buffer whose readable region wraps around
+/// the end of the array: `first` is the part up to the end of the array and
+/// `second` the part starting again at its beginning.
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{DualSliceReader, Read};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let ring = *b"lo, world!Hel";
+/// let (second, first) = ring.split_at(10);
+/// let mut reader = DualSliceReader::new(first, second);
+///
+/// let mut buf = [0u8; 13];
+/// reader.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"Hello, world!");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DualSliceReader<'a> {
+    first: &'a [u8],
+    second: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> DualSliceReader<'a> {
+    /// Creates a new reader which reads `first` and then `second`.
+    pub const fn new(first: &'a [u8], second: &'a [u8]) -> DualSliceReader<'a> {
+        DualSliceReader {
+            first,
+            second,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.first.len() + self.second.len() - self.pos
+    }
+
+    /// Gets the underlying slices.
+    pub const fn get_ref(&self) -> (&'a [u8], &'a [u8]) {
+        (self.first, self.second)
+    }
+
+    /// Returns the unread part of whichever slice is being read.
+    fn current(&self) -> &'a [u8] {
+        match self.pos.checked_sub(self.first.len()) {
+            None => &self.first[self.pos..],
+            Some(offset) => &self.second[offset..],
+        }
+    }
+}
+
+impl Read for DualSliceReader<'_> {
+    fn read(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let mut nread = 0;
+        while nread < dst.len() {
+            let src = self.current();
+            if src.is_empty() {
+                break;
+            }
+
+            let amt = cmp::min(src.len(), dst.len() - nread);
+            dst[nread..nread + amt].copy_from_slice(&src[..amt]);
+            self.pos += amt;
+            nread += amt;
+        }
+        Ok(nread)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl BufRead for DualSliceReader<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(self.current())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos += cmp::min(amt, self.current().len());
+    }
+}
+
+impl ReadReady for DualSliceReader<'_> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(self.remaining() > 0)
+    }
+}
+
+/// A writer into two byte slices, filled one after the other as a single
+/// stream.
+///
+/// This is the write side of a ring buffer whose free region wraps around the
+/// end of the array: `first` is the part up to the end of the array and
+/// `second` the part starting again at its beginning.
+///
+/// Like `&mut [u8]`, writing to a full `DualSliceWriter` writes nothing and
+/// returns `Ok(0)`, so [`write_all`] fails with [`ErrorKind::WriteZero`].
+///
+/// [`write_all`]: Write::write_all
+///
+/// # Examples
+///
+/// ```
+/// use acid_io::{DualSliceWriter, Write};
+///
+/// # fn main() -> acid_io::Result<()> {
+/// let mut ring = [0u8; 13];
+/// let (second, first) = ring.split_at_mut(10);
+/// let mut writer = DualSliceWriter::new(first, second);
+///
+/// writer.write_all(b"Hello, world!")?;
+/// assert_eq!(writer.written(), 13);
+/// assert_eq!(&ring, b"lo, world!Hel");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DualSliceWriter<'a> {
+    first: &'a mut [u8],
+    second: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> DualSliceWriter<'a> {
+    /// Creates a new writer which fills `first` and then `second`.
+    pub fn new(first: &'a mut [u8], second: &'a mut [u8]) -> DualSliceWriter<'a> {
+        DualSliceWriter {
+            first,
+            second,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn written(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes which can still be written.
+    pub fn remaining(&self) -> usize {
+        self.first.len() + self.second.len() - self.pos
+    }
+
+    /// Consumes the writer, returning the underlying slices.
+    pub fn into_inner(self) -> (&'a mut [u8], &'a mut [u8]) {
+        (self.first, self.second)
+    }
+}
+
+impl Write for DualSliceWriter<'_> {
+    fn write(&mut self, src: &[u8]) -> Result<usize> {
+        let mut nwritten = 0;
+        while nwritten < src.len() {
+            let dst = match self.pos.checked_sub(self.first.len()) {
+                None => &mut self.first[self.pos..],
+                Some(offset) => &mut self.second[offset..],
+            };
+            if dst.is_empty() {
+                break;
+            }
+
+            let amt = cmp::min(dst.len(), src.len() - nwritten);
+            dst[..amt].copy_from_slice(&src[nwritten..nwritten + amt]);
+            self.pos += amt;
+            nwritten += amt;
+        }
+        Ok(nwritten)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
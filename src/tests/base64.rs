@@ -0,0 +1,118 @@
+use crate::{Base64Reader, Base64Writer, ErrorKind, Read, Write};
+
+const CASES: [(&[u8], &[u8]); 7] = [
+    (b"", b""),
+    (b"f", b"Zg=="),
+    (b"fo", b"Zm8="),
+    (b"foo", b"Zm9v"),
+    (b"foob", b"Zm9vYg=="),
+    (b"fooba", b"Zm9vYmE="),
+    (b"foobar", b"Zm9vYmFy"),
+];
+
+/// Reads `r` to the end one byte at a time.
+fn read_bytewise<R: Read>(r: &mut R, out: &mut [u8]) -> usize {
+    let mut len = 0;
+    while r.read(&mut out[len..len + 1]).unwrap() == 1 {
+        len += 1;
+    }
+    len
+}
+
+#[test]
+fn base64_writer() {
+    for (plain, encoded) in CASES {
+        let mut out = [0u8; 8];
+        let mut w = Base64Writer::new(&mut out[..]);
+        // Split the input so that groups span writes.
+        for b in plain {
+            w.write_all(&[*b]).unwrap();
+        }
+        let rest = w.finish().unwrap();
+        let len = 8 - rest.len();
+        assert_eq!(&out[..len], encoded);
+    }
+
+    // A large write is accepted in part.
+    let mut out = [0u8; 512];
+    let mut w = Base64Writer::new(&mut out[..]);
+    assert_eq!(w.write(&[0xFF; 300]).unwrap(), 192);
+}
+
+#[test]
+fn base64_writer_short_write() {
+    let mut out = [0u8; 2];
+    let mut w = Base64Writer::new(&mut out[..]);
+    assert_eq!(w.write(b"abc").unwrap(), 3);
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::WriteZero);
+    assert_eq!(&out, b"YW");
+}
+
+#[test]
+fn base64_reader() {
+    for (plain, encoded) in CASES {
+        let mut out = [0u8; 8];
+        let mut r = Base64Reader::new(encoded);
+        let len = read_bytewise(&mut r, &mut out);
+        assert_eq!(&out[..len], plain);
+    }
+
+    // Groups and padding split across the inner reader's buffers, with
+    // embedded line breaks and no padding at the end.
+    let mut out = [0u8; 16];
+    let inner = (&b"Zm9v\r\nY"[..]).chain(&b"g="[..]);
+    let mut r = Base64Reader::new(inner.chain(&b"=\n"[..]));
+    assert_eq!(read_bytewise(&mut r, &mut out), 4);
+    assert_eq!(&out[..4], b"foob");
+
+    let mut r = Base64Reader::new((&b"Zm9vY"[..]).chain(&b"mE"[..]));
+    assert_eq!(r.read(&mut out).unwrap(), 3);
+    assert_eq!(r.read(&mut out).unwrap(), 2);
+    assert_eq!(&out[..2], b"ba");
+    assert_eq!(r.read(&mut out).unwrap(), 0);
+}
+
+#[test]
+fn base64_reader_invalid() {
+    let mut buf = [0u8; 8];
+
+    // Bytes before an invalid character come first, then the error.
+    let mut r = Base64Reader::new(&b"Zm9v*YmFy"[..]);
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"bar");
+
+    // Data after padding.
+    let mut r = Base64Reader::new(&b"Zg==\nZg=="[..]);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    // Misplaced padding.
+    for input in [&b"Z==="[..], b"=Zg=", b"Zg=g"] {
+        let mut r = Base64Reader::new(input);
+        assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    // A truncated group or padding.
+    let mut r = Base64Reader::new(&b"Zm9vY"[..]);
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    let mut r = Base64Reader::new(&b"Zg="[..]);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn base64_round_trip() {
+    let data: [u8; 256] = core::array::from_fn(|i| i as u8);
+    let mut encoded = [0u8; 344];
+    let mut w = Base64Writer::new(&mut encoded[..]);
+    w.write_all(&data).unwrap();
+    assert!(w.finish().unwrap().is_empty());
+
+    let mut decoded = [0u8; 256];
+    let mut r = Base64Reader::new(&encoded[..]);
+    r.read_exact(&mut decoded).unwrap();
+    assert_eq!(decoded, data);
+    assert_eq!(r.read(&mut decoded).unwrap(), 0);
+}
@@ -0,0 +1,64 @@
+use crate::{BitCursor, BitOrder, ErrorKind, SeekFrom};
+
+#[test]
+fn bit_cursor_msb_first() {
+    let mut bits = BitCursor::new([0xA5u8, 0x3C, 0xFF, 0x00, 0x12, 0x34, 0x56, 0x78, 0x9A]);
+    assert_eq!(bits.bit_len(), 72);
+    assert_eq!(bits.read_bits(0).unwrap(), 0);
+    assert!(bits.read_bit().unwrap());
+    assert!(!bits.read_bit().unwrap());
+    assert_eq!(bits.read_bits(10).unwrap(), 0b10_0101_0011);
+    assert_eq!(bits.bit_position(), 12);
+    assert_eq!(bits.remaining_bytes(), None);
+
+    assert_eq!(bits.align_to_byte(), 2);
+    assert_eq!(bits.align_to_byte(), 2);
+    assert_eq!(bits.remaining_bytes().unwrap().len(), 7);
+
+    bits.set_bit_position(4);
+    assert_eq!(bits.read_bits(64).unwrap(), 0x53CF_F001_2345_6789);
+    assert_eq!(bits.remaining_bits(), 4);
+}
+
+#[test]
+fn bit_cursor_lsb_first() {
+    let mut bits = BitCursor::with_order([0b1011_0010u8, 0b0000_0001], BitOrder::LsbFirst);
+    assert_eq!(bits.order(), BitOrder::LsbFirst);
+    assert!(!bits.read_bit().unwrap());
+    assert!(bits.read_bit().unwrap());
+    assert_eq!(bits.read_bits(7).unwrap(), 0b1_101100);
+    assert_eq!(bits.read_bits(7).unwrap(), 0);
+
+    bits.set_bit_position(0);
+    assert_eq!(bits.read_bits(16).unwrap(), 0x01B2);
+}
+
+#[test]
+fn bit_cursor_seek_and_eof() {
+    let mut bits = BitCursor::new(&b"\xF0"[..]);
+    assert_eq!(bits.seek_bits(SeekFrom::End(-3)).unwrap(), 5);
+    assert_eq!(
+        bits.read_bits(4).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert_eq!(bits.bit_position(), 5);
+    assert_eq!(bits.read_bits(3).unwrap(), 0);
+
+    assert_eq!(bits.seek_bits(SeekFrom::Current(-8)).unwrap(), 0);
+    assert_eq!(
+        bits.seek_bits(SeekFrom::Current(-1)).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    assert_eq!(bits.seek_bits(SeekFrom::Start(20)).unwrap(), 20);
+    assert_eq!(bits.remaining_bits(), 0);
+    assert_eq!(bits.remaining_bytes(), None);
+    assert_eq!(bits.align_to_byte(), 3);
+    assert_eq!(bits.remaining_bytes(), Some(&[][..]));
+    assert!(bits.read_bit().is_err());
+}
+
+#[test]
+#[should_panic(expected = "cannot read more than 64 bits at once")]
+fn bit_cursor_too_many_bits() {
+    let _ = BitCursor::new([0u8; 16]).read_bits(65);
+}
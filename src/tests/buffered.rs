@@ -173,6 +173,19 @@ fn test_buffered_reader_stream_position() {
     assert_eq!(reader.buffer(), &[][..]);
 }
 
+#[test]
+fn test_buffered_reader_stream_position_after_consume() {
+    let inner: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7];
+    let mut reader = BufReader::with_capacity(4, Cursor::new(inner));
+
+    assert_eq!(reader.fill_buf().ok(), Some(&[0, 1, 2, 3][..]));
+    reader.consume(3);
+    // The underlying reader is ahead by the one byte still buffered.
+    assert_eq!(reader.get_ref().position(), 4);
+    assert_eq!(reader.stream_position().ok(), Some(3));
+    assert_eq!(reader.buffer(), &[3][..]);
+}
+
 #[test]
 fn test_buffered_reader_invalidated_after_read() {
     let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
@@ -313,6 +326,53 @@ fn test_buffered_reader_read_to_string_consumes_buffer() {
     assert!(reader.buffer().is_empty());
 }
 
+#[test]
+fn test_buffered_reader_into_parts() {
+    let data: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7];
+    let mut reader = BufReader::with_capacity(4, data);
+    assert_eq!(reader.fill_buf().ok(), Some(&[0, 1, 2, 3][..]));
+    reader.consume(1);
+
+    let (mut inner, buffered) = reader.into_parts();
+    assert_eq!(buffered, [1, 2, 3]);
+    assert_eq!(inner, [4, 5, 6, 7]);
+
+    let mut rest = buffered;
+    inner.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [1, 2, 3, 4, 5, 6, 7]);
+
+    let (inner, buffered) = BufReader::new(data).into_parts();
+    assert!(buffered.is_empty());
+    assert_eq!(inner, data);
+}
+
+#[test]
+fn test_buffered_reader_fill_buf_at_least() {
+    let data: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let inner = data[..3].chain(&data[3..5]).chain(&data[5..]);
+    let mut reader = BufReader::with_capacity(4, inner);
+
+    assert_eq!(reader.fill_buf_at_least(4).unwrap(), [0, 1, 2, 3]);
+    reader.consume(3);
+    // Moves the leftover byte to the front to make room.
+    assert_eq!(reader.fill_buf_at_least(3).unwrap(), [3, 4, 5, 6]);
+    reader.consume(4);
+    // Shorter than requested only at EOF.
+    assert_eq!(reader.fill_buf_at_least(4).unwrap(), [7, 8, 9]);
+
+    let err = reader.fill_buf_at_least(5).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "cannot consume 3 bytes, only 2 are buffered")]
+fn test_buffered_reader_consume_too_much() {
+    let mut reader = BufReader::with_capacity(2, &b"abcd"[..]);
+    reader.fill_buf().unwrap();
+    reader.consume(3);
+}
+
 #[test]
 fn test_buffered_writer() {
     let inner = Vec::new();
@@ -928,3 +988,52 @@ fn single_formatted_write() {
         [RecordedEvent::Write("hello, world!\n".to_string())]
     );
 }
+
+#[test]
+fn buf_reader_skips_no_op_seeks() {
+    struct Counting<'a> {
+        inner: Cursor<&'a [u8]>,
+        seeks: usize,
+    }
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> crate::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for Counting<'_> {
+        fn seek(&mut self, pos: SeekFrom) -> crate::Result<u64> {
+            self.seeks += 1;
+            self.inner.seek(pos)
+        }
+
+        fn known_position(&self) -> Option<u64> {
+            self.inner.known_position()
+        }
+    }
+
+    let inner = Counting {
+        inner: Cursor::new(&b"0123456789"[..]),
+        seeks: 0,
+    };
+    let mut reader = BufReader::with_capacity(4, inner);
+
+    // With data buffered, the underlying reader has to move back.
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.known_position(), Some(2));
+    assert_eq!(reader.seek(SeekFrom::Current(0)).unwrap(), 2);
+    assert_eq!(reader.get_ref().seeks, 1);
+
+    // With the buffer empty, seeks to the current position are skipped.
+    assert_eq!(reader.seek(SeekFrom::Current(0)).unwrap(), 2);
+    assert_eq!(reader.seek(SeekFrom::Start(2)).unwrap(), 2);
+    assert_eq!(reader.get_ref().seeks, 1);
+
+    assert_eq!(reader.seek(SeekFrom::Start(5)).unwrap(), 5);
+    assert_eq!(reader.seek(SeekFrom::End(-5)).unwrap(), 5);
+    assert_eq!(reader.get_ref().seeks, 3);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"56");
+}
@@ -0,0 +1,188 @@
+use core::cmp;
+
+use crate::{
+    Checksum, ChecksumAppendWriter, ChecksumLayout, ChecksumReader, ChecksumVerifyReader,
+    ChecksumWriter, ErrorKind, IoSlice, IoSliceMut, Read, Result, Write,
+};
+
+#[derive(Default)]
+struct ByteSum(u64);
+
+impl Checksum for ByteSum {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0 += bytes.iter().map(|&b| b as u64).sum::<u64>();
+    }
+
+    fn finalize(self) -> u64 {
+        self.0
+    }
+}
+
+#[test]
+fn checksum_reader() -> Result<()> {
+    let mut r = ChecksumReader::new(&[1u8, 2, 3, 4][..], ByteSum::default());
+    let mut buf = [0u8; 3];
+    assert_eq!(r.read(&mut buf)?, 3);
+    assert_eq!(r.checksum().0, 6);
+
+    let (mut a, mut b) = ([0u8; 0], [0u8; 2]);
+    let n = r.read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])?;
+    assert_eq!(n, 1);
+    assert_eq!(r.finalize(), 10);
+    Ok(())
+}
+
+#[test]
+fn checksum_writer() -> Result<()> {
+    let mut out = [0u8; 3];
+    let mut w = ChecksumWriter::new(&mut out[..], ByteSum::default());
+    // Only the bytes which fit are counted.
+    assert_eq!(w.write(&[1, 2])?, 2);
+    assert_eq!(
+        w.write_vectored(&[IoSlice::new(&[3]), IoSlice::new(&[4])])?,
+        1
+    );
+    assert_eq!(w.write(&[5])?, 0);
+    let (rest, sum) = w.into_parts();
+    assert!(rest.is_empty());
+    assert_eq!(sum.finalize(), 6);
+    Ok(())
+}
+
+#[test]
+fn checksum_append_writer() -> Result<()> {
+    let mut out = [0u8; 8];
+    let layout = ChecksumLayout::little_endian(3);
+    let mut w = ChecksumAppendWriter::new(&mut out[..], ByteSum::default(), layout);
+    w.write_all(&[0xFF; 4])?;
+    w.flush()?;
+    assert_eq!(w.checksum().0, 0x3FC);
+    assert_eq!(w.get_ref().len(), 4);
+    assert_eq!(w.finish()?.len(), 1);
+    assert_eq!(&out[..7], &[0xFF, 0xFF, 0xFF, 0xFF, 0xFC, 0x03, 0x00]);
+
+    // The trailer keeps the low bytes of the checksum.
+    let mut out = [0u8; 1];
+    let w = ChecksumAppendWriter::new(&mut out[..], ByteSum(0x1234), ChecksumLayout::big_endian(1));
+    w.finish()?;
+    assert_eq!(out, [0x34]);
+
+    let mut out = [0u8; 3];
+    let mut w = ChecksumAppendWriter::new(
+        &mut out[..],
+        ByteSum::default(),
+        ChecksumLayout::big_endian(2),
+    );
+    w.write_all(&[1, 2])?;
+    assert_eq!(w.finish().unwrap_err().kind(), ErrorKind::WriteZero);
+    Ok(())
+}
+
+#[test]
+fn checksum_verify_reader() -> Result<()> {
+    let layout = ChecksumLayout::little_endian(2);
+    let data: &[u8] = &[1, 2, 3, 4, 5, 15, 0];
+
+    // Any split of the reads gives the same data and never exposes the
+    // trailer.
+    for chunk in 1..9 {
+        let mut r = ChecksumVerifyReader::new(data.chunked(chunk), ByteSum::default(), layout);
+        let mut out = [0u8; 8];
+        let mut len = 0;
+        loop {
+            let end = cmp::min(len + chunk, out.len());
+            match r.read(&mut out[len..end])? {
+                0 => break,
+                n => len += n,
+            }
+        }
+        assert_eq!(&out[..len], &[1, 2, 3, 4, 5]);
+        assert!(r.is_verified());
+        assert_eq!(r.read(&mut out)?, 0);
+    }
+
+    let mut r = ChecksumVerifyReader::new(&[1u8, 2, 4, 0][..], ByteSum::default(), layout);
+    let mut out = [0u8; 8];
+    assert_eq!(r.read(&mut out)?, 2);
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert!(!r.is_verified());
+
+    let mut r = ChecksumVerifyReader::new(&[0u8][..], ByteSum::default(), layout);
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let mut r = ChecksumVerifyReader::new(&[0u8, 0][..], ByteSum::default(), layout);
+    assert_eq!(r.read(&mut out)?, 0);
+    assert!(r.is_verified());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "checksum")]
+fn checksum_trailer_round_trip() -> Result<()> {
+    use crate::Crc32;
+
+    let layout = ChecksumLayout::big_endian(4);
+    let mut out = [0u8; 15];
+    let mut w = ChecksumAppendWriter::new(&mut out[..], Crc32::new(), layout);
+    w.write_all(b"hello world")?;
+    w.finish()?;
+
+    let mut r = ChecksumVerifyReader::new(&out[..], Crc32::new(), layout);
+    let mut buf = [0u8; 11];
+    r.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"hello world");
+    assert_eq!(r.read(&mut buf)?, 0);
+
+    out[3] ^= 1;
+    let mut r = ChecksumVerifyReader::new(&out[..], Crc32::new(), layout);
+    r.read_exact(&mut buf)?;
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "checksum width must be from 1 to 8")]
+fn checksum_layout_too_wide() {
+    ChecksumLayout::big_endian(9);
+}
+
+#[test]
+#[cfg(feature = "checksum")]
+fn crc32() {
+    use crate::Crc32;
+
+    assert_eq!(Crc32::new().finalize(), 0);
+
+    let mut crc = Crc32::new();
+    crc.update(b"The quick brown fox ");
+    crc.update(b"jumps over the lazy dog");
+    assert_eq!(crc.finalize(), 0x414F_A339);
+}
+
+#[test]
+#[cfg(feature = "checksum")]
+fn adler32() {
+    use crate::Adler32;
+
+    assert_eq!(Adler32::new().finalize(), 1);
+
+    // Long enough to need the modular reduction several times.
+    let data = [0xFFu8; 20000];
+    let mut whole = Adler32::new();
+    whole.update(&data);
+    let mut split = Adler32::new();
+    for chunk in data.chunks(7) {
+        split.update(chunk);
+    }
+    let expected = {
+        let (mut a, mut b) = (1u64, 0u64);
+        for &byte in &data[..] {
+            a = (a + byte as u64) % 65521;
+            b = (b + a) % 65521;
+        }
+        (b << 16) | a
+    };
+    assert_eq!(whole.finalize(), expected);
+    assert_eq!(split.finalize(), expected);
+}
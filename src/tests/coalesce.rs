@@ -0,0 +1,129 @@
+use crate::{CoalesceWriter, Error, ErrorKind, Result, Write};
+
+/// Records how many bytes each call to `write` carried, accepting at most
+/// `max_write` bytes per call.
+struct CallRecorder {
+    data: [u8; 64],
+    len: usize,
+    calls: [usize; 16],
+    n_calls: usize,
+    max_write: usize,
+}
+
+impl CallRecorder {
+    fn new(max_write: usize) -> CallRecorder {
+        CallRecorder {
+            data: [0; 64],
+            len: 0,
+            calls: [0; 16],
+            n_calls: 0,
+            max_write,
+        }
+    }
+
+    fn written(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    fn calls(&self) -> &[usize] {
+        &self.calls[..self.n_calls]
+    }
+}
+
+impl Write for CallRecorder {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = buf.len().min(self.max_write);
+        self.data[self.len..self.len + n].copy_from_slice(&buf[..n]);
+        self.len += n;
+        self.calls[self.n_calls] = n;
+        self.n_calls += 1;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn coalesce_small_writes() {
+    let mut w = CoalesceWriter::with_buffer(CallRecorder::new(64), [0u8; 8], 4);
+    w.write_all(b"ab").unwrap();
+    w.write_all(b"cde").unwrap();
+    w.write_all(b"f").unwrap();
+    assert_eq!(w.buffer(), b"abcdef");
+    assert!(w.get_ref().calls().is_empty());
+
+    // Doesn't fit in the remaining two bytes, so the batch is committed.
+    w.write_all(b"ghi").unwrap();
+    assert_eq!(w.get_ref().calls(), [6]);
+    assert_eq!(w.buffer(), b"ghi");
+
+    w.commit().unwrap();
+    assert_eq!(w.get_ref().calls(), [6, 3]);
+    assert_eq!(w.get_ref().written(), b"abcdefghi");
+
+    // Committing an empty buffer does not touch the writer.
+    w.commit().unwrap();
+    assert_eq!(w.get_ref().calls(), [6, 3]);
+}
+
+#[test]
+fn coalesce_large_writes_bypass_buffer() {
+    let mut w = CoalesceWriter::with_buffer(CallRecorder::new(64), [0u8; 8], 4);
+    w.write_all(b"ab").unwrap();
+    assert_eq!(w.write(b"cdef").unwrap(), 4);
+    assert_eq!(w.get_ref().calls(), [2, 4]);
+    assert!(w.buffer().is_empty());
+
+    // Larger than the buffer itself, regardless of the threshold.
+    w.set_threshold(100);
+    assert_eq!(w.write(b"0123456789").unwrap(), 10);
+    assert_eq!(w.get_ref().calls(), [2, 4, 10]);
+    assert_eq!(w.get_ref().written(), b"abcdef0123456789");
+}
+
+#[test]
+fn coalesce_short_inner_writes() {
+    let mut w = CoalesceWriter::with_buffer(CallRecorder::new(2), [0u8; 8], 8);
+    w.write_all(b"abcde").unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.get_ref().calls(), [2, 2, 1]);
+    assert_eq!(w.get_ref().written(), b"abcde");
+}
+
+#[test]
+fn coalesce_commit_error_keeps_pending() {
+    let mut out = [0u8; 4];
+    let mut w = CoalesceWriter::with_buffer(&mut out[..], [0u8; 8], 8);
+    w.write_all(b"abcdef").unwrap();
+    let err: Error = w.commit().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(w.buffer(), b"ef");
+
+    let (inner, buf, pending) = w.into_parts();
+    assert!(inner.is_empty());
+    assert_eq!(&buf[..pending], b"ef");
+    assert_eq!(&out, b"abcd");
+}
+
+#[test]
+fn coalesce_drop_commits() {
+    let mut out = [0u8; 4];
+    {
+        let mut w = CoalesceWriter::with_buffer(&mut out[..], [0u8; 4], 4);
+        w.write_all(b"xyz").unwrap();
+    }
+    assert_eq!(&out, b"xyz\0");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn coalesce_boxed_buffer() {
+    let mut w = CoalesceWriter::with_capacity(alloc::vec::Vec::new(), 4, 3);
+    w.write_all(b"ab").unwrap();
+    w.write_all(b"c").unwrap();
+    assert_eq!(w.buffer(), b"abc");
+    w.write_all(b"defg").unwrap();
+    assert_eq!(w.into_inner().unwrap(), b"abcdefg");
+}
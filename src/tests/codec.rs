@@ -0,0 +1,577 @@
+use core::cmp;
+
+use crate::{
+    ErrorKind, EscapeWriter, HexReader, HexWriter, LineEnding, NewlineReader, NewlineWriter, Read,
+    Result, RunLengthEncoding, RunLengthReader, RunLengthWriter, SlipReader, SlipWriter, Transform,
+    TransformReader, TransformWriter, UnescapeReader, Utf8Writer, Write, XorReader, XorWriter,
+};
+
+#[test]
+fn rle_write() -> Result<()> {
+    let mut out = [0u8; 16];
+    let mut w = RunLengthWriter::new(&mut out[..]);
+    w.write_all(b"")?;
+    w.write_all(b"aa")?;
+    w.write_all(b"ab")?;
+    w.flush()?;
+    // Flushing again does not emit an empty run.
+    w.flush()?;
+    w.write_all(b"b")?;
+    let rest = w.into_inner()?.len();
+    assert_eq!(&out[..16 - rest], [3, b'a', 1, b'b', 1, b'b']);
+    Ok(())
+}
+
+#[test]
+fn rle_write_long_run() -> Result<()> {
+    let mut out = [0u8; 6];
+    let mut w = RunLengthWriter::with_encoding(&mut out[..], RunLengthEncoding::ByteFirst);
+    w.write_all(&[7; 300])?;
+    w.write_all(&[8])?;
+    w.flush()?;
+    assert_eq!(out, [7, 255, 7, 45, 8, 1]);
+    Ok(())
+}
+
+#[test]
+fn rle_write_error_keeps_state() {
+    let mut out = [0u8; 3];
+    let mut w = RunLengthWriter::new(&mut out[..]);
+    // The first pair fits, the second is only half written.
+    assert_eq!(w.write(b"aabc").unwrap(), 3);
+    let err = w.write(b"c").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::WriteZero);
+    assert_eq!(out, [2, b'a', 1]);
+}
+
+#[test]
+fn rle_read_spans_calls() -> Result<()> {
+    let encoded: &[u8] = &[5, b'x', 1, b'y'];
+    let mut r = RunLengthReader::new(encoded);
+    let mut buf = [0u8; 2];
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(&buf, b"xx");
+    // The rest of the run, without reaching into the next pair.
+    assert_eq!(r.read(&mut buf)?, 1);
+    assert_eq!(buf[0], b'x');
+    assert_eq!(r.read(&mut buf)?, 1);
+    assert_eq!(buf[0], b'y');
+    assert_eq!(r.read(&mut buf)?, 0);
+    Ok(())
+}
+
+#[test]
+fn rle_read_invalid() {
+    let mut buf = [0u8; 8];
+    let mut r = RunLengthReader::new(&[2, b'a', 3][..]);
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let mut r = RunLengthReader::new(&[0, b'a'][..]);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn rle_round_trip() -> Result<()> {
+    let input: [u8; 600] = core::array::from_fn(|i| (i / 7 % 3) as u8);
+    for encoding in [RunLengthEncoding::CountFirst, RunLengthEncoding::ByteFirst] {
+        let mut encoded = [0u8; 1024];
+        let mut w = RunLengthWriter::with_encoding(&mut encoded[..], encoding);
+        w.write_all(&input)?;
+        let len = 1024 - w.into_inner()?.len();
+
+        let mut r = RunLengthReader::with_encoding(&encoded[..len], encoding);
+        let mut decoded = [0u8; 600];
+        r.read_exact(&mut decoded)?;
+        assert_eq!(decoded, input);
+        assert_eq!(r.read(&mut decoded)?, 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn utf8_write_split_characters() -> Result<()> {
+    let text = "a\u{e9}\u{20ac}\u{1f600}z".as_bytes();
+    let mut out = [0u8; 16];
+    let mut w = Utf8Writer::new(&mut out[..]);
+    for chunk in text.chunks(1) {
+        w.write_all(chunk)?;
+    }
+    w.flush()?;
+    assert_eq!(&out[..text.len()], text);
+    Ok(())
+}
+
+#[test]
+fn utf8_write_holds_back_partial_character() -> Result<()> {
+    let mut out = [0u8; 8];
+    let mut w = Utf8Writer::new(&mut out[..]);
+    assert_eq!(w.write(b"ab\xE2\x82")?, 2);
+    assert_eq!(w.write(b"\xE2\x82")?, 2);
+    assert_eq!(w.get_ref().len(), 6);
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::InvalidData);
+    w.write_all(b"\xAC")?;
+    w.flush()?;
+    assert_eq!(w.get_ref().len(), 3);
+    Ok(())
+}
+
+#[test]
+fn utf8_write_invalid() {
+    let mut out = [0u8; 8];
+    let mut w = Utf8Writer::new(&mut out[..]);
+    assert_eq!(w.write(b"ok\xFFno").unwrap(), 2);
+    assert_eq!(
+        w.write(b"\xFFno").unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    // Overlong encodings and surrogates are rejected.
+    for bad in [
+        &b"\xC0\x80"[..],
+        b"\xE0\x80\x80",
+        b"\xED\xA0\x80",
+        b"\xF4\x90\x80\x80",
+    ] {
+        let mut out = [0u8; 8];
+        let mut w = Utf8Writer::new(&mut out[..]);
+        assert_eq!(w.write_all(bad).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    // A broken continuation is reported once the held back bytes run out.
+    let mut out = [0u8; 8];
+    let mut w = Utf8Writer::new(&mut out[..]);
+    assert_eq!(w.write(b"\xE2").unwrap(), 1);
+    assert_eq!(w.write(b"a").unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn newline_write() -> Result<()> {
+    let mut out = [0u8; 32];
+    let mut w = NewlineWriter::new(&mut out[..], LineEnding::CrLf);
+    w.write_all(b"a\nb\r\nc\rd")?;
+    // A `\r\n` split across writes is still one line ending.
+    w.write_all(b"e\r")?;
+    w.flush()?;
+    w.write_all(b"\nf\r")?;
+    w.write_all(b"\r")?;
+    let rest = w.into_inner()?;
+    assert_eq!(rest.len(), 32 - 15);
+    assert_eq!(&out[..15], b"a\r\nb\r\nc\rde\r\nf\r\r");
+
+    let mut out = [0u8; 16];
+    let mut w = NewlineWriter::new(&mut out[..], LineEnding::Lf);
+    w.write_all(b"a\r")?;
+    w.write_all(b"\nb\nc\r\n")?;
+    w.into_inner()?;
+    assert_eq!(&out[..6], b"a\nb\nc\n");
+    Ok(())
+}
+
+#[test]
+fn newline_write_full() {
+    let mut out = [0u8; 3];
+    let mut w = NewlineWriter::new(&mut out[..], LineEnding::CrLf);
+    // The line ending is accepted, and the failure to pass it on surfaces
+    // on the next call.
+    w.write_all(b"ab\n").unwrap();
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::WriteZero);
+}
+
+#[test]
+fn newline_read() -> Result<()> {
+    // `\r\n` split between the two halves of the chain.
+    let input = (&b"a\r\nb\r"[..]).chain(&b"\nc\rd\n\n"[..]);
+    let r = NewlineReader::new(input, LineEnding::Lf);
+    let mut out = [0u8; 16];
+    let n = r.read_full().read(&mut out)?;
+    assert_eq!(&out[..n], b"a\nb\nc\nd\n\n");
+
+    let mut r = NewlineReader::new(&b"a\nb\r\r\nc"[..], LineEnding::CrLf);
+    let mut out = [0u8; 16];
+    let n = r.by_ref().read_full().read(&mut out)?;
+    assert_eq!(&out[..n], b"a\r\nb\r\n\r\nc");
+    assert_eq!(r.read(&mut out)?, 0);
+    Ok(())
+}
+
+#[test]
+fn newline_read_buffered() -> Result<()> {
+    use crate::BufRead;
+
+    let mut r = NewlineReader::new(&b"ab\r\ncd"[..], LineEnding::CrLf);
+    assert_eq!(r.fill_buf()?, b"ab");
+    r.consume(2);
+    assert_eq!(r.fill_buf()?, b"\r\n");
+    r.consume(1);
+    assert_eq!(r.fill_buf()?, b"\n");
+    r.consume(1);
+    assert_eq!(r.fill_buf()?, b"cd");
+    r.consume(2);
+    assert_eq!(r.fill_buf()?, b"");
+    Ok(())
+}
+
+#[test]
+fn escape_write() -> Result<()> {
+    let mut out = [0u8; 16];
+    let mut w = EscapeWriter::new(&mut out[..], b'\\', &[(b'\\', b'\\'), (b'\n', b'n')]);
+    assert_eq!(w.write(b"ab\ncd")?, 2);
+    assert_eq!(w.write(b"\ncd")?, 1);
+    w.write_all(b"\\")?;
+    w.flush()?;
+    w.into_inner()?;
+    assert_eq!(&out[..6], b"ab\\n\\\\");
+
+    // An escape sequence which does not fit is reported by the next call.
+    let mut out = [0u8; 2];
+    let mut w = EscapeWriter::new(&mut out[..], b'\\', &[(b'\\', b'\\')]);
+    assert_eq!(w.write(b"a\\")?, 1);
+    assert_eq!(w.write(b"\\")?, 1);
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::WriteZero);
+    Ok(())
+}
+
+#[test]
+#[should_panic = "the escape byte must itself be escaped"]
+fn escape_write_unescaped_escape() {
+    EscapeWriter::new(&mut [0u8; 4][..], b'\\', &[(b'\n', b'n')]);
+}
+
+#[test]
+fn slip_write() -> Result<()> {
+    let mut out = [0u8; 16];
+    let mut w = SlipWriter::new(&mut out[..]);
+    w.write_all(&[1, 0xC0, 0xDB, 2])?;
+    w.end_packet()?;
+    w.end_packet()?;
+    w.write_all(&[3])?;
+    w.end_packet()?;
+    w.into_inner()?;
+    assert_eq!(
+        &out[..10],
+        &[1, 0xDB, 0xDC, 0xDB, 0xDD, 2, 0xC0, 0xC0, 3, 0xC0]
+    );
+    Ok(())
+}
+
+#[test]
+fn unescape_read() -> Result<()> {
+    let escapes = [(b'\\', b'\\'), (b'\n', b'n')];
+
+    // An escape sequence split between the two halves of the chain.
+    let input = (&b"ab\\"[..]).chain(&b"nc\\\\"[..]);
+    let mut r = UnescapeReader::new(input, b'\\', &escapes);
+    let mut out = [0u8; 8];
+    assert_eq!(r.read(&mut out)?, 2);
+    assert_eq!(r.read(&mut out[2..])?, 3);
+    assert_eq!(&out[..5], b"ab\nc\\");
+    assert_eq!(r.read(&mut out)?, 0);
+    assert!(!r.is_frame_end());
+
+    // Unknown sequences are reported after the bytes before them, then
+    // skipped.
+    let mut r = UnescapeReader::new(&b"a\\xb\\"[..], b'\\', &escapes);
+    assert_eq!(r.read(&mut out)?, 1);
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut out)?, 1);
+    assert_eq!(out[0], b'b');
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut out)?, 0);
+    Ok(())
+}
+
+#[test]
+fn unescape_read_frames() -> Result<()> {
+    let mut r = UnescapeReader::new(&b"ab|\\|c|"[..], b'\\', &[(b'\\', b'\\'), (b'|', b'/')]);
+    r.set_delimiter(Some(b'|'));
+    let mut out = [0u8; 8];
+    assert_eq!(r.read(&mut out)?, 2);
+    assert_eq!(r.read(&mut out)?, 0);
+    assert!(r.is_frame_end());
+    r.next_frame();
+
+    // The delimiter cannot be escaped by accident.
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut out)?, 1);
+    assert_eq!(out[0], b'c');
+    assert_eq!(r.read(&mut out)?, 0);
+    assert!(r.is_frame_end());
+    r.next_frame();
+    assert_eq!(r.read(&mut out)?, 0);
+    assert!(!r.is_frame_end());
+    Ok(())
+}
+
+#[test]
+fn slip_round_trip() -> Result<()> {
+    let packets: [&[u8]; 3] = [&[1, 0xC0, 2], &[], &[0xDB, 0xDB, 0xDC]];
+    let mut encoded = [0u8; 32];
+    let mut w = SlipWriter::new(&mut encoded[..]);
+    for packet in packets {
+        w.write_all(packet)?;
+        w.end_packet()?;
+    }
+    let len = 32 - w.into_inner()?.len();
+
+    let mut r = SlipReader::new(&encoded[..len]);
+    for packet in packets {
+        let mut out = [0u8; 8];
+        let n = r.by_ref().read_full().read(&mut out)?;
+        assert_eq!(&out[..n], packet);
+        assert!(r.next_packet()?);
+    }
+    assert!(!r.next_packet()?);
+    Ok(())
+}
+
+#[test]
+fn slip_skip_packet() -> Result<()> {
+    let mut r = SlipReader::new(&[1, 2, 0xDB, 0x00, 3, 0xC0, 4, 0xC0, 5][..]);
+    let mut out = [0u8; 1];
+    assert_eq!(r.read(&mut out)?, 1);
+    assert!(r.next_packet()?);
+    assert_eq!(r.read(&mut out)?, 1);
+    assert_eq!(out[0], 4);
+    assert!(r.next_packet()?);
+    assert!(!r.next_packet()?);
+    Ok(())
+}
+
+// Hex encodes its input, holding back the low nibble if the output is full.
+#[derive(Debug, Default)]
+struct Hex {
+    pending: Option<u8>,
+}
+
+impl Transform for Hex {
+    fn transform(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let (mut i, mut o) = (0, 0);
+        loop {
+            if let Some(c) = self.pending {
+                if o == output.len() {
+                    break;
+                }
+                output[o] = c;
+                o += 1;
+                self.pending = None;
+            }
+            if i == input.len() || o == output.len() {
+                break;
+            }
+            output[o] = DIGITS[(input[i] >> 4) as usize];
+            self.pending = Some(DIGITS[(input[i] & 0xF) as usize]);
+            i += 1;
+            o += 1;
+        }
+        Ok((i, o))
+    }
+
+    fn finish(&mut self, output: &mut [u8]) -> Result<usize> {
+        match self.pending.take() {
+            Some(c) if !output.is_empty() => {
+                output[0] = c;
+                Ok(1)
+            }
+            pending => {
+                self.pending = pending;
+                Ok(0)
+            }
+        }
+    }
+}
+
+struct Stuck;
+
+impl Transform for Stuck {
+    fn transform(&mut self, _: &[u8], _: &mut [u8]) -> Result<(usize, usize)> {
+        Ok((0, 0))
+    }
+}
+
+#[test]
+fn transform_reader() -> Result<()> {
+    let mut r = TransformReader::new(&[0x01, 0xAB, 0xFF][..], Hex::default());
+    let mut out = [0u8; 6];
+    for i in 0..6 {
+        assert_eq!(r.read(&mut out[i..i + 1])?, 1);
+    }
+    assert_eq!(&out, b"01abff");
+    assert_eq!(r.read(&mut out)?, 0);
+    assert!(r.transform().pending.is_none());
+
+    let mut r = TransformReader::new(&[0x12, 0x34][..], Hex::default());
+    let mut out = [0u8; 8];
+    assert_eq!(r.read(&mut out[..3])?, 3);
+    assert_eq!(r.read(&mut out[3..])?, 1);
+    assert_eq!(&out[..4], b"1234");
+    assert_eq!(r.read(&mut out)?, 0);
+
+    let mut r = TransformReader::new(&b"x"[..], Stuck);
+    assert_eq!(r.read(&mut out).unwrap_err().kind(), ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+fn transform_writer() -> Result<()> {
+    let mut out = [0u8; 6];
+    let mut w = TransformWriter::new(&mut out[..], Hex::default());
+    w.write_all(&[0x01, 0xAB])?;
+    w.write_all(&[0xFF])?;
+    w.flush()?;
+    assert_eq!(w.into_inner()?.len(), 0);
+    assert_eq!(&out, b"01abff");
+
+    let mut out = [0u8; 3];
+    let mut w = TransformWriter::new(&mut out[..], Hex::default());
+    assert_eq!(w.write(&[0x12, 0x34]).unwrap(), 2);
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::WriteZero);
+
+    let mut w = TransformWriter::new(&mut out[..], Stuck);
+    assert_eq!(w.write(b"x").unwrap_err().kind(), ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+fn xor_round_trip() -> Result<()> {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let key = b"secret";
+
+    // Write in uneven pieces so the key position carries across calls.
+    let mut encoded = [0u8; 43];
+    let mut w = XorWriter::new(&mut encoded[..], &key[..]);
+    for piece in data.chunks(5) {
+        w.write_all(piece)?;
+    }
+    assert_eq!(w.key_position(), 43 % 6);
+    assert_ne!(&encoded, data);
+    for (i, (&e, &d)) in encoded.iter().zip(data.iter()).enumerate() {
+        assert_eq!(e ^ key[i % key.len()], d);
+    }
+
+    let mut r = XorReader::new(&encoded[..], key);
+    let mut decoded = [0u8; 43];
+    let mut len = 0;
+    while len < decoded.len() {
+        let end = cmp::min(len + 4, decoded.len());
+        len += r.read(&mut decoded[len..end])?;
+    }
+    assert_eq!(&decoded, data);
+    assert_eq!(r.read(&mut decoded)?, 0);
+    Ok(())
+}
+
+#[test]
+fn xor_writer_short_write() -> Result<()> {
+    let mut out = [0u8; 2];
+    let mut w = XorWriter::new(&mut out[..], [1, 2, 3]);
+    assert_eq!(w.write(&[0, 0, 0])?, 2);
+    assert_eq!(w.key_position(), 2);
+    assert_eq!(out, [1, 2]);
+
+    // Writes longer than the internal buffer are passed on in pieces.
+    let mut out = [0u8; 300];
+    let mut w = XorWriter::new(&mut out[..], [0xFF]);
+    assert_eq!(w.write(&[0u8; 300])?, 256);
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "the XOR key must not be empty")]
+fn xor_empty_key() {
+    XorReader::new(&b""[..], [0u8; 0]);
+}
+
+#[test]
+fn hex_reader() {
+    // A byte split across the two halves of a chain.
+    let mut r = HexReader::new((&b"0aB"[..]).chain(&b"c"[..]));
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(&buf[..1], [0xBC]);
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+
+    // Small reads leave the rest of the input in the stream.
+    let mut r = HexReader::new(&b"00ff7F"[..]);
+    let mut one = [0u8; 1];
+    r.read_exact(&mut one).unwrap();
+    assert_eq!(one, [0x00]);
+    r.read_exact(&mut one).unwrap();
+    assert_eq!(one, [0xFF]);
+    assert_eq!(r.get_ref(), b"7F");
+}
+
+#[test]
+fn hex_writer() {
+    let mut out = [0u8; 8];
+    let mut w = HexWriter::new(&mut out[..]);
+    w.set_uppercase(true);
+    w.write_all(&[0x00, 0xAB, 0x7f]).unwrap();
+    w.into_inner().unwrap();
+    assert_eq!(&out[..6], b"00AB7F");
+
+    // Groups continue across writes and have no trailing separator.
+    let mut out = [0u8; 16];
+    let mut w = HexWriter::new(&mut out[..]);
+    w.set_group(Some((2, b':')));
+    w.write_all(&[1, 2, 3]).unwrap();
+    w.write_all(&[4, 0xfe]).unwrap();
+    w.into_inner().unwrap();
+    assert_eq!(&out[..14], b"0102:0304:fe\0\0");
+
+    // A large write is accepted in part.
+    let mut out = [0u8; 512];
+    let mut w = HexWriter::new(&mut out[..]);
+    assert_eq!(w.write(&[0xFF; 200]).unwrap(), 85);
+}
+
+#[test]
+fn hex_writer_short_write() {
+    // The staged text is still written after the inner writer fills up.
+    let mut out = [0u8; 3];
+    let mut w = HexWriter::new(&mut out[..]);
+    assert_eq!(w.write(b"ab").unwrap(), 2);
+    assert_eq!(w.flush().unwrap_err().kind(), ErrorKind::WriteZero);
+    assert_eq!(&out, b"616");
+}
+
+#[test]
+#[should_panic(expected = "group size must be non-zero")]
+fn hex_writer_empty_group() {
+    HexWriter::new(&mut [0u8; 0][..]).set_group(Some((0, b' ')));
+}
+
+#[test]
+fn hex_reader_invalid() {
+    // Bytes before an invalid digit come first, then the error, then the rest.
+    let mut r = HexReader::new(&b"41 42g43"[..]);
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0x41);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0x42);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0x43);
+
+    // An unpaired digit before the invalid byte is dropped with it.
+    let mut r = HexReader::new(&b"414g2435"[..]);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0x41);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], [0x24, 0x35]);
+
+    // With whitespace skipped, it may even split a byte.
+    let mut r = HexReader::new(&b" 4\t1\r\n4"[..]);
+    r.set_skip_whitespace(true);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0x41);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+}
@@ -71,6 +71,24 @@ fn test_mem_mut_writer() {
     assert_eq!(&writer.get_ref()[..], b);
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mem_mut_writer_overwrite_and_append() {
+    let mut vec = vec![0xff; 10];
+    {
+        let mut writer = Cursor::new(&mut vec);
+        assert_eq!(writer.write(&[0, 1, 2]).unwrap(), 3);
+        assert_eq!(writer.position(), 3);
+        assert_eq!(writer.get_ref().len(), 10);
+
+        assert_eq!(writer.seek(SeekFrom::End(0)).unwrap(), 10);
+        assert_eq!(writer.write(&[10, 11]).unwrap(), 2);
+        assert_eq!(writer.position(), 12);
+    }
+    let b: &[_] = &[0, 1, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 10, 11];
+    assert_eq!(&vec[..], b);
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn test_box_slice_writer() {
@@ -187,6 +205,25 @@ fn test_buf_writer_seek() {
     assert_eq!(buf, b);
 }
 
+#[test]
+fn test_array_writer() {
+    let mut cursor = Cursor::new([0u8; 8]);
+    cursor.write_all(&[1, 2, 3]).unwrap();
+    assert_eq!(
+        cursor
+            .write_vectored(&[IoSlice::new(&[4, 5]), IoSlice::new(&[6, 7, 8, 9])])
+            .unwrap(),
+        5
+    );
+    assert_eq!(cursor.write(&[10]).unwrap(), 0);
+
+    cursor.seek(SeekFrom::Start(0)).unwrap();
+    let mut out = [0u8; 8];
+    cursor.read_exact(&mut out).unwrap();
+    assert_eq!(out, [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(cursor.into_inner(), out);
+}
+
 #[test]
 fn test_buf_writer_error() {
     let mut buf = [0u8; 2];
@@ -328,7 +365,7 @@ fn read_to_end() {
 #[cfg(feature = "alloc")]
 #[test]
 fn test_slice_reader() {
-    let in_buf = vec![0, 1, 2, 3, 4, 5, 6, 7];
+    let in_buf = [0, 1, 2, 3, 4, 5, 6, 7];
     let reader = &mut &in_buf[..];
     let mut buf = [];
     assert_eq!(reader.read(&mut buf).unwrap(), 0);
@@ -351,7 +388,7 @@ fn test_slice_reader() {
 #[cfg(feature = "alloc")]
 #[test]
 fn test_slice_reader_vectored() {
-    let in_buf = vec![0, 1, 2, 3, 4, 5, 6, 7];
+    let in_buf = [0, 1, 2, 3, 4, 5, 6, 7];
     let reader = &mut &in_buf[..];
     let mut buf = [];
     assert_eq!(
@@ -388,7 +425,7 @@ fn test_slice_reader_vectored() {
 #[cfg(feature = "alloc")]
 #[test]
 fn test_read_exact() {
-    let in_buf = vec![0, 1, 2, 3, 4, 5, 6, 7];
+    let in_buf = [0, 1, 2, 3, 4, 5, 6, 7];
     let reader = &mut &in_buf[..];
     let mut buf = [];
     assert!(reader.read_exact(&mut buf).is_ok());
@@ -407,7 +444,7 @@ fn test_read_exact() {
 #[cfg(feature = "alloc")]
 #[test]
 fn test_buf_reader() {
-    let in_buf = vec![0, 1, 2, 3, 4, 5, 6, 7];
+    let in_buf = [0, 1, 2, 3, 4, 5, 6, 7];
     let mut reader = Cursor::new(&in_buf[..]);
     let mut buf = [];
     assert_eq!(reader.read(&mut buf).unwrap(), 0);
@@ -533,6 +570,131 @@ fn seek_before_0() {
     }
 }
 
+#[test]
+fn seek_boundaries() {
+    fn check<T: AsRef<[u8]>>(mut r: Cursor<T>) {
+        let invalid = |r: &mut Cursor<T>, pos| {
+            let before = r.position();
+            assert_eq!(
+                r.seek(pos).unwrap_err().kind(),
+                crate::ErrorKind::InvalidInput
+            );
+            assert_eq!(r.position(), before);
+        };
+
+        // Seeking back by the full range of `i64` from near the start.
+        invalid(&mut r, SeekFrom::End(i64::MIN));
+        assert_eq!(r.seek(SeekFrom::Start(3)).unwrap(), 3);
+        invalid(&mut r, SeekFrom::Current(i64::MIN));
+        invalid(&mut r, SeekFrom::Current(-4));
+        assert_eq!(r.seek(SeekFrom::Current(-3)).unwrap(), 0);
+
+        // Relative seeks from the very top of the range.
+        assert_eq!(r.seek(SeekFrom::Start(u64::MAX)).unwrap(), u64::MAX);
+        invalid(&mut r, SeekFrom::Current(1));
+        invalid(&mut r, SeekFrom::Current(i64::MAX));
+        assert_eq!(r.seek(SeekFrom::Current(0)).unwrap(), u64::MAX);
+        assert_eq!(
+            r.seek(SeekFrom::Current(i64::MIN)).unwrap(),
+            u64::MAX - (1 << 63)
+        );
+        assert_eq!(r.seek(SeekFrom::Current(i64::MAX)).unwrap(), u64::MAX - 1);
+
+        // Far past the end, reads see no data rather than panicking.
+        let mut buf = [0u8; 2];
+        assert_eq!(r.read(&mut buf).unwrap(), 0);
+        assert_eq!(
+            r.seek(SeekFrom::End(i64::MAX)).unwrap(),
+            i64::MAX as u64 + 4
+        );
+    }
+
+    check(Cursor::new([1u8, 2, 3, 4]));
+    #[cfg(feature = "alloc")]
+    check(Cursor::new(vec![1u8, 2, 3, 4]));
+}
+
+#[test]
+fn align_to() {
+    let buf = [0u8; 4];
+    let mut r = Cursor::new(&buf[..]);
+    assert_eq!(r.align_to(4).unwrap(), 0);
+    assert_eq!(r.seek(SeekFrom::Start(1)).unwrap(), 1);
+    assert_eq!(r.align_to(0).unwrap(), 1);
+    assert_eq!(r.align_to(1).unwrap(), 1);
+    assert_eq!(r.align_to(4).unwrap(), 4);
+    assert_eq!(r.align_to(3).unwrap(), 6);
+    assert_eq!(r.position(), 6);
+
+    // Already aligned near the top of the range.
+    r.set_position(u64::MAX - 7);
+    assert_eq!(r.align_to(8).unwrap(), u64::MAX - 7);
+
+    r.set_position(u64::MAX - 6);
+    assert_eq!(
+        r.align_to(8).unwrap_err().kind(),
+        crate::ErrorKind::InvalidInput
+    );
+    assert_eq!(r.position(), u64::MAX - 6);
+}
+
+#[test]
+fn advance() {
+    let mut buf = [0u8; 4];
+    let mut c = Cursor::new(&mut buf[..]);
+    c.remaining_slice_mut()[..3].copy_from_slice(b"abc");
+    c.advance(3).unwrap();
+    assert_eq!(c.remaining_slice_mut().len(), 1);
+    assert_eq!(
+        c.advance(2).unwrap_err().kind(),
+        crate::ErrorKind::InvalidInput
+    );
+    assert_eq!(c.position(), 3);
+    c.advance(1).unwrap();
+    c.advance(0).unwrap();
+    assert!(c.remaining_slice_mut().is_empty());
+    assert!(c.advance(1).is_err());
+
+    // Positions already past the end never move.
+    c.set_position(10);
+    assert!(c.advance(0).is_err());
+    assert!(c.remaining_slice_mut().is_empty());
+
+    c.set_position(0);
+    assert!(c.advance(usize::MAX).is_err());
+    assert_eq!(&buf, b"abc\0");
+}
+
+#[test]
+fn read_footer() {
+    use crate::ReadSeekExt;
+
+    let data = b"0123456789";
+    let mut c = Cursor::new(&data[..]);
+    c.set_position(3);
+
+    let mut footer = [0; 4];
+    c.read_footer(&mut footer).unwrap();
+    assert_eq!(&footer, b"6789");
+    assert_eq!(c.position(), 3);
+
+    let mut all = [0; 10];
+    c.read_footer(&mut all).unwrap();
+    assert_eq!(&all, data);
+    assert_eq!(c.position(), 3);
+
+    c.read_footer(&mut []).unwrap();
+    assert_eq!(c.position(), 3);
+
+    let mut too_long = [0; 11];
+    assert_eq!(
+        c.read_footer(&mut too_long).unwrap_err().kind(),
+        crate::ErrorKind::UnexpectedEof
+    );
+    assert_eq!(too_long, [0; 11]);
+    assert_eq!(c.position(), 3);
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn test_seekable_mem_writer() {
@@ -576,6 +738,29 @@ fn vec_seek_past_end() {
     assert_eq!(r.write(&[3]).unwrap(), 1);
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_seek_write_read_back() {
+    let mut c = Cursor::new(b"head".to_vec());
+    assert_eq!(c.seek(SeekFrom::End(0)).unwrap(), 4);
+    c.write_all(b"-tail").unwrap();
+    assert_eq!(c.stream_position().unwrap(), 9);
+    assert_eq!(c.stream_len().unwrap(), 9);
+
+    // Writing past the end zero-fills the gap and grows the stream.
+    assert_eq!(c.seek(SeekFrom::End(2)).unwrap(), 11);
+    assert_eq!(c.stream_len().unwrap(), 9);
+    c.write_all(b"!").unwrap();
+    assert_eq!(c.stream_len().unwrap(), 12);
+    assert_eq!(c.seek(SeekFrom::End(0)).unwrap(), 12);
+
+    assert_eq!(c.seek(SeekFrom::Start(0)).unwrap(), 0);
+    let mut all = Vec::new();
+    assert_eq!(c.read_to_end(&mut all).unwrap(), 12);
+    assert_eq!(all, b"head-tail\0\0!");
+    assert_eq!(c.stream_position().unwrap(), 12);
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn vec_seek_before_0() {
@@ -606,9 +791,268 @@ fn test_eq() {
     let _: AssertEq<Cursor<Vec<u8>>> = AssertEq(Cursor::new(Vec::new()));
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_insert_remove() {
+    let mut c = Cursor::new(b"ad".to_vec());
+    c.set_position(1);
+    assert_eq!(c.insert(b"bc").unwrap(), 2);
+    assert_eq!(c.get_ref(), b"abcd");
+    assert_eq!(c.position(), 3);
+
+    c.set_position(1);
+    c.remove(2);
+    assert_eq!(c.get_ref(), b"ad");
+    assert_eq!(c.position(), 1);
+
+    // Removing past the end stops at the end.
+    c.remove(10);
+    assert_eq!(c.get_ref(), b"a");
+    c.set_position(5);
+    c.remove(1);
+    assert_eq!(c.get_ref(), b"a");
+
+    // Inserting past the end zero-fills the gap.
+    c.set_position(3);
+    c.insert(b"z").unwrap();
+    assert_eq!(c.get_ref(), b"a\0\0z");
+    assert_eq!(c.position(), 4);
+}
+
+#[test]
+fn cached_seek() {
+    use crate::CachedSeek;
+
+    struct Counting<'a> {
+        inner: Cursor<&'a [u8]>,
+        seeks: usize,
+    }
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> crate::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for Counting<'_> {
+        fn seek(&mut self, pos: SeekFrom) -> crate::Result<u64> {
+            self.seeks += 1;
+            self.inner.seek(pos)
+        }
+    }
+
+    let inner = Counting {
+        inner: Cursor::new(&b"0123456789"[..]),
+        seeks: 0,
+    };
+    let mut c = CachedSeek::new(inner).unwrap();
+    let queried = c.get_ref().seeks;
+
+    let mut buf = [0u8; 3];
+    c.read_exact(&mut buf).unwrap();
+    assert_eq!(c.seek(SeekFrom::Start(3)).unwrap(), 3);
+    assert_eq!(c.seek(SeekFrom::Current(0)).unwrap(), 3);
+    assert_eq!(c.stream_position().unwrap(), 3);
+    assert_eq!(c.get_ref().seeks, queried);
+
+    assert_eq!(c.seek(SeekFrom::Current(2)).unwrap(), 5);
+    assert_eq!(c.seek(SeekFrom::End(-5)).unwrap(), 5);
+    assert_eq!(c.get_ref().seeks, queried + 2);
+
+    // Access through `get_mut` discards the cache.
+    c.get_mut().inner.set_position(8);
+    assert_eq!(c.seek(SeekFrom::Current(0)).unwrap(), 8);
+    assert_eq!(c.get_ref().seeks, queried + 3);
+    assert!(c.seek(SeekFrom::Current(-9)).is_err());
+}
+
+#[test]
+fn write_all_at() {
+    use crate::WriteSeekExt;
+
+    let mut buf = [0u8; 6];
+    let mut c = Cursor::new(&mut buf[..]);
+    c.write_all(b"abc").unwrap();
+    c.write_all_at(4, b"xy").unwrap();
+    assert_eq!(c.position(), 3);
+
+    // The position is restored even when the write fails.
+    let err = c.write_all_at(5, b"too long").unwrap_err();
+    assert_eq!(err.kind(), crate::ErrorKind::WriteZero);
+    assert_eq!(c.position(), 3);
+    assert_eq!(&buf, b"abc\0xt");
+}
+
+#[test]
+fn fixed_len() {
+    use crate::FixedLen;
+
+    struct Unknown;
+
+    impl FixedLen for Unknown {}
+
+    // The length stays the same across reads and seeks.
+    let mut c = Cursor::new(&b"abcd"[..]);
+    assert_eq!(c.fixed_len(), Some(4));
+    let mut buf = [0u8; 3];
+    c.read_exact(&mut buf).unwrap();
+    assert_eq!(c.fixed_len(), Some(4));
+    c.seek(SeekFrom::Current(-2)).unwrap();
+    assert_eq!(c.fixed_len(), Some(4));
+    c.set_position(10);
+    assert_eq!(c.fixed_len(), Some(4));
+    assert_eq!(c.fixed_len(), Some(c.stream_len().unwrap()));
+
+    let mut slice = &b"abcd"[..];
+    assert_eq!(slice.fixed_len(), Some(4));
+    slice.read_exact(&mut buf).unwrap();
+    assert_eq!(slice.fixed_len(), Some(1));
+
+    assert_eq!(Unknown.fixed_len(), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_written() {
+    let mut c = Cursor::new(b"0123".to_vec());
+    c.write_all(b"ab").unwrap();
+    assert_eq!(c.written(), b"ab23");
+
+    // Seeking does not count as writing, in either direction.
+    c.set_position(1);
+    assert_eq!(c.written(), b"ab23");
+    c.set_position(10);
+    assert_eq!(c.written(), b"ab23");
+    c.write_all(b"z").unwrap();
+    c.set_position(0);
+    assert_eq!(c.written(), b"ab23\0\0\0\0\0\0z");
+    assert_eq!(c.into_written(), b"ab23\0\0\0\0\0\0z");
+
+    let mut c = Cursor::new(b"0123".to_vec());
+    c.write_all(b"ab").unwrap();
+    assert_eq!(c.into_written(), b"ab23");
+
+    // Inserting and removing move the written region with the data.
+    let mut c = Cursor::new(b"0123".to_vec());
+    c.insert(b"ab").unwrap();
+    assert_eq!(c.written(), b"ab0123");
+    c.set_position(0);
+    c.remove(3);
+    assert_eq!(c.into_written(), b"123");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_eq_ignores_write_history() {
+    use alloc::format;
+
+    let mut written = Cursor::new(Vec::new());
+    written.write_all(&[1, 2]).unwrap();
+    written.set_position(0);
+    let fresh = Cursor::new(vec![1, 2]);
+    assert_eq!(written, fresh);
+    assert_eq!(written.written(), fresh.written());
+    assert_eq!(format!("{:?}", written), "Cursor { inner: [1, 2], pos: 0 }");
+
+    written.set_position(1);
+    assert_ne!(written, fresh);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn vec_sparse_write() {
+    let mut c = Cursor::new(Vec::new());
+    c.set_position(10);
+    c.write_all(b"hi").unwrap();
+    assert_eq!(c.position(), 12);
+
+    // Reads see the zero-filled gap.
+    c.set_position(0);
+    let mut buf = [0xFFu8; 12];
+    c.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..10], &[0; 10]);
+    assert_eq!(&buf[10..], b"hi");
+
+    // The high-water mark, not the position, decides what was written.
+    c.set_position(0);
+    assert_eq!(c.into_written(), buf);
+}
+
 #[allow(dead_code)]
 fn const_cursor() {
     const CURSOR: Cursor<&[u8]> = Cursor::new(&[0]);
     const _: &&[u8] = CURSOR.get_ref();
     const _: u64 = CURSOR.position();
 }
+
+#[test]
+fn cursor_chunks() {
+    let mut c = Cursor::new([1u8, 2, 3, 4, 5, 6]);
+    assert_eq!(c.chunks(2).count(), 3);
+
+    c.set_position(1);
+    let chunks: [&[u8]; 2] = [&[2, 3, 4], &[5, 6]];
+    assert!(c.chunks(3).eq(chunks));
+    assert_eq!(c.chunks(3).next_back(), Some(&[5u8, 6][..]));
+    assert_eq!(c.position(), 1);
+
+    c.set_position(10);
+    assert_eq!(c.chunks(4).next(), None);
+}
+
+#[test]
+fn cursor_as_ref() {
+    fn len(data: impl AsRef<[u8]>) -> usize {
+        data.as_ref().len()
+    }
+
+    let mut c = Cursor::new(&b"abcdef"[..]);
+    let mut buf = [0u8; 2];
+    c.read_exact(&mut buf).unwrap();
+    assert_eq!(c.as_ref(), b"abcdef");
+    assert_eq!(len(&c), 6);
+    assert_eq!(c.consumed_slice(), b"ab");
+    assert_eq!(c.remaining_slice(), b"cdef");
+
+    c.set_position(100);
+    assert_eq!(c.consumed_slice(), b"abcdef");
+    assert_eq!(len(c), 6);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn read_all_from_start() {
+    use crate::ReadSeekExt;
+
+    let mut c = Cursor::new(Vec::new());
+    c.write_all(b"hello").unwrap();
+
+    let mut buf = vec![b'>'];
+    assert_eq!(c.read_all_from_start(&mut buf).unwrap(), 5);
+    assert_eq!(buf, b">hello");
+    assert_eq!(c.position(), 5);
+
+    // Writes after a snapshot are picked up by the next one.
+    c.write_all(b", world").unwrap();
+    buf.clear();
+    assert_eq!(c.read_all_from_start(&mut buf).unwrap(), 12);
+    assert_eq!(buf, b"hello, world");
+}
+
+#[test]
+fn try_into_array() {
+    let mut c = Cursor::new([1u8, 2, 3]);
+    c.set_position(2);
+    let c = c.try_into_array::<2>().unwrap_err();
+    assert_eq!(c.position(), 2);
+    assert_eq!(c.try_into_array::<3>().unwrap(), [1, 2, 3]);
+
+    assert_eq!(Cursor::new(&b""[..]).try_into_array::<0>().unwrap(), []);
+
+    #[cfg(feature = "alloc")]
+    {
+        let c = Cursor::new(vec![7u8; 4]);
+        let c = c.try_into_array::<5>().unwrap_err();
+        assert_eq!(c.try_into_array::<4>().unwrap(), [7; 4]);
+    }
+}
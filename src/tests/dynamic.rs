@@ -0,0 +1,113 @@
+use crate::{Cursor, DynReader, ErrorKind, Read, Seek, SeekFrom, Write};
+
+#[test]
+fn dyn_reader_seek() {
+    let mut cursor = Cursor::new(b"abcdef");
+    let mut r = DynReader::seekable(&mut cursor);
+    assert!(r.is_seekable());
+    assert!(r.supports_end_seek());
+    assert_eq!(r.try_seek(SeekFrom::End(-2)).unwrap(), 4);
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ef");
+    assert_eq!(cursor.position(), 6);
+
+    let mut slice = &b"abcdef"[..];
+    let mut r = DynReader::new(&mut slice);
+    assert!(!r.is_seekable());
+    assert!(!r.supports_end_seek());
+    assert_eq!(
+        r.seek(SeekFrom::Start(0)).unwrap_err().kind(),
+        ErrorKind::Unsupported
+    );
+    assert_eq!(r.size_hint(), (6, Some(6)));
+    r.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ab");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dyn_reader_buffered() {
+    use crate::{BufRead, BufReader};
+
+    let mut cursor = Cursor::new(b"abcdef");
+    let mut r = BufReader::with_capacity(2, DynReader::seekable(&mut cursor));
+    assert_eq!(r.fill_buf().unwrap(), b"ab");
+    assert_eq!(r.seek(SeekFrom::Current(1)).unwrap(), 1);
+    assert_eq!(r.fill_buf().unwrap(), b"bc");
+
+    let mut slice = &b"abcdef"[..];
+    let mut r = BufReader::with_capacity(2, DynReader::new(&mut slice));
+    assert_eq!(
+        r.seek(SeekFrom::Current(1)).unwrap_err().kind(),
+        ErrorKind::Unsupported
+    );
+}
+
+#[test]
+fn dyn_read_by_reference() {
+    let mut a = &b"abc"[..];
+    let r: &mut dyn Read = &mut a;
+    let mut limited = r.take(2);
+    let mut buf = [0u8; 4];
+    assert_eq!(limited.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"ab");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dyn_read_boxed() {
+    use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+    let mut readers: Vec<Box<dyn Read>> = vec![
+        Box::new(&b"ab"[..]),
+        Box::new(Cursor::new(b"cd")),
+        Box::new((&b"e"[..]).chain(&b"f"[..])),
+    ];
+    let mut out = Vec::new();
+    for r in readers.iter_mut() {
+        r.read_to_end(&mut out).unwrap();
+    }
+    assert_eq!(out, b"abcdef");
+
+    let mut r: Box<dyn Read> = Box::new(&b"text"[..]);
+    let mut s = String::new();
+    r.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "text");
+}
+
+#[test]
+fn dyn_write_by_reference() {
+    let mut buf = [0u8; 8];
+    let mut cursor = Cursor::new(&mut buf[..]);
+    {
+        fn emit<W: Write>(mut w: W) {
+            write!(w, "{}-{}", 1, 2).unwrap();
+            w.write_all(b"!").unwrap();
+            w.flush().unwrap();
+        }
+        let w: &mut dyn Write = &mut cursor;
+        emit(w);
+    }
+    assert_eq!(cursor.position(), 4);
+    assert_eq!(&buf[..4], b"1-2!");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dyn_write_boxed() {
+    use alloc::{boxed::Box, vec::Vec};
+
+    let mut w: Box<dyn Write> = Box::new(Vec::new());
+    w.write_all(b"abc").unwrap();
+    write!(w, "{}", 42).unwrap();
+    assert_eq!(w.write(b"d").unwrap(), 1);
+    w.flush().unwrap();
+
+    let mut out = Vec::new();
+    {
+        let mut w: Box<dyn Write + '_> = Box::new(&mut out);
+        w.write_all(b"xyz").unwrap();
+    }
+    assert_eq!(out, b"xyz");
+}
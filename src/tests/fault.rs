@@ -0,0 +1,69 @@
+use crate::{Error, ErrorKind, FaultyReader, FaultyWriter, Read, Write, WriteFault};
+
+#[test]
+fn faulty_reader() {
+    let schedule = [
+        (0, Error::from(ErrorKind::Interrupted)),
+        (3, Error::from(ErrorKind::Other)),
+        (3, Error::from(ErrorKind::UnexpectedEof)),
+    ];
+    let mut r = FaultyReader::new(&b"abcdef"[..], schedule);
+    let mut buf = [0u8; 8];
+
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::Interrupted);
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(r.position(), 3);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::Other);
+    assert_eq!(
+        r.read(&mut buf).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert!(!r.has_pending_faults());
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"def");
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn faulty_reader_past_end() {
+    // A fault scheduled beyond the end of the stream is never reached.
+    let mut r = FaultyReader::new(&b"ab"[..], [(5, Error::from(ErrorKind::Other))]);
+    let mut buf = [0u8; 8];
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+    assert!(r.has_pending_faults());
+}
+
+#[test]
+fn faulty_writer() {
+    let schedule = [
+        (0, WriteFault::Short(10)),
+        (2, WriteFault::Short(1)),
+        (2, WriteFault::Error(Error::from(ErrorKind::Other))),
+        (5, WriteFault::Short(0)),
+    ];
+    let mut out = [0u8; 8];
+    let mut w = FaultyWriter::new(&mut out[..], schedule);
+
+    // The short write is still cut at the next scheduled offset.
+    assert_eq!(w.write(b"abcdef").unwrap(), 2);
+    assert_eq!(w.write(b"cdef").unwrap(), 1);
+    assert_eq!(w.write(b"def").unwrap_err().kind(), ErrorKind::Other);
+    assert_eq!(w.write(b"def").unwrap(), 2);
+    assert_eq!(w.write(b"f").unwrap(), 0);
+    assert!(!w.has_pending_faults());
+    assert_eq!(w.write(b"f").unwrap(), 1);
+    assert_eq!(w.position(), 6);
+    w.flush().unwrap();
+    assert_eq!(&out[..6], b"abcdef");
+}
+
+#[test]
+fn faulty_writer_write_all() {
+    let schedule = (1..4).map(|n| (n, WriteFault::Error(Error::from(ErrorKind::Interrupted))));
+    let mut out = [0u8; 8];
+    let mut w = FaultyWriter::new(&mut out[..], schedule);
+    w.write_all(b"hello").unwrap();
+    assert_eq!(w.position(), 5);
+    assert_eq!(&out[..5], b"hello");
+}
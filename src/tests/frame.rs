@@ -0,0 +1,147 @@
+use crate::{BufRead, ErrorKind, FramedReader, FramedWriter, Read, Result, TrimReader, Write};
+
+#[test]
+fn framed_write() -> Result<()> {
+    let mut out = [0u8; 12];
+    let mut w = FramedWriter::with_buffer(&mut out[..], [0u8; 4]);
+    assert_eq!(w.frame_size(), 4);
+
+    w.write_all(b"ab")?;
+    w.write_all(b"c")?;
+    assert_eq!(w.frame(), b"abc");
+    w.flush()?;
+    assert_eq!(w.get_ref().len(), 12);
+    w.finish_frame(b'.')?;
+
+    // An empty frame is all padding.
+    w.finish_frame(b'-')?;
+    w.write_all(b"wxyz")?;
+    w.finish_frame(b'.')?;
+    assert!(w.frame().is_empty());
+    assert_eq!(&out, b"abc.----wxyz");
+    Ok(())
+}
+
+#[test]
+fn framed_write_too_long() -> Result<()> {
+    let mut out = [0u8; 4];
+    let mut w = FramedWriter::with_buffer(&mut out[..], [0u8; 4]);
+    assert_eq!(w.write(b"abcdef")?, 4);
+    assert_eq!(w.write(b"ef").unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(w.write(b"")?, 0);
+
+    w.discard_frame();
+    w.write_all(b"ok")?;
+    w.finish_frame(0)?;
+    assert_eq!(&out, b"ok\0\0");
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn framed_write_empty_frame() {
+    let _ = FramedWriter::with_buffer(&mut [0u8; 4][..], [0u8; 0]);
+}
+
+#[test]
+fn framed_read() -> Result<()> {
+    let data = b"ab..----wxyz";
+    // Frames arrive split across short reads.
+    let inner = (&data[..3]).chain(&data[3..7]).chain(&data[7..]);
+    let mut r = FramedReader::with_buffer(inner, [0u8; 4]);
+    r.set_padding(Some(b'-'));
+
+    let mut buf = [0u8; 3];
+    assert_eq!(r.read(&mut buf)?, 3);
+    assert_eq!(&buf, b"ab.");
+    assert_eq!(r.fill_buf()?, b".");
+    r.consume(1);
+    // The all-padding frame is skipped.
+    assert_eq!(r.fill_buf()?, b"wxyz");
+    r.consume(4);
+    assert_eq!(r.read(&mut buf)?, 0);
+    Ok(())
+}
+
+#[test]
+fn framed_read_truncated() {
+    let mut r = FramedReader::with_buffer(&b"abcdef"[..], [0u8; 4]);
+    let mut buf = [0u8; 8];
+    assert_eq!(r.read(&mut buf).unwrap(), 4);
+    assert_eq!(
+        r.read(&mut buf).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn framed_round_trip() -> Result<()> {
+    use alloc::vec::Vec;
+
+    let mut w = FramedWriter::new(Vec::new(), 5);
+    for msg in [&b"one"[..], b"three", b""] {
+        w.write_all(msg)?;
+        w.finish_frame(0)?;
+    }
+    let framed = w.into_inner();
+    assert_eq!(framed.len(), 15);
+
+    let mut r = FramedReader::new(&framed[..], 5);
+    r.set_padding(Some(0));
+    let mut out = Vec::new();
+    r.read_to_end(&mut out)?;
+    assert_eq!(out, b"onethree");
+    Ok(())
+}
+
+#[test]
+fn trim_reader() -> Result<()> {
+    // Short reads of the underlying reader fill the tail bit by bit.
+    let inner = (&b"<<abcdef>>"[..]).chunked(3);
+    let mut r = TrimReader::with_buffer(inner, 2, [0u8; 2]);
+    let mut out = [0u8; 8];
+    let mut len = 0;
+    loop {
+        match r.read(&mut out[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    assert_eq!(&out[..len], b"abcdef");
+    assert_eq!(r.trailer(), b">>");
+
+    // Small reads leave the rest of the data held.
+    let mut r = TrimReader::with_buffer(&b"abcdefg"[..], 0, [0u8; 3]);
+    let mut one = [0u8; 1];
+    r.read_exact(&mut one)?;
+    assert_eq!(one, *b"a");
+    assert_eq!(r.trailer(), b"bcd");
+    let mut rest = [0u8; 3];
+    r.read_exact(&mut rest)?;
+    assert_eq!(&rest, b"bcd");
+    assert_eq!(r.read(&mut rest)?, 0);
+    assert_eq!(r.trailer(), b"efg");
+    Ok(())
+}
+
+#[test]
+fn trim_reader_short_stream() -> Result<()> {
+    // Ends within the head.
+    let mut r = TrimReader::with_buffer(&b"ab"[..], 3, [0u8; 2]);
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read(&mut buf)?, 0);
+    assert_eq!(r.trailer(), b"");
+
+    // Ends within the tail.
+    let mut r = TrimReader::with_buffer(&b"abc"[..], 1, [0u8; 4]);
+    assert_eq!(r.read(&mut buf)?, 0);
+    assert_eq!(r.trailer(), b"bc");
+
+    // Nothing withheld.
+    let mut r = TrimReader::with_buffer(&b"abc"[..], 1, [0u8; 0]);
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(&buf[..2], b"bc");
+    Ok(())
+}
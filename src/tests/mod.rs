@@ -6,13 +6,25 @@ use alloc::{
 };
 use core::{cmp, ops::Deref};
 
-use crate::{BufRead, Cursor, ErrorKind, IoSlice, IoSliceMut, Read, Result};
 #[cfg(feature = "alloc")]
-use crate::{BufReader, Seek, SeekFrom, Write};
+use crate::BufReader;
+use crate::{
+    BufRead, Cursor, ErrorKind, IoSlice, IoSliceMut, Read, ReadReady, Result, Seek, SeekFrom, Write,
+};
 
+#[cfg(feature = "base64")]
+mod base64;
+mod bits;
 #[cfg(feature = "alloc")]
 mod buffered;
+mod checksum;
+mod coalesce;
+mod codec;
 mod cursor;
+mod dynamic;
+mod fault;
+mod frame;
+mod slices;
 mod util;
 
 #[test]
@@ -51,6 +63,96 @@ fn split() {
     assert!(s.next().is_none());
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn records() {
+    let buf = Cursor::new(&b"abcdef"[..]);
+    let mut r = buf.records(3);
+    assert_eq!(r.next().unwrap().unwrap(), b"abc");
+    assert_eq!(r.next().unwrap().unwrap(), b"def");
+    assert!(r.next().is_none());
+
+    // Records spanning several internal buffer refills.
+    let buf = BufReader::with_capacity(2, &b"abcdefg"[..]);
+    let mut r = buf.records(3);
+    assert_eq!(r.next().unwrap().unwrap(), b"abc");
+    assert_eq!(r.next().unwrap().unwrap(), b"def");
+    assert_eq!(
+        r.next().unwrap().unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert!(r.next().is_none());
+
+    let buf = BufReader::with_capacity(2, &b"abcdefg"[..]);
+    let mut r = buf.records(3).allow_partial(true);
+    assert_eq!(r.next().unwrap().unwrap(), b"abc");
+    assert_eq!(r.next().unwrap().unwrap(), b"def");
+    assert_eq!(r.next().unwrap().unwrap(), b"g");
+    assert!(r.next().is_none());
+
+    assert!((&b""[..]).records(1).next().is_none());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn records_resume_after_error() {
+    let flaky = Flaky {
+        data: b"abcdef",
+        ready: false,
+    };
+    let mut r = BufReader::with_capacity(2, flaky).records(3);
+    let mut next = || r.next().map(|record| record.map_err(|e| e.kind()));
+
+    // Each interrupted record is resumed on the next call.
+    assert_eq!(next(), Some(Err(ErrorKind::WouldBlock)));
+    assert_eq!(next(), Some(Ok(b"abc".to_vec())));
+    assert_eq!(next(), Some(Err(ErrorKind::WouldBlock)));
+    assert_eq!(next(), Some(Ok(b"def".to_vec())));
+    assert_eq!(next(), Some(Err(ErrorKind::WouldBlock)));
+    assert_eq!(next(), None);
+}
+
+#[test]
+#[should_panic]
+#[cfg(feature = "alloc")]
+fn records_zero_size() {
+    let _ = (&b"abc"[..]).records(0);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn read_utf8_chunks() {
+    let text = "a\u{e9}\u{20ac}\u{1f600}z";
+    for cap in 1..8 {
+        let buf = BufReader::with_capacity(cap, text.as_bytes());
+        let mut joined = String::new();
+        for chunk in buf.read_utf8_chunks() {
+            joined.push_str(&chunk.unwrap());
+        }
+        assert_eq!(joined, text);
+    }
+
+    // The valid prefix comes first, then the error, then the rest.
+    let buf = BufReader::with_capacity(2, &b"ab\xFFcd"[..]);
+    let chunks: Vec<_> = buf.read_utf8_chunks().collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].as_ref().unwrap(), "ab");
+    assert_eq!(
+        chunks[1].as_ref().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(chunks[2].as_ref().unwrap(), "cd");
+
+    // A truncated character at the end of the stream.
+    let mut c = (&b"ab\xE2\x82"[..]).read_utf8_chunks();
+    assert_eq!(c.next().unwrap().unwrap(), "ab");
+    assert_eq!(
+        c.next().unwrap().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert!(c.next().is_none());
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn read_line() {
@@ -108,6 +210,58 @@ fn read_to_end() {
     assert_eq!(v, data);
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn read_to_end_with() {
+    use crate::{Doubling, GrowthStrategy, Linear};
+
+    // Records the calls made to the inner strategy.
+    struct Recording<S> {
+        inner: S,
+        calls: Vec<(usize, usize)>,
+    }
+
+    impl<S: GrowthStrategy> GrowthStrategy for Recording<S> {
+        fn next_reserve(&mut self, len: usize, last_read: usize) -> usize {
+            self.calls.push((len, last_read));
+            self.inner.next_reserve(len, last_read)
+        }
+    }
+
+    let data = [5u8; 100];
+    let mut strategy = Recording {
+        inner: Linear(40),
+        calls: Vec::new(),
+    };
+    let mut v = vec![1, 2];
+    v.shrink_to_fit();
+    assert_eq!(
+        (&data[..]).read_to_end_with(&mut v, &mut strategy).unwrap(),
+        100
+    );
+    assert_eq!(v.len(), 102);
+    assert_eq!(&v[..3], [1, 2, 5]);
+    assert_eq!(strategy.calls, [(2, 0), (42, 40), (82, 40)]);
+    assert!(v.capacity() >= 122);
+
+    let mut strategy = Recording {
+        inner: Doubling,
+        calls: Vec::new(),
+    };
+    let mut v = Vec::new();
+    (&data[..])
+        .chunked(10)
+        .read_to_end_with(&mut v, &mut strategy)
+        .unwrap();
+    assert_eq!(v, data);
+    assert_eq!(strategy.calls, [(0, 0), (32, 2), (64, 2)]);
+
+    // A strategy asking for nothing still makes progress.
+    let mut v = Vec::new();
+    (&data[..3]).read_to_end_with(&mut v, Linear(0)).unwrap();
+    assert_eq!(v, [5; 3]);
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn read_to_string() {
@@ -238,6 +392,51 @@ fn chain_bufread() {
     cmp_bufread(chain1, chain2, &testdata[..]);
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn chain_all_bufread() {
+    let testdata = b"ABCDEFGHIJKL";
+    let chain1 = crate::chain_all(vec![
+        &testdata[..3],
+        &testdata[3..3],
+        &testdata[3..9],
+        &testdata[9..],
+    ]);
+    let chain2 = (&testdata[..4])
+        .chain(&testdata[4..8])
+        .chain(&testdata[8..]);
+    cmp_bufread(chain1, chain2, &testdata[..]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn chain_all_read() {
+    let mut chain = crate::chain_all(vec![&b"ab"[..], b"", b"cde"]);
+    assert_eq!(chain.size_hint(), (5, Some(5)));
+
+    let mut buf = [0; 4];
+    assert_eq!(chain.read(&mut buf).unwrap(), 2);
+    assert_eq!(chain.read(&mut []).unwrap(), 0);
+    assert_eq!(chain.current(), 0);
+    assert_eq!(chain.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"cde");
+    assert_eq!(chain.current(), 2);
+    assert_eq!(chain.read(&mut buf).unwrap(), 0);
+    assert_eq!(chain.current(), 3);
+    assert_eq!(chain.size_hint(), (0, Some(0)));
+
+    let empty = crate::chain_all(Vec::<&[u8]>::new());
+    assert_eq!(empty.size_hint(), (0, Some(0)));
+
+    let readers: Vec<alloc::boxed::Box<dyn Read>> = vec![
+        alloc::boxed::Box::new(&b"xy"[..]),
+        alloc::boxed::Box::new(crate::repeat(b'z').take(2)),
+    ];
+    let mut s = String::new();
+    crate::chain_all(readers).read_to_string(&mut s).unwrap();
+    assert_eq!(s, "xyzz");
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn bufreader_size_hint() {
@@ -259,6 +458,396 @@ fn bufreader_size_hint() {
     assert_eq!(lower_bound, buffer_length - 1);
 }
 
+#[test]
+fn read_ready() -> Result<()> {
+    let mut r = &b"ab"[..];
+    assert!(r.read_ready()?);
+    r.read_exact(&mut [0; 2])?;
+    assert!(!r.read_ready()?);
+
+    assert!(!crate::empty().read_ready()?);
+    assert!(crate::repeat(0).read_ready()?);
+
+    let mut take = (&b"ab"[..]).take(0);
+    assert!(!take.read_ready()?);
+    take.set_limit(1);
+    assert!(take.read_ready()?);
+
+    // An exhausted first reader defers to the second.
+    let mut chain = (&b""[..]).chain(Cursor::new(&b"cd"[..]));
+    assert!(chain.read_ready()?);
+    let mut chain = (&b""[..]).chain(&b""[..]);
+    assert!(!chain.read_ready()?);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bufreader_read_ready() -> Result<()> {
+    let mut r = BufReader::with_capacity(2, &b"abc"[..]);
+    assert!(r.read_ready()?);
+    r.fill_buf()?;
+    r.get_mut().read_exact(&mut [0; 1])?;
+    assert!(r.read_ready()?);
+    r.consume(2);
+    assert!(!r.read_ready()?);
+
+    Ok(())
+}
+
+#[test]
+fn read_or_eof() {
+    let mut r = &b"abc"[..];
+    let mut buf = [0u8; 2];
+    assert_eq!(r.read_or_eof(&mut buf).unwrap(), Some(2));
+    assert_eq!(r.read_or_eof(&mut []).unwrap(), Some(0));
+    assert_eq!(r.read_or_eof(&mut buf).unwrap(), Some(1));
+    assert_eq!(r.read_or_eof(&mut buf).unwrap(), None);
+    assert_eq!(r.read_or_eof(&mut []).unwrap(), Some(0));
+}
+
+#[test]
+fn read_exact_or_default() {
+    let mut r = (&b"ab"[..]).chain(&b"cd"[..]);
+    let mut buf = [0u8; 3];
+    assert!(r.read_exact_or_default(&mut buf).unwrap());
+    assert_eq!(&buf, b"abc");
+    assert!(r.read_exact_or_default(&mut []).unwrap());
+
+    let mut buf = [0u8; 2];
+    assert_eq!(
+        r.read_exact_or_default(&mut buf).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert!(!r.read_exact_or_default(&mut buf).unwrap());
+    assert_eq!(&buf, b"d\0");
+    assert!(r.read_exact_or_default(&mut []).unwrap());
+
+    let schedule = [(0, crate::Error::from(ErrorKind::Interrupted))];
+    let mut r = crate::FaultyReader::new(&b"xy"[..], schedule);
+    assert!(r.read_exact_or_default(&mut buf).unwrap());
+    assert_eq!(&buf, b"xy");
+}
+
+#[test]
+fn read_full() {
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(crate::Error::from(ErrorKind::Interrupted));
+            }
+            let len = cmp::min(buf.len(), 1);
+            self.data.read(&mut buf[..len])
+        }
+    }
+
+    let mut r = Trickle {
+        data: b"abcde",
+        interrupt: false,
+    }
+    .read_full();
+    let mut buf = [0u8; 3];
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"abc");
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"de");
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+
+    // An error after some progress is deferred to the next read.
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _buf: &mut [u8]) -> Result<usize> {
+            Err(crate::Error::from(ErrorKind::Other))
+        }
+    }
+
+    let mut r = (&b"ab"[..]).chain(Broken).read_full();
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::Other);
+
+    // The error is kept even if the underlying reader only returns it once.
+    struct FailOnce<'a> {
+        data: &'a [u8],
+        failed: bool,
+    }
+
+    impl Read for FailOnce<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            if self.data.len() == 2 && !self.failed {
+                self.failed = true;
+                return Err(crate::Error::from(ErrorKind::InvalidData));
+            }
+            let len = cmp::min(buf.len(), 1);
+            self.data.read(&mut buf[..len])
+        }
+    }
+
+    let mut r = FailOnce {
+        data: b"abcd",
+        failed: false,
+    }
+    .read_full();
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"ab");
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"cd");
+}
+
+#[test]
+fn chunked_reader() -> Result<()> {
+    let mut r = (&b"abcdefg"[..]).chunked(3);
+    assert_eq!(r.max(), 3);
+    let mut buf = [0u8; 8];
+    assert_eq!(r.read(&mut buf)?, 3);
+    assert_eq!(r.read(&mut buf[..2])?, 2);
+    assert_eq!(r.fill_buf()?, b"fg");
+    assert_eq!(r.size_hint(), (2, Some(2)));
+
+    let mut r = (&b"abcdefg"[..]).chunked(2);
+    assert_eq!(r.fill_buf()?, b"ab");
+    r.consume(1);
+    assert_eq!(r.fill_buf()?, b"bc");
+    Ok(())
+}
+
+#[test]
+fn chunked_on_read_write_type() -> Result<()> {
+    // Cursor implements both Read and Write, so the trait has to be named.
+    let mut data = *b"abcd";
+    let mut buf = [0u8; 4];
+    assert_eq!(
+        Read::chunked(Cursor::new(&mut data[..]), 3).read(&mut buf)?,
+        3
+    );
+    assert_eq!(&buf, b"abc\0");
+    assert_eq!(
+        Write::chunked(Cursor::new(&mut buf[..]), 1).write(b"xy")?,
+        1
+    );
+    assert_eq!(&buf, b"xbc\0");
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn chunked_reader_zero() {
+    let _ = (&b""[..]).chunked(0);
+}
+
+#[test]
+fn chunked_writer() -> Result<()> {
+    use crate::Write;
+
+    let mut out = [0u8; 8];
+    let mut w = (&mut out[..]).chunked(3);
+    assert_eq!(w.write(b"abcd")?, 3);
+
+    // Whole buffers are passed on while they fit...
+    let bufs = [IoSlice::new(b"d"), IoSlice::new(b"e"), IoSlice::new(b"fg")];
+    assert_eq!(w.write_vectored(&bufs)?, 2);
+    // ...otherwise a prefix of the first one.
+    let bufs = [IoSlice::new(b""), IoSlice::new(b"fghij")];
+    assert_eq!(w.write_vectored(&bufs)?, 3);
+    assert_eq!(w.write_vectored(&[])?, 0);
+    w.flush()?;
+    assert_eq!(&out, b"abcdefgh");
+    Ok(())
+}
+
+#[test]
+fn prepend() -> Result<()> {
+    let mut r = (&b"def"[..]).prepend(b"abc");
+    assert_eq!(r.size_hint(), (6, Some(6)));
+    assert!(r.read_ready()?);
+
+    let mut buf = [0u8; 2];
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(r.prefix(), b"c");
+    assert_eq!(r.fill_buf()?, b"c");
+    r.consume(1);
+    assert_eq!(r.fill_buf()?, b"def");
+
+    // Reads never span the prefix and the reader.
+    let mut r = (&b"def"[..]).prepend(b"abc");
+    let mut buf = [0u8; 6];
+    assert_eq!(r.read(&mut buf)?, 3);
+    assert_eq!(r.read(&mut buf[3..])?, 3);
+    assert_eq!(&buf, b"abcdef");
+    Ok(())
+}
+
+#[test]
+fn resumable_reader() -> Result<()> {
+    use crate::ResumableReader;
+
+    // Segments are handed out one at a time; an empty segment is skipped and
+    // the end is reported once between the second and third.
+    let segments = [Some(&b""[..]), Some(b"cd"), None, Some(b"e")];
+    let mut calls = 0;
+    let mut r = ResumableReader::new(&b"ab"[..], || {
+        calls += 1;
+        Ok(segments.get(calls - 1).copied().flatten())
+    });
+
+    let mut buf = [0u8; 8];
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(r.read(&mut []).unwrap(), 0);
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(&buf[..2], b"cd");
+    assert_eq!(r.read(&mut buf)?, 0);
+    assert_eq!(r.read(&mut buf)?, 1);
+    assert_eq!(r.get_ref(), b"");
+    assert_eq!(r.read(&mut buf)?, 0);
+
+    let mut r = ResumableReader::new(&b""[..], || Err(ErrorKind::Other.into()));
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::Other);
+    Ok(())
+}
+
+#[test]
+fn write_fmt_buffered() {
+    // Records the length of each write.
+    struct Counting<'a> {
+        dst: &'a mut [u8],
+        writes: [usize; 4],
+        count: usize,
+    }
+
+    impl Write for Counting<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.writes[self.count] = buf.len();
+            self.count += 1;
+            self.dst.write(buf)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut out = [0u8; 512];
+    let mut w = Counting {
+        dst: &mut out,
+        writes: [0; 4],
+        count: 0,
+    };
+    w.write_fmt_buffered(format_args!("{}, {}{}", "a", 1, 'c'))
+        .unwrap();
+    assert_eq!(w.writes[..w.count], [5]);
+
+    // Output longer than the stack buffer, produced one fill at a time.
+    w.count = 0;
+    w.write_fmt_buffered(format_args!("{:>300}", "end"))
+        .unwrap();
+    #[cfg(feature = "alloc")]
+    assert_eq!(w.writes[..w.count], [300]);
+    #[cfg(not(feature = "alloc"))]
+    assert_eq!(w.writes[..w.count], [256, 44]);
+    assert_eq!(&out[..6], b"a, 1c ");
+    assert_eq!(&out[302..305], b"end");
+
+    // Errors from the writer are returned.
+    let mut short = [0u8; 2];
+    let err = short
+        .as_mut_slice()
+        .write_fmt_buffered(format_args!("{}", 123))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+}
+
+#[test]
+fn deadline_reader() {
+    use crate::DeadlineReader;
+
+    // Expires after two checks.
+    let mut checks = 0;
+    let mut r = DeadlineReader::new((&b"abcdef"[..]).chunked(2), || {
+        checks += 1;
+        checks > 2
+    });
+    let mut buf = [0u8; 6];
+    let err = r.read_exact(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TimedOut);
+    assert_eq!(&buf[..4], b"abcd");
+
+    // The underlying reader is not touched once expired.
+    assert_eq!(r.read(&mut buf).unwrap_err().kind(), ErrorKind::TimedOut);
+    let mut rest = [0u8; 2];
+    r.get_mut().read_exact(&mut rest).unwrap();
+    assert_eq!(&rest, b"ef");
+}
+
+#[test]
+fn merge_reader() {
+    use crate::MergeReader;
+
+    // Equal bytes come from the first reader first; buffers split runs.
+    let first = (&b"aab"[..]).chain(&b"dx"[..]);
+    let mut r = MergeReader::new(first, &b"abcz"[..], |a: u8, b: u8| a.cmp(&b));
+    let mut out = [0u8; 9];
+    r.read_exact(&mut out).unwrap();
+    assert_eq!(&out, b"aaabbcdxz");
+    assert_eq!(r.read(&mut out).unwrap(), 0);
+
+    // A custom order, with one side empty.
+    let mut r = MergeReader::new(&b"cba"[..], &b""[..], |a: u8, b: u8| b.cmp(&a));
+    let mut small = [0u8; 2];
+    assert_eq!(r.read(&mut small).unwrap(), 2);
+    assert_eq!(&small, b"cb");
+
+    let mut r = MergeReader::new(&b"ca"[..], &b"db"[..], |a: u8, b: u8| b.cmp(&a));
+    let mut out = [0u8; 4];
+    r.read_exact(&mut out).unwrap();
+    assert_eq!(&out, b"dcba");
+}
+
+#[test]
+#[should_panic(expected = "cannot consume 4 bytes, only 3 are available")]
+fn slice_consume_too_much() {
+    let mut buf = &b"abc"[..];
+    buf.consume(4);
+}
+
+#[test]
+fn inspect() -> Result<()> {
+    let mut seen = [0u8; 8];
+    let mut calls = 0;
+    let mut total = 0;
+    {
+        let mut r = (&b"abcdef"[..]).inspect(|bytes| {
+            seen[total..total + bytes.len()].copy_from_slice(bytes);
+            total += bytes.len();
+            calls += 1;
+        });
+
+        let mut buf = [0; 4];
+        assert_eq!(r.read(&mut buf)?, 4);
+        assert_eq!(&buf, b"abcd");
+        assert_eq!(r.size_hint(), (2, Some(2)));
+
+        let (mut a, mut b) = ([0; 1], [0; 4]);
+        let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+        assert_eq!(r.read_vectored(&mut bufs)?, 2);
+        assert_eq!(r.read(&mut buf)?, 0);
+    }
+
+    assert_eq!(&seen[..total], b"abcdef");
+    // One call for each plain read and one per filled slice.
+    assert_eq!(calls, 4);
+
+    Ok(())
+}
+
 #[test]
 fn empty_size_hint() {
     let size_hint = crate::empty().bytes().size_hint();
@@ -290,6 +879,72 @@ fn chain_empty_size_hint() {
     assert_eq!(size_hint, (0, Some(0)));
 }
 
+#[test]
+fn cursor_size_hint() {
+    let mut c = Cursor::new([1, 2, 3]);
+    assert_eq!(c.size_hint(), (3, Some(3)));
+    c.set_position(1);
+    assert_eq!((&mut c).bytes().size_hint(), (2, Some(2)));
+    c.set_position(10);
+    assert_eq!(c.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn chain_repeat_size_hint() {
+    let chain = crate::repeat(0).chain(crate::repeat(1));
+    assert_eq!(chain.size_hint(), (usize::MAX, None));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn read_to_end_presizes_from_size_hint() {
+    // Records the size of the buffer passed to the first read.
+    struct FirstRead<R> {
+        inner: R,
+        first: Option<usize>,
+    }
+
+    impl<R: Read> Read for FirstRead<R> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.first.get_or_insert(buf.len());
+            self.inner.read(buf)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.inner.size_hint()
+        }
+    }
+
+    let data = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+    let mut r = FirstRead {
+        inner: Cursor::new(&data[..]),
+        first: None,
+    };
+    let mut v = Vec::new();
+    assert_eq!(r.read_to_end(&mut v).unwrap(), data.len());
+    assert_eq!(v, data);
+    // The whole stream fits in the space reserved up front.
+    assert!(r.first.unwrap() >= data.len());
+
+    // A reader claiming an enormous length must not be trusted blindly.
+    struct Liar<'a>(&'a [u8]);
+
+    impl Read for Liar<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.0.read(buf)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, None)
+        }
+    }
+
+    let mut v = Vec::new();
+    assert_eq!(Liar(b"abc").read_to_end(&mut v).unwrap(), 3);
+    assert_eq!(v, b"abc");
+    assert!(v.capacity() <= 1024 * 1024);
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn chain_size_hint() {
@@ -337,6 +992,108 @@ fn seek_len() -> Result<()> {
     Ok(())
 }
 
+// A seekable stream of fixed length whose seeks can be made to fail.
+struct FailingSeeker {
+    pos: u64,
+    len: u64,
+    fail_start: bool,
+    fail_end: bool,
+}
+
+impl Seek for FailingSeeker {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        match pos {
+            SeekFrom::Start(_) if self.fail_start => return Err(ErrorKind::Other.into()),
+            SeekFrom::End(_) if self.fail_end => return Err(ErrorKind::Other.into()),
+            SeekFrom::Start(n) => self.pos = n,
+            SeekFrom::End(n) => self.pos = (self.len as i64 + n) as u64,
+            SeekFrom::Current(n) => self.pos = (self.pos as i64 + n) as u64,
+        }
+        Ok(self.pos)
+    }
+}
+
+impl Read for FailingSeeker {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // Each byte holds the low bits of its own position.
+        let n = cmp::min(buf.len() as u64, self.len.saturating_sub(self.pos)) as usize;
+        for (i, b) in buf[..n].iter_mut().enumerate() {
+            *b = (self.pos + i as u64) as u8;
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+#[test]
+fn seek_len_checked() {
+    let mut s = FailingSeeker {
+        pos: 3,
+        len: 10,
+        fail_start: false,
+        fail_end: false,
+    };
+    assert_eq!(s.stream_len_checked().unwrap(), 10);
+    assert_eq!(s.pos, 3);
+
+    // Restoring the position fails: the measured length is reported.
+    s.fail_start = true;
+    let err = s.stream_len_checked().unwrap_err();
+    assert_eq!(err.measured_len(), Some(10));
+    assert_eq!(err.error().kind(), ErrorKind::Other);
+
+    // Already at the end: no restoring seek is needed.
+    assert_eq!(s.stream_len_checked().unwrap(), 10);
+
+    // Measuring fails: no length is reported.
+    s.fail_end = true;
+    let err = s.stream_len_checked().unwrap_err();
+    assert_eq!(err.measured_len(), None);
+    assert_eq!(err.into_error().kind(), ErrorKind::Other);
+
+    let mut c = Cursor::new(&b"hello"[..]);
+    c.set_position(2);
+    assert_eq!(c.stream_len_checked().unwrap(), 5);
+    assert_eq!(c.position(), 2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bufreader_seek_len_keeps_buffer() -> Result<()> {
+    let mut r = BufReader::with_capacity(4, Cursor::new(&b"0123456789"[..]));
+    let mut buf = [0; 1];
+    r.read_exact(&mut buf)?;
+    assert_eq!(r.buffer(), b"123");
+
+    assert_eq!(r.stream_len()?, 10);
+    assert_eq!(r.buffer(), b"123");
+    assert_eq!(r.stream_len_checked().unwrap(), 10);
+    assert_eq!(r.buffer(), b"123");
+    assert_eq!(r.stream_position()?, 1);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bufreader_seek_len_failed_restore_discards_buffer() {
+    let inner = FailingSeeker {
+        pos: 0,
+        len: 10,
+        fail_start: true,
+        fail_end: false,
+    };
+    let mut r = BufReader::with_capacity(4, inner);
+    let mut buf = [0; 1];
+    r.read_exact(&mut buf).unwrap();
+    assert_eq!(r.buffer(), &[1, 2, 3]);
+
+    // The inner reader is left at the end, so the buffer must not be reused.
+    assert_eq!(r.stream_len().unwrap_err().kind(), ErrorKind::Other);
+    assert!(r.buffer().is_empty());
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn seek_position() -> Result<()> {
@@ -370,10 +1127,12 @@ fn seek_position() -> Result<()> {
 
 // A simple example reader which uses the default implementation of
 // read_to_end.
+#[cfg(feature = "alloc")]
 struct ExampleSliceReader<'a> {
     slice: &'a [u8],
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Read for ExampleSliceReader<'a> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         let len = cmp::min(self.slice.len(), buf.len());
@@ -425,6 +1184,38 @@ fn io_slice_mut_advance_slices() {
     assert_eq!(bufs[0].deref(), [3; 6].as_ref());
 }
 
+#[test]
+fn io_slice_from_arrays() {
+    let mut a = [1u8; 2];
+    let mut b = [2u8; 3];
+    let mut bufs = IoSliceMut::from_arrays([&mut a, &mut b[..1], &mut []]);
+    assert_eq!(IoSliceMut::total_len(&bufs), 3);
+    bufs[1][0] = 7;
+    assert_eq!(b, [7, 2, 2]);
+
+    let bufs = IoSlice::from_arrays([&a, &b]);
+    assert_eq!(IoSlice::total_len(&bufs), 5);
+    assert_eq!(IoSlice::total_len(&bufs[1..]), 3);
+    assert_eq!(IoSlice::total_len(&[]), 0);
+}
+
+#[test]
+fn io_slice_iter_bytes() {
+    let mut a = [1u8, 2];
+    let mut b = [3u8];
+    let mut bufs = IoSliceMut::from_arrays([&mut [], &mut a, &mut [], &mut b]);
+    assert!(IoSliceMut::iter_bytes(&bufs).eq([1, 2, 3]));
+    bufs[1][0] = 9;
+    assert!(IoSliceMut::iter_bytes(&bufs[2..]).eq([3]));
+    assert!(IoSliceMut::iter_bytes(&bufs).eq([9, 2, 3]));
+
+    let bufs = IoSlice::from_arrays([b"ab", b"", b"c"]);
+    let mut iter = IoSlice::iter_bytes(&bufs);
+    assert_eq!(iter.next(), Some(b'a'));
+    assert!(iter.eq(*b"bc"));
+    assert_eq!(IoSlice::iter_bytes(&[]).count(), 0);
+}
+
 #[test]
 fn io_slice_mut_advance_slices_empty_slice() {
     let mut empty_bufs = &mut [][..];
@@ -442,6 +1233,34 @@ fn io_slice_mut_advance_slices_beyond_total_length() {
     assert!(bufs.is_empty());
 }
 
+#[test]
+fn io_slice_accessors() {
+    let data = [1, 2, 3];
+    let buf = IoSlice::from(&data);
+    assert_eq!(buf.len(), 3);
+    assert!(!buf.is_empty());
+    assert_eq!(buf.as_slice(), &[1, 2, 3]);
+
+    let buf = IoSlice::from(&data[..0]);
+    assert_eq!(buf.len(), 0);
+    assert!(buf.is_empty());
+    assert_eq!(buf.as_slice(), &[]);
+}
+
+#[test]
+fn io_slice_mut_accessors() {
+    let mut data = [1, 2, 3];
+    let mut buf = IoSliceMut::from(&mut data);
+    assert_eq!(buf.len(), 3);
+    assert!(!buf.is_empty());
+    buf.as_mut_slice()[1] = 5;
+    assert_eq!(buf.as_slice(), &[1, 5, 3]);
+
+    let buf = IoSliceMut::from(&mut data[..0]);
+    assert!(buf.is_empty());
+    assert_eq!(data, [1, 5, 3]);
+}
+
 #[test]
 fn io_slice_advance_slices() {
     let buf1 = [1; 8];
@@ -598,8 +1417,954 @@ fn test_write_all_vectored() {
     for (n_bufs, per_call) in writer_configs.iter().copied() {
         for (mut input, wanted) in tests.clone().into_iter() {
             let mut writer = test_writer(n_bufs, per_call);
-            assert!(writer.write_all_vectored(&mut *input).is_ok());
-            assert_eq!(&*writer.written, &*wanted);
+            assert!(writer.write_all_vectored(&mut input).is_ok());
+            assert_eq!(&*writer.written, wanted);
+        }
+    }
+}
+
+#[test]
+fn write_padding() -> Result<()> {
+    use crate::Write;
+
+    let mut buf = [0u8; 160];
+    let mut w = &mut buf[..];
+    assert_eq!(w.write_padding(0, 8, 1)?, 0);
+    assert_eq!(w.write_padding(3, 0, 1)?, 0);
+    assert_eq!(w.write_padding(3, 1, 1)?, 0);
+    assert_eq!(w.write_padding(3, 8, 1)?, 5);
+    // More padding than one internal chunk.
+    assert_eq!(w.write_padding(1, 151, 2)?, 150);
+    assert_eq!(w.len(), 5);
+    assert_eq!(&buf[..5], [1; 5]);
+    assert!(buf[5..155].iter().all(|&b| b == 2));
+
+    let mut short = [0u8; 2];
+    let err = (&mut short[..])
+        .write_padding(0x10001, 0x10000, 0)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+
+    Ok(())
+}
+
+#[test]
+fn write_iter() -> Result<()> {
+    use crate::Write;
+
+    let mut buf = [0u8; 600];
+    let mut w = &mut buf[..];
+    assert_eq!(w.write_iter(core::iter::empty())?, 0);
+    assert_eq!(w.write_iter((0..=255).chain(0..=255).chain(0..10))?, 522);
+    assert_eq!(w.len(), 78);
+    assert!(buf[..512].iter().enumerate().all(|(i, &b)| b == i as u8));
+    assert_eq!(&buf[512..522], [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let mut short = [0u8; 300];
+    let err = (&mut short[..]).write_iter([7; 301]);
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::WriteZero);
+
+    Ok(())
+}
+
+/// Alternates between reporting `WouldBlock` and reading from the slice.
+struct Flaky<'a> {
+    data: &'a [u8],
+    ready: bool,
+}
+
+impl Read for Flaky<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.ready = !self.ready;
+        if self.ready {
+            self.data.read(buf)
+        } else {
+            Err(crate::Error::would_block())
+        }
+    }
+}
+
+#[test]
+fn take_passes_would_block() {
+    let mut take = Flaky {
+        data: b"abcd",
+        ready: true,
+    }
+    .take(3);
+
+    let mut buf = [0; 2];
+    let err = take.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    assert_eq!(take.limit(), 3);
+
+    assert_eq!(take.read(&mut buf).unwrap(), 2);
+    assert_eq!(take.limit(), 1);
+    assert_eq!(
+        take.read(&mut buf).unwrap_err().kind(),
+        ErrorKind::WouldBlock
+    );
+    assert_eq!(take.limit(), 1);
+    assert_eq!(take.read(&mut buf).unwrap(), 1);
+    assert_eq!(take.limit(), 0);
+}
+
+#[test]
+fn chain_passes_would_block() {
+    let first = Flaky {
+        data: b"ab",
+        ready: true,
+    };
+    let mut chain = first.chain(&b"cd"[..]);
+
+    let mut buf = [0; 4];
+    let err = chain.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    assert_eq!(chain.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"ab");
+
+    // The first reader is not treated as exhausted until it reports EOF.
+    assert!(chain.read(&mut buf).is_err());
+    assert_eq!(chain.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"cd");
+}
+
+#[test]
+fn vectored_hints_are_forwarded() {
+    let flaky = Flaky {
+        data: b"",
+        ready: false,
+    };
+    assert!((&b""[..]).take(1).is_read_vectored());
+    assert!(!flaky.take(1).is_read_vectored());
+
+    let flaky = Flaky {
+        data: b"",
+        ready: false,
+    };
+    assert!((&b""[..]).chain(&b""[..]).is_read_vectored());
+    assert!(!(&b""[..]).chain(flaky).is_read_vectored());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn vectored_hints_of_buffered_types() {
+    let flaky = Flaky {
+        data: b"",
+        ready: false,
+    };
+    assert!(BufReader::new(&b""[..]).is_read_vectored());
+    assert!(!BufReader::new(flaky).is_read_vectored());
+
+    // Vectored writes are coalesced into the buffer, which is always cheap.
+    assert!(crate::BufWriter::new(Vec::new()).is_write_vectored());
+}
+
+#[test]
+fn take_read_vectored() {
+    let mut a = [0u8; 2];
+    let mut b = [0u8; 2];
+
+    // Within the limit, every buffer is filled in one call.
+    let mut r = (&b"abcdef"[..]).take(4);
+    let n = r
+        .read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])
+        .unwrap();
+    assert_eq!(n, 4);
+    assert_eq!((&a, &b), (b"ab", b"cd"));
+    assert_eq!(r.limit(), 0);
+
+    // Otherwise, only the first buffer is used.
+    let mut r = (&b"abcdef"[..]).take(3);
+    let n = r
+        .read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])
+        .unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(r.limit(), 1);
+}
+
+#[test]
+fn option_read_write() -> Result<()> {
+    use crate::Write;
+
+    let mut buf = [0; 4];
+    let mut absent: Option<&[u8]> = None;
+    assert_eq!(absent.read(&mut buf)?, 0);
+    assert_eq!(absent.size_hint(), (0, Some(0)));
+    assert!(!absent.read_ready()?);
+    assert!(absent.fill_buf()?.is_empty());
+
+    let mut present = Some(&b"ab"[..]);
+    assert!(present.read_ready()?);
+    assert_eq!(present.fill_buf()?, b"ab");
+    present.consume(1);
+    assert_eq!(present.read(&mut buf)?, 1);
+    assert_eq!(buf[0], b'b');
+
+    let mut sink: Option<&mut [u8]> = None;
+    assert_eq!(sink.write(b"abc")?, 3);
+    assert_eq!(
+        sink.write_vectored(&[IoSlice::new(b"ab"), IoSlice::new(b"c")])?,
+        3
+    );
+    sink.flush()?;
+
+    let mut out = [0; 2];
+    let mut present = Some(&mut out[..]);
+    present.write_all(b"xy")?;
+    assert!(present.write_all(b"z").is_err());
+    assert_eq!(&out, b"xy");
+
+    Ok(())
+}
+
+#[test]
+fn tuple_write() -> Result<()> {
+    use crate::Write;
+
+    let (mut a, mut b) = ([0u8; 8], [0u8; 8]);
+    {
+        let mut both = (&mut a[..], &mut b[..]);
+        assert_eq!(both.write(b"abc")?, 3);
+        write!(both, "{}", 12345)?;
+        both.flush()?;
+    }
+    assert_eq!(a, b);
+    assert_eq!(&a, b"abc12345");
+
+    // The first failure is reported.
+    let (mut a, mut b) = ([0u8; 4], [0u8; 2]);
+    let mut both = (&mut a[..], &mut b[..]);
+    let err = both.write(b"abc").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(both.0.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn split_writer() -> Result<()> {
+    use crate::{SplitWriter, Write};
+
+    let (mut head, mut body) = ([0u8; 4], [0u8; 8]);
+    let mut w = SplitWriter::new(&mut head[..], 4, &mut body[..]);
+    assert_eq!(w.write(b"ab")?, 2);
+    // Straddles the boundary.
+    assert_eq!(w.write(b"cdef")?, 4);
+    assert_eq!(w.write(b"gh")?, 2);
+    assert_eq!(w.position(), 8);
+    let (head_left, body_left) = w.into_inner();
+    assert_eq!((head_left.len(), body_left.len()), (0, 4));
+    assert_eq!(&head, b"abcd");
+    assert_eq!(&body[..4], b"efgh");
+
+    // A failure past the boundary does not lose the bytes already written.
+    let (mut head, mut body) = ([0u8; 2], [0u8; 0]);
+    let mut w = SplitWriter::new(&mut head[..], 2, &mut body[..]);
+    assert_eq!(w.write(b"abc")?, 2);
+    assert_eq!(w.write_all(b"c").unwrap_err().kind(), ErrorKind::WriteZero);
+    assert_eq!(w.position(), 2);
+
+    // A zero boundary sends everything to the second writer.
+    let (mut head, mut body) = ([0u8; 2], [0u8; 2]);
+    let mut w = SplitWriter::new(&mut head[..], 0, &mut body[..]);
+    assert_eq!(w.boundary(), 0);
+    w.write_all(b"xy")?;
+    assert_eq!(&body, b"xy");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn error_downcast() {
+    use crate::{Error, ErrorTrait};
+    use core::fmt;
+
+    #[derive(Debug, PartialEq)]
+    struct Custom(u8);
+
+    impl fmt::Display for Custom {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "custom {}", self.0)
+        }
+    }
+
+    impl ErrorTrait for Custom {}
+
+    let mut err = Error::new(ErrorKind::Other, Custom(1));
+    assert_eq!(err.downcast_ref::<Custom>(), Some(&Custom(1)));
+    err = err.downcast::<Error>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Other);
+    assert_eq!(err.downcast::<Custom>().unwrap(), Custom(1));
+
+    let err = Error::new_const(ErrorKind::Other, &"constant");
+    assert!(err.downcast_ref::<Custom>().is_none());
+    let err = err.downcast::<Custom>().unwrap_err();
+    assert_eq!(err.to_string(), "constant");
+}
+
+#[test]
+fn eof_to_none() {
+    use crate::{Error, IoResultExt};
+
+    assert_eq!(Ok::<u8, Error>(7).eof_to_none().unwrap(), Some(7));
+    let eof: Result<u8> = Err(ErrorKind::UnexpectedEof.into());
+    assert_eq!(eof.eof_to_none().unwrap(), None);
+    let other: Result<u8> = Err(ErrorKind::InvalidData.into());
+    assert_eq!(
+        other.eof_to_none().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    let mut r = &[1u8, 2, 3][..];
+    let mut buf = [0u8; 2];
+    assert_eq!(r.read_exact(&mut buf).eof_to_none().unwrap(), Some(()));
+    assert_eq!(r.read_exact(&mut buf).eof_to_none().unwrap(), None);
+}
+
+#[test]
+fn fmt_error_conversion() {
+    use core::fmt;
+
+    struct Broken;
+
+    impl fmt::Display for Broken {
+        fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    fn glue() -> Result<()> {
+        Err(fmt::Error)?;
+        Ok(())
+    }
+
+    let mut buf = [0u8; 8];
+    let err = crate::Write::write_fmt(&mut &mut buf[..], format_args!("{}", Broken)).unwrap_err();
+    let converted = glue().unwrap_err();
+    assert_eq!(err.kind(), converted.kind());
+    assert_eq!(converted.kind(), ErrorKind::Uncategorized);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn vec_deque_read_write() -> Result<()> {
+    use alloc::collections::VecDeque;
+
+    // Make the contents wrap around the end of the ring buffer.
+    let mut deque = VecDeque::with_capacity(8);
+    deque.extend([0u8; 6]);
+    deque.drain(..6);
+    deque.write_all(b"abc")?;
+    deque.write_vectored(&[IoSlice::new(b"de"), IoSlice::new(b"fgh")])?;
+    assert_eq!(deque.size_hint(), (8, Some(8)));
+    assert!(deque.read_ready()?);
+
+    let front = deque.fill_buf()?.len();
+    assert!(front > 0 && front <= 8);
+    deque.consume(1);
+
+    let mut buf = [0u8; 5];
+    deque.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"bcdef");
+    assert_eq!(
+        deque.read_exact(&mut buf).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert_eq!(deque.read(&mut buf[..1])?, 1);
+    assert_eq!(buf[0], b'g');
+
+    let mut rest = Vec::new();
+    deque.read_to_end(&mut rest)?;
+    assert_eq!(rest, b"h");
+    assert_eq!(deque.read(&mut buf)?, 0);
+    assert!(!deque.read_ready()?);
+    Ok(())
+}
+
+#[test]
+fn write_length_prefixed() {
+    let mut out = [0u8; 8];
+    let mut w = &mut out[..];
+    w.write_length_prefixed::<1>(b"ab").unwrap();
+    w.write_length_prefixed::<3>(b"").unwrap();
+    assert_eq!(w.len(), 2);
+    assert_eq!(&out[..6], b"\x02ab\x00\x00\x00");
+
+    let long = [0u8; 256];
+    let mut out = [0u8; 8];
+    let mut w = &mut out[..];
+    assert_eq!(
+        w.write_length_prefixed::<1>(&long).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    assert_eq!(w.len(), 8);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn read_length_prefixed() {
+    let mut out = Vec::new();
+    out.write_length_prefixed::<4>(b"hello").unwrap();
+    out.write_length_prefixed::<16>(b"wide").unwrap();
+    assert_eq!(out.len(), 4 + 5 + 16 + 4);
+
+    let mut r = &out[..];
+    let mut msg = b"> ".to_vec();
+    assert_eq!(r.read_length_prefixed::<4>(&mut msg, 5).unwrap(), 5);
+    assert_eq!(msg, b"> hello");
+    assert_eq!(r.read_length_prefixed::<16>(&mut msg, 5).unwrap(), 4);
+    assert_eq!(msg, b"> hellowide");
+
+    // Oversized and truncated messages leave the buffer untouched.
+    let mut msg = Vec::new();
+    let err = (&b"\x00\x09abc"[..])
+        .read_length_prefixed::<2>(&mut msg, 8)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = (&b"\x00\x09abc"[..])
+        .read_length_prefixed::<2>(&mut msg, 9)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert!(msg.is_empty());
+
+    // A length which cannot be a `usize` is rejected.
+    let err = (&[1u8; 17][..])
+        .read_length_prefixed::<17>(&mut msg, usize::MAX)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn read_line_limited() {
+    // Lines are split across the reader's buffer boundaries.
+    let mut r = BufReader::with_capacity(3, &b"ab\nabcd\nabc\nxyz"[..]);
+    let mut line = String::new();
+    assert_eq!(r.read_line_limited(&mut line, 4).unwrap(), 3);
+    assert_eq!(line, "ab\n");
+
+    line.clear();
+    let err = r.read_line_limited(&mut line, 4).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(line, "");
+    // The rest of the long line is still in the stream.
+    assert_eq!(r.read_line_limited(&mut line, 4).unwrap(), 1);
+    assert_eq!(line, "\n");
+
+    line.clear();
+    assert_eq!(r.read_line_limited(&mut line, 4).unwrap(), 4);
+    assert_eq!(line, "abc\n");
+    line.clear();
+    assert_eq!(r.read_line_limited(&mut line, 4).unwrap(), 3);
+    assert_eq!(line, "xyz");
+    assert_eq!(r.read_line_limited(&mut line, 4).unwrap(), 0);
+
+    let mut one = &b"x"[..];
+    assert_eq!(
+        one.read_line_limited(&mut line, 0).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    // A last line without a newline may use up the whole limit.
+    let mut last = &b"abcd"[..];
+    line.clear();
+    assert_eq!(last.read_line_limited(&mut line, 4).unwrap(), 4);
+    assert_eq!(line, "abcd");
+    assert_eq!(last.read_line_limited(&mut line, 4).unwrap(), 0);
+
+    // The end of the stream is not a line too long, even with no room.
+    let mut empty = &b""[..];
+    assert_eq!(empty.read_line_limited(&mut line, 0).unwrap(), 0);
+}
+
+#[test]
+fn read_until_slice() {
+    // Lines are split across the two halves of the chain.
+    let mut r = (&b"ab\nab"[..]).chain(&b"cd\nabcd"[..]);
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read_until_slice(b'\n', &mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"ab\n");
+
+    let err = r.read_until_slice(b'\n', &mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(&buf, b"abcd");
+    // The rest of the long line is still in the stream.
+    assert_eq!(r.read_until_slice(b'\n', &mut buf).unwrap(), 1);
+    assert_eq!(buf[0], b'\n');
+
+    // A line which exactly fills the buffer at EOF is not an error.
+    assert_eq!(r.read_until_slice(b'\n', &mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"abcd");
+    assert_eq!(r.read_until_slice(b'\n', &mut buf).unwrap(), 0);
+
+    let mut one = &b"x"[..];
+    assert_eq!(
+        one.read_until_slice(b'\n', &mut []).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(one.read_until_slice(b'\n', &mut buf).unwrap(), 1);
+    assert_eq!((&b""[..]).read_until_slice(b'\n', &mut []).unwrap(), 0);
+}
+
+#[test]
+fn read_line_slice() {
+    let mut r = (&b"ab\nabc"[..]).chain(&b"def\nxy\nlast"[..]);
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read_line_slice(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"ab\n");
+
+    // The whole long line is skipped, across the two halves of the chain.
+    let err = r.read_line_slice(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(&buf, b"abcd");
+    assert_eq!(r.read_line_slice(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"xy\n");
+
+    assert_eq!(r.read_line_slice(&mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"last");
+    assert_eq!(r.read_line_slice(&mut buf).unwrap(), 0);
+
+    // A long last line without a newline is skipped up to EOF.
+    let mut r = &b"abcdef"[..];
+    assert!(r.read_line_slice(&mut buf).is_err());
+    assert!(r.is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn lines_with_offsets() {
+    let buf = Cursor::new(&b"12\r\n\n\xFF\n34"[..]);
+    let mut s = buf.lines_with_offsets();
+    assert_eq!(s.next().unwrap().unwrap(), (0, "12".to_string()));
+    assert_eq!(s.next().unwrap().unwrap(), (4, String::new()));
+    // The invalid line is still counted.
+    assert_eq!(
+        s.next().unwrap().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(s.offset(), 7);
+    assert_eq!(s.next().unwrap().unwrap(), (7, "34".to_string()));
+    assert!(s.next().is_none());
+    assert_eq!(s.offset(), 9);
+}
+
+#[test]
+fn write_all_flushing() {
+    // Accepts at most 3 bytes per write and records the total at each flush.
+    #[derive(Default)]
+    struct Flushes {
+        written: usize,
+        flushed_at: [usize; 4],
+        flushes: usize,
+    }
+
+    impl Write for Flushes {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            let n = buf.len().min(3);
+            self.written += n;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            self.flushed_at[self.flushes] = self.written;
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    let mut w = Flushes::default();
+    w.write_all_flushing(&[0u8; 10], 4).unwrap();
+    assert_eq!(w.flushes, 3);
+    assert_eq!(&w.flushed_at[..3], &[4, 8, 10]);
+
+    w.write_all_flushing(&[], 4).unwrap();
+    assert_eq!(w.flushes, 3);
+
+    let mut out = [0u8; 5];
+    let err = (&mut out[..])
+        .write_all_flushing(b"abcdefgh", 4)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(&out, b"abcde");
+}
+
+#[test]
+fn expect_len() {
+    let mut r = (&b"abcdef"[..]).expect_len(4);
+    assert_eq!(r.size_hint(), (4, Some(4)));
+    let mut buf = [0u8; 3];
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(r.remaining(), 1);
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+    assert_eq!(r.into_inner(), b"ef");
+
+    let mut r = (&b"ab"[..]).chain(&b"c"[..]).expect_len(5);
+    r.consume(1);
+    assert_eq!(r.fill_buf().unwrap(), b"b");
+    r.consume(1);
+    assert_eq!(r.fill_buf().unwrap(), b"c");
+    r.consume(1);
+    assert_eq!(r.fill_buf().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(
+        r.read(&mut buf).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert_eq!(r.read(&mut []).unwrap(), 0);
+    assert_eq!(r.remaining(), 2);
+
+    let mut r = (&b"abc"[..]).expect_len(4);
+    assert_eq!(
+        r.read_exact(&mut [0u8; 4]).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn expect_write_len() {
+    use crate::ExpectWriteLen;
+
+    let mut out = [0u8; 8];
+    let mut w = ExpectWriteLen::new(&mut out[..], 4);
+    w.write_all(b"ab").unwrap();
+    assert_eq!(
+        w.write_vectored(&[IoSlice::new(b"c"), IoSlice::new(b"d")])
+            .unwrap(),
+        2
+    );
+    assert_eq!(w.written(), 4);
+    assert_eq!(w.finish().unwrap().len(), 4);
+
+    let mut w = ExpectWriteLen::new(&mut out[..], 1);
+    w.write_all(b"ab").unwrap();
+    assert_eq!(w.finish().unwrap_err().kind(), ErrorKind::InvalidData);
+
+    // Only the bytes accepted by the underlying writer count.
+    let mut small = [0u8; 1];
+    let mut w = ExpectWriteLen::new(&mut small[..], 2);
+    assert_eq!(w.write(b"ab").unwrap(), 1);
+    assert_eq!(w.finish().unwrap_err().kind(), ErrorKind::InvalidData);
+
+    assert!(ExpectWriteLen::new(&mut out[..], 0).finish().is_ok());
+}
+
+#[test]
+fn read_vectored_coalesced() {
+    // Counts the calls to `read`, which has no vectored implementation.
+    struct Counting<'a> {
+        data: &'a [u8],
+        reads: usize,
+    }
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.reads += 1;
+            self.data.read(buf)
+        }
+    }
+
+    let mut r = Counting {
+        data: b"abcdefgh",
+        reads: 0,
+    };
+    let (mut a, mut b, mut c) = ([0u8; 0], [0u8; 2], [0u8; 3]);
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    assert_eq!(r.read_vectored_coalesced(&mut bufs).unwrap(), 5);
+    assert_eq!(r.reads, 1);
+    assert_eq!((&b, &c), (b"ab", b"cde"));
+
+    // Short reads only fill the leading buffers.
+    let (mut b, mut c) = ([0u8; 2], [0u8; 3]);
+    let mut bufs = [IoSliceMut::new(&mut b), IoSliceMut::new(&mut c)];
+    assert_eq!(r.read_vectored_coalesced(&mut bufs).unwrap(), 3);
+    assert_eq!(r.read_vectored_coalesced(&mut bufs).unwrap(), 0);
+    assert_eq!((&b, &c[..1]), (b"fg", &b"h"[..]));
+
+    // A large first buffer is read into directly.
+    let mut r = Counting {
+        data: &[1; 600],
+        reads: 0,
+    };
+    let (mut big, mut small) = ([0u8; 512], [0u8; 4]);
+    let mut bufs = [IoSliceMut::new(&mut big), IoSliceMut::new(&mut small)];
+    assert_eq!(r.read_vectored_coalesced(&mut bufs).unwrap(), 512);
+    assert_eq!(small, [0; 4]);
+
+    // More than 512 bytes of small buffers are filled up to the cap.
+    let mut r = Counting {
+        data: &[7; 1000],
+        reads: 0,
+    };
+    let mut chunks = [[0u8; 100]; 6];
+    let mut bufs = chunks.each_mut().map(|c| IoSliceMut::new(&mut c[..]));
+    assert_eq!(r.read_vectored_coalesced(&mut bufs).unwrap(), 512);
+    assert_eq!(chunks[5][..12], [7; 12]);
+    assert_eq!(chunks[5][12], 0);
+}
+
+#[test]
+fn write_vectored_gathered() {
+    // Records the calls to `write`, which has no vectored implementation.
+    struct Counting<'a> {
+        dst: &'a mut [u8],
+        writes: usize,
+    }
+
+    impl Write for Counting<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.writes += 1;
+            let n = cmp::min(buf.len(), self.dst.len());
+            self.dst[..n].copy_from_slice(&buf[..n]);
+            self.dst = &mut core::mem::take(&mut self.dst)[n..];
+            Ok(n)
         }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut dst = [0u8; 8];
+    let mut w = Counting {
+        dst: &mut dst,
+        writes: 0,
+    };
+    let bufs = [IoSlice::new(b""), IoSlice::new(b"ab"), IoSlice::new(b"cde")];
+    assert_eq!(w.write_vectored_gathered(&bufs).unwrap(), 5);
+    assert_eq!(w.writes, 1);
+
+    // Short writes only take the leading bytes.
+    let bufs = [IoSlice::new(b"fg"), IoSlice::new(b"hij")];
+    assert_eq!(w.write_vectored_gathered(&bufs).unwrap(), 3);
+    assert_eq!(w.write_vectored_gathered(&bufs).unwrap(), 0);
+    assert_eq!(&dst, b"abcdefgh");
+
+    // A large first buffer is written directly.
+    let mut dst = [0u8; 1100];
+    let mut w = Counting {
+        dst: &mut dst,
+        writes: 0,
+    };
+    let big = [1u8; 512];
+    let bufs = [IoSlice::new(&big), IoSlice::new(b"small")];
+    assert_eq!(w.write_vectored_gathered(&bufs).unwrap(), 512);
+
+    // More than 512 bytes of small buffers are written up to the cap.
+    let chunks = [[7u8; 100]; 6];
+    let bufs = chunks.each_ref().map(|c| IoSlice::new(&c[..]));
+    assert_eq!(w.write_vectored_gathered(&bufs).unwrap(), 512);
+    assert_eq!(w.writes, 2);
+    assert!(dst[512..1024].iter().all(|&b| b == 7));
+    assert_eq!(dst[1024], 0);
+}
+
+#[test]
+fn append_only() {
+    use crate::AppendOnly;
+
+    let mut buf = [0u8; 8];
+    let mut c = Cursor::new(&mut buf[..]);
+    c.write_all(b"ab").unwrap();
+    let mut w = AppendOnly::new(c).unwrap();
+    assert_eq!(w.high_water_mark(), 2);
+
+    w.write_all(b"cd").unwrap();
+    for pos in [SeekFrom::Start(3), SeekFrom::Current(-1), SeekFrom::End(-5)] {
+        assert_eq!(w.seek(pos).unwrap_err().kind(), ErrorKind::Unsupported);
+        assert_eq!(w.stream_position().unwrap(), 4);
+        assert_eq!(w.get_ref().position(), 4);
+    }
+
+    // Forward seeks, and seeks back to the high-water mark, are allowed.
+    assert_eq!(w.seek(SeekFrom::End(-2)).unwrap(), 6);
+    assert_eq!(w.seek(SeekFrom::Start(4)).unwrap(), 4);
+    assert_eq!(w.seek(SeekFrom::Current(1)).unwrap(), 5);
+    w.write_all(b"e").unwrap();
+    assert_eq!(w.high_water_mark(), 6);
+    assert!(w.seek(SeekFrom::Start(5)).is_err());
+    assert!(w.seek(SeekFrom::Current(i64::MIN)).is_err());
+
+    w.into_inner();
+    assert_eq!(&buf, b"abcd\0e\0\0");
+}
+
+#[test]
+fn append_only_failed_restore() {
+    use crate::AppendOnly;
+
+    // A cursor whose absolute seeks can be made to fail.
+    struct NoRestore<'a> {
+        inner: Cursor<&'a mut [u8]>,
+        fail_start: bool,
+    }
+
+    impl Seek for NoRestore<'_> {
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            match pos {
+                SeekFrom::Start(_) if self.fail_start => Err(ErrorKind::Other.into()),
+                _ => self.inner.seek(pos),
+            }
+        }
+    }
+
+    impl Write for NoRestore<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.inner.write(buf)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut buf = [0u8; 8];
+    let mut w = AppendOnly::new(NoRestore {
+        inner: Cursor::new(&mut buf[..]),
+        fail_start: false,
+    })
+    .unwrap();
+    w.write_all(b"abcd").unwrap();
+
+    // Seeking back to the original position fails, leaving the writer at 2.
+    w.get_mut().fail_start = true;
+    assert_eq!(
+        w.seek(SeekFrom::End(-6)).unwrap_err().kind(),
+        ErrorKind::Other
+    );
+    assert_eq!(w.get_ref().inner.position(), 2);
+    assert!(w.write(b"x").is_err());
+    assert!(w.write_vectored(&[IoSlice::new(b"x")]).is_err());
+
+    // Seeking at or after the high-water mark makes writing possible again.
+    assert_eq!(w.seek(SeekFrom::End(-4)).unwrap(), 4);
+    w.write_all(b"e").unwrap();
+
+    w.into_inner();
+    assert_eq!(&buf, b"abcde\0\0\0");
+}
+
+#[test]
+fn high_water_writer() {
+    use crate::HighWaterWriter;
+
+    let mut buf = [0u8; 8];
+    let mut c = Cursor::new(&mut buf[..]);
+    c.set_position(2);
+    let mut w = HighWaterWriter::new(c).unwrap();
+    assert_eq!(w.high_water(), 0);
+
+    // Seeking alone, and empty writes, leave the mark alone.
+    assert_eq!(w.seek(SeekFrom::End(0)).unwrap(), 8);
+    assert_eq!(w.write(b"").unwrap(), 0);
+    assert_eq!(w.high_water(), 0);
+
+    w.seek(SeekFrom::Start(5)).unwrap();
+    w.write_vectored(&[IoSlice::new(b"a"), IoSlice::new(b"b")])
+        .unwrap();
+    assert_eq!(w.high_water(), 7);
+    w.seek(SeekFrom::Current(-6)).unwrap();
+    w.write_all(b"xy").unwrap();
+    assert_eq!(w.stream_position().unwrap(), 3);
+    assert_eq!(w.high_water(), 7);
+
+    // Writes at the end of a full buffer do not count.
+    w.seek(SeekFrom::End(0)).unwrap();
+    assert!(w.write_all(b"z").is_err());
+    assert_eq!(w.high_water(), 7);
+
+    w.into_inner();
+    assert_eq!(&buf, b"\0xy\0\0ab\0");
+}
+
+#[test]
+fn observed() {
+    use core::cell::Cell;
+
+    #[derive(Default)]
+    struct Log {
+        read: Cell<usize>,
+        written: Cell<usize>,
+        flushes: Cell<usize>,
+    }
+
+    impl crate::IoObserver for Log {
+        fn on_read(&self, n: usize) {
+            self.read.set(self.read.get() + n);
+        }
+
+        fn on_write(&self, n: usize) {
+            self.written.set(self.written.get() + n);
+        }
+
+        fn on_flush(&self) {
+            self.flushes.set(self.flushes.get() + 1);
+        }
+    }
+
+    let log = Log::default();
+    let mut buf = [0u8; 8];
+    let mut stream = crate::Observed::new(Cursor::new(&mut buf[..]), &log);
+
+    stream.write_all(b"abcd").unwrap();
+    stream.flush().unwrap();
+    assert_eq!(log.written.get(), 4);
+    assert_eq!(log.flushes.get(), 1);
+
+    assert_eq!(stream.seek(SeekFrom::Start(1)).unwrap(), 1);
+    let mut out = [0u8; 2];
+    stream.read_exact(&mut out).unwrap();
+    assert_eq!(&out, b"bc");
+    assert_eq!(log.read.get(), 2);
+
+    assert_eq!(stream.fill_buf().unwrap(), b"d\0\0\0\0");
+    stream.consume(3);
+    assert_eq!(log.read.get(), 5);
+    assert_eq!(stream.stream_position().unwrap(), 6);
+
+    // The position and seek support of the wrapped stream are passed on.
+    assert_eq!(stream.known_position(), Some(6));
+    assert!(stream.supports_end_seek());
+
+    // Failed operations are not reported.
+    assert!(stream.write_all(b"xyz").is_err());
+    assert_eq!(log.written.get(), 6);
+}
+
+#[test]
+fn supports_end_seek() {
+    // A stream which can only move forwards from where it is.
+    struct Forward(u64);
+
+    impl Seek for Forward {
+        fn seek(&mut self, pos: SeekFrom) -> crate::Result<u64> {
+            match pos {
+                SeekFrom::Current(n) if n >= 0 => self.0 += n as u64,
+                _ => return Err(crate::ErrorKind::InvalidInput.into()),
+            }
+            Ok(self.0)
+        }
+
+        fn supports_end_seek(&self) -> bool {
+            false
+        }
+    }
+
+    assert!(Cursor::new([0u8; 4]).supports_end_seek());
+    assert!(!Forward(0).supports_end_seek());
+    assert!(!crate::CachedSeek::new(Forward(0))
+        .unwrap()
+        .supports_end_seek());
+
+    #[cfg(feature = "alloc")]
+    {
+        use crate::BufWriter;
+
+        assert!(BufReader::new(Cursor::new(&b"ab"[..])).supports_end_seek());
+        let mut out = [0u8; 4];
+        assert!(BufWriter::new(Cursor::new(&mut out[..])).supports_end_seek());
     }
 }
@@ -0,0 +1,130 @@
+use crate::{
+    BufRead, DualSliceReader, DualSliceWriter, ErrorKind, Read, ReadReady, Result, Seek, SeekFrom,
+    SlicesReader, Write,
+};
+
+#[test]
+fn slices_read() -> Result<()> {
+    let fragments: [&[u8]; 5] = [b"", b"abc", b"", b"d", b"efg"];
+    let mut r = SlicesReader::new(&fragments);
+    assert_eq!(r.size_hint(), (7, Some(7)));
+
+    let mut buf = [0u8; 5];
+    assert_eq!(r.read(&mut buf)?, 5);
+    assert_eq!(&buf, b"abcde");
+    assert_eq!(r.size_hint(), (2, Some(2)));
+    assert!(r.read_ready()?);
+    assert_eq!(r.read(&mut buf)?, 2);
+    assert_eq!(&buf[..2], b"fg");
+    assert_eq!(r.read(&mut buf)?, 0);
+    assert!(!r.read_ready()?);
+    assert_eq!(r.remaining(), 0);
+    Ok(())
+}
+
+#[test]
+fn slices_bufread() -> Result<()> {
+    let fragments: [&[u8]; 4] = [b"ab", b"", b"cde", b""];
+    let mut r = SlicesReader::new(&fragments);
+    assert_eq!(r.fill_buf()?, b"ab");
+    r.consume(1);
+    assert_eq!(r.fill_buf()?, b"b");
+    r.consume(1);
+    assert_eq!(r.fill_buf()?, b"cde");
+    // Consuming more than is available stays within the fragment.
+    r.consume(10);
+    assert_eq!(r.fill_buf()?, b"");
+    assert_eq!(r.remaining(), 0);
+
+    let mut empty = SlicesReader::new(&[]);
+    assert_eq!(empty.fill_buf()?, b"");
+    assert_eq!(empty.size_hint(), (0, Some(0)));
+    Ok(())
+}
+
+#[test]
+fn slices_seek() -> Result<()> {
+    let fragments: [&[u8]; 4] = [b"abc", b"", b"de", b"fgh"];
+    let mut r = SlicesReader::new(&fragments);
+    assert_eq!(r.len(), 8);
+
+    let mut buf = [0u8; 2];
+    assert_eq!(r.seek(SeekFrom::Start(4))?, 4);
+    r.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"ef");
+    assert_eq!(r.stream_position()?, 6);
+
+    assert_eq!(r.seek(SeekFrom::Current(-4))?, 2);
+    r.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"cd");
+
+    assert_eq!(r.seek(SeekFrom::End(-1))?, 7);
+    assert_eq!(r.fill_buf()?, b"h");
+    assert_eq!(r.stream_len()?, 8);
+    assert_eq!(r.stream_position()?, 7);
+
+    // Fragment boundaries land at the start of the next fragment.
+    assert_eq!(r.seek(SeekFrom::Start(3))?, 3);
+    assert_eq!(r.fill_buf()?, b"de");
+
+    assert_eq!(
+        r.seek(SeekFrom::Current(-4)).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    assert_eq!(r.position(), 3);
+    Ok(())
+}
+
+#[test]
+fn slices_seek_past_end() -> Result<()> {
+    let fragments: [&[u8]; 2] = [b"ab", b"cd"];
+    let mut r = SlicesReader::new(&fragments);
+    assert_eq!(r.seek(SeekFrom::Start(10))?, 4);
+    assert_eq!(r.read(&mut [0; 4])?, 0);
+    assert_eq!(r.seek(SeekFrom::End(1))?, 4);
+
+    r.set_clamp_seeks(false);
+    r.seek(SeekFrom::Start(1))?;
+    assert_eq!(
+        r.seek(SeekFrom::Start(5)).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    assert_eq!(r.position(), 1);
+    assert_eq!(r.seek(SeekFrom::End(0))?, 4);
+    Ok(())
+}
+
+#[test]
+fn dual_slice_read() -> Result<()> {
+    let mut r = DualSliceReader::new(b"abc", b"de");
+    assert_eq!(r.size_hint(), (5, Some(5)));
+
+    let mut buf = [0u8; 4];
+    assert_eq!(r.read(&mut buf)?, 4);
+    assert_eq!(&buf, b"abcd");
+    assert_eq!(r.position(), 4);
+    assert_eq!(r.fill_buf()?, b"e");
+    r.consume(5);
+    assert_eq!(r.remaining(), 0);
+    assert!(!r.read_ready()?);
+    assert_eq!(r.read(&mut buf)?, 0);
+
+    // An empty first slice goes straight to the second.
+    let mut r = DualSliceReader::new(b"", b"xy");
+    assert_eq!(r.fill_buf()?, b"xy");
+    Ok(())
+}
+
+#[test]
+fn dual_slice_write() {
+    let (mut a, mut b) = ([0u8; 2], [0u8; 3]);
+    let mut w = DualSliceWriter::new(&mut a, &mut b);
+    assert_eq!(w.write(b"a").unwrap(), 1);
+    assert_eq!(w.write(b"bcd").unwrap(), 3);
+    assert_eq!(w.remaining(), 1);
+    assert_eq!(w.write(b"ef").unwrap(), 1);
+    assert_eq!(w.write(b"g").unwrap(), 0);
+    assert_eq!(w.write_all(b"g").unwrap_err().kind(), ErrorKind::WriteZero);
+    assert_eq!(w.written(), 5);
+    assert_eq!((&a, &b), (b"ab", b"cde"));
+}
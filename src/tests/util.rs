@@ -1,8 +1,9 @@
+#[cfg(feature = "alloc")]
 use core::cmp::{max, min};
 
-use crate::{copy, empty, prelude::*, repeat, sink, Empty, Repeat, Result, SeekFrom, Sink};
+use crate::{copy, empty, prelude::*, repeat, sink, Empty, Repeat, SeekFrom, Sink};
 #[cfg(feature = "alloc")]
-use crate::{BufWriter, DEFAULT_BUF_SIZE};
+use crate::{BufWriter, Result, DEFAULT_BUF_SIZE};
 
 #[test]
 fn copy_copies() {
@@ -17,12 +18,14 @@ fn copy_copies() {
     );
 }
 
+#[cfg(feature = "alloc")]
 struct ShortReader {
     cap: usize,
     read_size: usize,
     observed_buffer: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl Read for ShortReader {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         let bytes = min(self.cap, self.read_size);
@@ -32,10 +35,12 @@ impl Read for ShortReader {
     }
 }
 
+#[cfg(feature = "alloc")]
 struct WriteObserver {
     observed_buffer: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl Write for WriteObserver {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         self.observed_buffer = max(self.observed_buffer, buf.len());
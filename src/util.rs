@@ -2,7 +2,10 @@
 
 use core::fmt;
 
-use crate::{BufRead, ErrorKind, IoSlice, IoSliceMut, Read, Result, Seek, SeekFrom, Write};
+use crate::{
+    BufRead, ErrorKind, IoSlice, IoSliceMut, Read, ReadReady, Result, Seek, SeekFrom,
+    StreamLenError, Write,
+};
 
 /// A reader which is always at EOF.
 ///
@@ -47,6 +50,13 @@ impl Read for Empty {
     }
 }
 
+impl ReadReady for Empty {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+}
+
 impl BufRead for Empty {
     #[inline]
     fn fill_buf(&mut self) -> Result<&[u8]> {
@@ -65,6 +75,10 @@ impl Seek for Empty {
         Ok(0)
     }
 
+    fn stream_len_checked(&mut self) -> core::result::Result<u64, StreamLenError> {
+        Ok(0)
+    }
+
     fn stream_position(&mut self) -> Result<u64> {
         Ok(0)
     }
@@ -132,6 +146,8 @@ impl Read for Repeat {
     }
 }
 
+impl ReadReady for Repeat {}
+
 impl fmt::Debug for Repeat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Repeat").finish_non_exhaustive()
@@ -253,10 +269,10 @@ impl fmt::Debug for Sink {
 ///     Ok(())
 /// }
 /// ```
-pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> Result<u64>
+pub fn copy<R, W>(reader: &mut R, writer: &mut W) -> Result<u64>
 where
-    R: Read,
-    W: Write,
+    R: Read + ?Sized,
+    W: Write + ?Sized,
 {
     // TODO(dataphract): std::io provides a specialized copy impl for BufWriter,
     // but we don't have access to specialization. Since copy_to is not a method,